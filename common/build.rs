@@ -0,0 +1,51 @@
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+use blake2b_ref::Blake2bBuilder;
+
+// Constants
+const CKB_HASH_PERSONALIZATION: &[u8] = b"ckb-default-hash"; // The same personalization used by CKB for data hashes.
+const HASH_LEN: usize = 32; // Number of bytes for a Blake2b 256-bit hash.
+const SECP256K1_LIB_PATH: &str = "../deps/secp256k1_blake2b_sighash_all_dual"; // The secp256k1 library which is loaded dynamically.
+const SECP256K1_LIB_HASH_PATH: &str = "../deps/secp256k1_blake2b_sighash_all_dual.blake2b"; // The pinned data hash of the released secp256k1 library.
+const SECP256K1_LIB_HASH: &str = include_str!("../deps/secp256k1_blake2b_sighash_all_dual.blake2b");
+
+// Pin the data hash of the released secp256k1 library, and check the library in the deps directory against it, so only that exact library can be loaded.
+fn main()
+{
+	if env::var_os("CARGO_FEATURE_SIGNATURE").is_none()
+	{
+		return;
+	}
+
+	println!("cargo:rerun-if-changed={}", SECP256K1_LIB_PATH);
+	println!("cargo:rerun-if-changed={}", SECP256K1_LIB_HASH_PATH);
+
+	let expected = SECP256K1_LIB_HASH.trim();
+	if expected.len() != HASH_LEN * 2
+	{
+		panic!("{} must contain the hex data hash of the released secp256k1 library", SECP256K1_LIB_HASH_PATH);
+	}
+
+	let library = match fs::read(SECP256K1_LIB_PATH)
+	{
+		Ok(library) => library,
+		Err(_) => panic!("{} is missing, run scripts/fetch-deps.sh to build and check it", SECP256K1_LIB_PATH),
+	};
+
+	let mut hasher = Blake2bBuilder::new(HASH_LEN).personal(CKB_HASH_PERSONALIZATION).build();
+	hasher.update(&library);
+	let mut hash = [0u8; HASH_LEN];
+	hasher.finalize(&mut hash);
+
+	let actual: String = hash.iter().map(|byte| format!("{:02x}", byte)).collect();
+	if !actual.eq_ignore_ascii_case(expected)
+	{
+		panic!("the data hash of {} is {}, but {} is pinned", SECP256K1_LIB_PATH, actual, expected);
+	}
+
+	let code = format!("/// The data hash of the secp256k1 library.\npub const CODE_HASH_SECP256K1_LIB: [u8; {}] = {:?};\n", HASH_LEN, hash);
+	let out_dir = PathBuf::from(env::var("OUT_DIR").expect("OUT_DIR"));
+	fs::write(out_dir.join("code_hashes.rs"), code).expect("write code hashes");
+}
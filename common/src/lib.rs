@@ -0,0 +1,13 @@
+//! Helpers shared by the Token Buy Lock, Token Sell Lock and Token Swap Lock contracts.
+//!
+//! Each lock stores the owner lock hash in the cell data, so the same owner mode and per owner grouping is used by all of them.
+//! The `signature` feature adds secp256k1 signature verification through the library deployed on chain, which is shared with the SUDT type script.
+
+#![no_std]
+
+extern crate alloc;
+
+pub mod owner;
+pub mod requirements;
+#[cfg(feature = "signature")]
+pub mod signature;
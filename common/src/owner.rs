@@ -0,0 +1,35 @@
+// Import from alloc core instead of from std since we are in no-std mode.
+use alloc::collections::BTreeSet;
+use alloc::vec::Vec;
+use core::result::Result;
+
+// Import CKB syscalls and structures.
+use ckb_std::ckb_constants::Source;
+use ckb_std::high_level::{load_cell_data, load_cell_lock_hash, QueryIter};
+
+/// Find the lock hashes of all inputs.
+pub fn load_input_lock_hashes() -> BTreeSet<Vec<u8>>
+{
+	QueryIter::new(load_cell_lock_hash, Source::Input).map(|lock_hash| lock_hash.to_vec()).collect()
+}
+
+/// Determine if owner mode is enabled for the whole script group.
+///
+/// Multiple cells can have multiple owners since the owner hash is stored in data and not in the args.
+/// All owners must be checked, and owner mode is only enabled when every owner has a cell in the inputs.
+/// The owner lock hash is extracted from the data of each cell with the specified function.
+pub fn check_group_owner_mode<E, F>(parse_owner_lock_hash: F) -> Result<bool, E>
+	where F: Fn(&[u8]) -> Result<Vec<u8>, E>
+{
+	// Find all the unique owners.
+	let mut owner_lock_hashes = BTreeSet::new();
+	for data in QueryIter::new(load_cell_data, Source::GroupInput)
+	{
+		owner_lock_hashes.insert(parse_owner_lock_hash(&data)?);
+	}
+
+	// Check if all owner lock hashes are present in the input lock hashes, indicating owner mode.
+	let input_lock_hashes = load_input_lock_hashes();
+
+	Ok(owner_lock_hashes.is_subset(&input_lock_hashes))
+}
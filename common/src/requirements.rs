@@ -0,0 +1,87 @@
+// Import from alloc core instead of from std since we are in no-std mode.
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+use core::result::Result;
+
+// Import CKB syscalls and structures.
+use ckb_std::ckb_constants::Source;
+use ckb_std::ckb_types::{bytes::Bytes, prelude::*};
+use ckb_std::error::SysError;
+use ckb_std::high_level::{load_script, load_cell_data, load_cell_lock, QueryIter};
+
+use crate::owner::load_input_lock_hashes;
+
+/// The total amount required for a key is too large to be represented.
+///
+/// Each contract converts this to its own overflow error.
+pub struct Overflow;
+
+/// An amount of tokens or capacity which can be totalled.
+pub trait Amount: Copy
+{
+	const ZERO: Self;
+
+	fn checked_add(self, other: Self) -> Option<Self>;
+}
+
+impl Amount for u64
+{
+	const ZERO: Self = 0;
+
+	fn checked_add(self, other: Self) -> Option<Self>
+	{
+		u64::checked_add(self, other)
+	}
+}
+
+impl Amount for u128
+{
+	const ZERO: Self = 0;
+
+	fn checked_add(self, other: Self) -> Option<Self>
+	{
+		u128::checked_add(self, other)
+	}
+}
+
+/// Add an amount to the total required for a key, adding the key if it doesn't already exist.
+pub fn add_requirement<K: Ord, A: Amount>(requirements: &mut BTreeMap<K, A>, key: K, amount: A) -> Result<(), Overflow>
+{
+	let total_amount = requirements.entry(key).or_insert(A::ZERO);
+	*total_amount = total_amount.checked_add(amount).ok_or(Overflow)?;
+
+	Ok(())
+}
+
+/// Calculate the amounts required by all cells which use the code hash and hash type of the current script, grouped by args and then by owner lock hash.
+///
+/// Cells with different args run in other script groups, which each count the same outputs. Summing every group stops one payment from settling orders in several groups.
+/// A group is skipped when every one of its owners has a cell in the inputs, since it runs in owner mode and requires no payment.
+/// The owner lock hash and required amount are extracted from the data of each cell with the specified function.
+pub fn calculate_lock_requirements<A, E, F>(parse_order_data: F) -> Result<BTreeMap<Vec<u8>, BTreeMap<Vec<u8>, A>>, E>
+	where A: Amount, E: From<Overflow> + From<SysError>, F: Fn(&[u8]) -> Result<(Vec<u8>, A), E>
+{
+	let script = load_script()?;
+
+	// Sum the requirements of every cell with the same code hash and hash type, grouped by args.
+	let mut group_requirements: BTreeMap<Vec<u8>, BTreeMap<Vec<u8>, A>> = BTreeMap::new();
+	for (i, lock_script) in QueryIter::new(load_cell_lock, Source::Input).enumerate()
+	{
+		if lock_script.code_hash().as_slice() != script.code_hash().as_slice() || lock_script.hash_type().as_slice() != script.hash_type().as_slice()
+		{
+			continue;
+		}
+
+		let args: Bytes = lock_script.args().unpack();
+		let (owner_lock_hash, amount) = parse_order_data(&load_cell_data(i, Source::Input)?)?;
+		add_requirement(group_requirements.entry(args.to_vec()).or_insert_with(BTreeMap::new), owner_lock_hash, amount)?;
+	}
+
+	// Remove the groups which are in owner mode.
+	let input_lock_hashes = load_input_lock_hashes();
+	let requirements = group_requirements.into_iter()
+		.filter(|(_, owner_requirements)| !owner_requirements.keys().all(|owner_lock_hash| input_lock_hashes.contains(owner_lock_hash)))
+		.collect();
+
+	Ok(requirements)
+}
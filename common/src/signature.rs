@@ -0,0 +1,60 @@
+// Import from alloc core instead of from std since we are in no-std mode.
+use alloc::vec;
+use core::result::Result;
+
+// Import CKB syscalls and structures.
+use ckb_std::dynamic_loading::{CKBDLContext, Symbol};
+use ckb_std::error::SysError;
+use ckb_std::high_level::load_tx_hash;
+
+// Import the data hash of the secp256k1 library, which is generated by the build script.
+include!(concat!(env!("OUT_DIR"), "/code_hashes.rs"));
+
+// Constants
+pub const PUBKEY_HASH_LEN: usize = 20; // A blake160 hash is the first 20 bytes of the Blake2b hash of a compressed public key.
+pub const SIGNATURE_LEN: usize = 65; // A recoverable signature is r and s followed by the recovery id.
+const LIBRARY_LEN: usize = 128 * 1024; // The memory reserved for loading the secp256k1 library.
+const SECP256K1_DATA_LEN: usize = 1_048_576; // The precomputed table which the secp256k1 library loads from the secp256k1_data cell dep.
+
+// Functions exported by the secp256k1 library.
+type LoadPrefilledData = unsafe extern "C" fn(data: *mut u8, len: *mut u64) -> i32;
+type ValidateSignature = unsafe extern "C" fn(prefilled_data: *const u8, signature: *const u8, signature_len: u64, message: *const u8, message_len: u64, output: *mut u8, output_len: *mut u64) -> i32;
+
+/// Verify that a recoverable secp256k1 signature over the transaction hash was made by the key with the specified blake160 public key hash.
+///
+/// The message is the raw transaction hash, not the sighash-all message of the default lock, so the signature covers the transaction but not its witnesses.
+/// Signatures are recovered by the secp256k1 library deployed on chain, which must be in the cell deps along with the secp256k1_data cell.
+/// A malformed or mismatched signature is not valid, and a missing library or data cell is an error.
+pub fn verify_signature(pubkey_hash: &[u8], signature: &[u8]) -> Result<bool, SysError>
+{
+	if signature.len() != SIGNATURE_LEN
+	{
+		return Ok(false);
+	}
+
+	// Load the secp256k1 library from the cell deps by its data hash.
+	let mut context = unsafe { CKBDLContext::<[u8; LIBRARY_LEN]>::new() };
+	let library = context.load(&CODE_HASH_SECP256K1_LIB).map_err(|_| SysError::ItemMissing)?;
+	let load_prefilled_data: Symbol<LoadPrefilledData> = unsafe { library.get(b"load_prefilled_data") }.ok_or(SysError::ItemMissing)?;
+	let validate_signature: Symbol<ValidateSignature> = unsafe { library.get(b"validate_signature") }.ok_or(SysError::ItemMissing)?;
+
+	// Load the precomputed table from the secp256k1_data cell dep.
+	let mut prefilled_data = vec![0u8; SECP256K1_DATA_LEN];
+	let mut prefilled_data_len = SECP256K1_DATA_LEN as u64;
+	if unsafe { load_prefilled_data(prefilled_data.as_mut_ptr(), &mut prefilled_data_len) } != 0
+	{
+		return Err(SysError::ItemMissing);
+	}
+
+	// Recover the public key which signed the transaction hash, which the library returns as a blake160 hash.
+	let tx_hash = load_tx_hash()?;
+	let mut output = [0u8; PUBKEY_HASH_LEN];
+	let mut output_len = PUBKEY_HASH_LEN as u64;
+	let result = unsafe { validate_signature(prefilled_data.as_ptr(), signature.as_ptr(), signature.len() as u64, tx_hash.as_ptr(), tx_hash.len() as u64, output.as_mut_ptr(), &mut output_len) };
+	if result != 0 || output_len != PUBKEY_HASH_LEN as u64
+	{
+		return Ok(false);
+	}
+
+	Ok(output[..] == pubkey_hash[..])
+}
@@ -0,0 +1,72 @@
+// Import from core instead of from std since we are in no-std mode.
+use core::result::Result;
+
+// Import CKB syscalls and structures.
+// https://nervosnetwork.github.io/ckb-std/riscv64imac-unknown-none-elf/doc/ckb_std/index.html
+use ckb_std::ckb_constants::Source;
+use ckb_std::ckb_types::{bytes::Bytes, prelude::*};
+use ckb_std::high_level::{load_script, load_cell_lock_hash, load_cell_data, QueryIter};
+
+// Import our local error codes.
+use crate::error::Error;
+
+// Constants
+const LOCK_HASH_LEN: usize = 32; // Number of bytes for a lock hash. (Blake2b 256-bit 32 bytes)
+const PRICE_LEN: usize = 16; // Each part of a price is a u128, which is 16 bytes.
+const ORACLE_DATA_LEN: usize = PRICE_LEN * 2; // A price is a u128 numerator followed by a u128 denominator.
+
+/// Determine if the owner lock hash in the args is present in the inputs.
+fn check_owner_mode(args: &Bytes) -> Result<bool, Error>
+{
+	if args.len() != LOCK_HASH_LEN
+	{
+		return Err(Error::ArgsLength);
+	}
+
+	let is_owner_mode = QueryIter::new(load_cell_lock_hash, Source::Input).any(|lock_hash| args[..] == lock_hash[..]);
+
+	Ok(is_owner_mode)
+}
+
+/// Verify that every price cell in the outputs has a valid price.
+fn validate_prices() -> Result<(), Error>
+{
+	for data in QueryIter::new(load_cell_data, Source::GroupOutput)
+	{
+		if data.len() != ORACLE_DATA_LEN
+		{
+			return Err(Error::DataLength);
+		}
+
+		let mut buffer = [0u8; PRICE_LEN];
+		buffer.copy_from_slice(&data[PRICE_LEN..ORACLE_DATA_LEN]);
+		if u128::from_le_bytes(buffer) == 0
+		{
+			return Err(Error::Denominator);
+		}
+	}
+
+	Ok(())
+}
+
+/// A mock price oracle, used to test orders which are priced by an oracle.
+///
+/// The args are the lock hash of the oracle owner, who is the only one who can create, update, or destroy price cells.
+/// The data of each price cell is a u128 numerator of tokens followed by a u128 denominator of shannons, which must not be zero.
+/// This is the layout the Token Buy Lock reads from an oracle cell dep. A real oracle would also prove where its prices come from.
+pub fn main() -> Result<(), Error>
+{
+	// Load the args of the current script.
+	let script = load_script()?;
+	let args: Bytes = script.args().unpack();
+
+	// Only the owner can change price cells.
+	if !check_owner_mode(&args)?
+	{
+		return Err(Error::Owner);
+	}
+
+	validate_prices()?;
+
+	Ok(())
+}
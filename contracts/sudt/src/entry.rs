@@ -5,20 +5,47 @@ use core::result::Result;
 // https://nervosnetwork.github.io/ckb-std/riscv64imac-unknown-none-elf/doc/ckb_std/index.html
 use ckb_std::ckb_constants::Source;
 use ckb_std::ckb_types::{bytes::Bytes, prelude::*};
-use ckb_std::high_level::{load_script, load_cell_lock_hash, load_cell_data, QueryIter};
+use ckb_std::error::SysError;
+use ckb_std::high_level::{load_script, load_cell_lock_hash, load_cell_data, load_witness_args, QueryIter};
 
 // Import our local error codes.
 use crate::error::Error;
 
+// Import the signature verification used to authorize the owner, which is shared with the Token Buy Lock.
+use token_lock_common::signature::{verify_signature, PUBKEY_HASH_LEN, SIGNATURE_LEN};
+
 // Constants
 const LOCK_HASH_LEN: usize = 32; // Number of bytes for a lock hash. (Blake2b 256-bit 32 bytes)
 const SUDT_DATA_LEN: usize = 16; // SUDT uses a u128, which is 16 bytes.
 
+/// Load the owner signature from the witness of the first cell in the script group.
+///
+/// The signature is in the input type field when the group has inputs, otherwise it is in the output type field.
+fn load_owner_signature() -> Result<Option<Bytes>, Error>
+{
+	let signature = match load_witness_args(0, Source::GroupInput)
+	{
+		Ok(witness_args) => witness_args.input_type().to_opt(),
+		Err(SysError::IndexOutOfBound) => match load_witness_args(0, Source::GroupOutput)
+		{
+			Ok(witness_args) => witness_args.output_type().to_opt(),
+			Err(SysError::IndexOutOfBound) => None,
+			Err(err) => return Err(err.into()),
+		},
+		Err(err) => return Err(err.into()),
+	};
+
+	Ok(signature.map(|signature| signature.raw_data()))
+}
+
 /// Determine if owner mode is enabled.
+///
+/// The args are the owner lock hash, optionally followed by an owner public key hash.
+/// With a public key hash, the owner can also sign the transaction hash instead of spending a cell with the owner lock.
 fn check_owner_mode(args: &Bytes) -> Result<bool, Error>
 {
-	// Verify the the arguments length matches the length of a Blake2b hash.
-	if args.len() != LOCK_HASH_LEN
+	// Verify the the arguments length matches the length of a Blake2b hash, with or without a public key hash.
+	if args.len() != LOCK_HASH_LEN && args.len() != LOCK_HASH_LEN + PUBKEY_HASH_LEN
 	{
 		return Err(Error::ArgsLength);
 	}
@@ -26,17 +53,33 @@ fn check_owner_mode(args: &Bytes) -> Result<bool, Error>
 	// Compare the Lock Script Hash from the script args with the Lock Scripts
 	// of each input cell to determine if a match exists.
 	let is_owner_mode = QueryIter::new(load_cell_lock_hash, Source::Input)
-		.find(|lock_hash|args[..]==lock_hash[..]).is_some();
+		.find(|lock_hash|args[0..LOCK_HASH_LEN]==lock_hash[..]).is_some();
+	if is_owner_mode || args.len() == LOCK_HASH_LEN
+	{
+		return Ok(is_owner_mode);
+	}
 
-	// Return the owner mode status.
-	Ok(is_owner_mode)
+	// Check for a signature by the owner public key hash. A signature which is present must be valid.
+	match load_owner_signature()?
+	{
+		Some(signature) if signature.len() == SIGNATURE_LEN =>
+		{
+			if !verify_signature(&args[LOCK_HASH_LEN..], &signature)?
+			{
+				return Err(Error::Signature);
+			}
+
+			Ok(true)
+		},
+		_ => Ok(false),
+	}
 }
 
 /// Count the number of tokens in the specified source. Source should be either GroupInput or GroupOutput.
 fn determine_token_amount(source: Source) -> Result<u128, Error>
 {
 	// Track the number of tokens that are counted.
-	let mut total_token_amount = 0;
+	let mut total_token_amount: u128 = 0;
 
 	// Cycle through the data in each cell within the specified source.
 	let cell_data = QueryIter::new(load_cell_data, source);
@@ -51,7 +94,7 @@ fn determine_token_amount(source: Source) -> Result<u128, Error>
 			let amount = u128::from_le_bytes(buffer);
 
 			// Add the amount of tokens in the cell to the total amount of tokens.
-			total_token_amount += amount;
+			total_token_amount = total_token_amount.checked_add(amount).ok_or(Error::Overflow)?;
 		}
 		// If the data is less than 16 bytes, then return an encoding error.
 		else
@@ -1,5 +1,6 @@
 // Import from alloc core instead of from std since we are in no-std mode.
 use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::vec;
 use alloc::vec::Vec;
 use core::result::Result;
 
@@ -7,95 +8,463 @@ use core::result::Result;
 // https://nervosnetwork.github.io/ckb-std/riscv64imac-unknown-none-elf/doc/ckb_std/index.html
 // use ckb_std::debug;
 use ckb_std::ckb_constants::Source;
-use ckb_std::ckb_types::{bytes::Bytes, prelude::*};
-use ckb_std::high_level::{load_script, load_cell_lock_hash, load_cell_type_hash, load_cell_data, QueryIter};
+use ckb_std::ckb_types::{bytes::Bytes, packed::Script, prelude::*};
+use ckb_std::error::SysError;
+use ckb_std::high_level::{load_script, load_cell_capacity, load_cell_lock, load_cell_lock_hash, load_cell_type_hash, load_cell_data, load_header, load_input_out_point, load_witness_args, QueryIter};
+
+// Import the owner mode and grouping shared with the other locks.
+use token_lock_common::owner::load_input_lock_hashes;
+use token_lock_common::requirements::add_requirement;
 
 // Import our local error codes.
 use crate::error::Error;
 
+// Import the Merkle proof verification used by allowlist orders.
+use crate::merkle::verify_proof;
+
+// Import the signature verification used to cancel orders, which is shared with the SUDT type script.
+use token_lock_common::signature::{verify_signature, SIGNATURE_LEN};
+
+// Import the order cell data layout.
+use crate::order::{LockArgs, Order, AUCTION_METRIC_TIMESTAMP, BASIS_POINTS_DENOMINATOR, SCRIPT_HASH_LEN, SUDT_DATA_LEN};
+
 // Constants
-const SCRIPT_HASH_LEN: usize = 32; // Number of bytes for a lock hash. (Blake2b 256-bit 32 bytes)
-const SUDT_DATA_LEN: usize = 16; // SUDT uses a u128, which is 16 bytes.
+const OUT_POINT_LEN: usize = 36; // An out point is a 32 byte transaction hash followed by a u32 index.
+const BINDING_MARKER: &[u8] = b"TBLB"; // A bound payment must opt in with this marker, so other data after the SUDT amount is never read as a binding.
+const ORACLE_DATA_LEN: usize = SUDT_DATA_LEN * 2; // An oracle price is a u128 numerator followed by a u128 denominator.
+
+/// The SUDT tokens required from each payout lock hash and token type hash pair.
+type PayoutRequirements = BTreeMap<(Vec<u8>, Vec<u8>), u128>;
+
+/// The limits on the cells used to pay a payout lock hash. A maximum of zero cells is unlimited.
+#[derive(Clone, Copy, Default)]
+struct PayoutCellLimits
+{
+	min_capacity: u64,
+	max_cells: u32,
+}
+
+/// The SUDT tokens required by the Token Buy Lock cells in the script group.
+struct TokenBuyRequirements
+{
+	/// The total tokens required by each payout lock hash and token type hash.
+	payouts: PayoutRequirements,
+	/// The tokens required by each order with a bound payment, keyed by the out point of the order cell.
+	bound_orders: BTreeMap<Vec<u8>, PayoutRequirements>,
+	/// The alternative payments accepted by each order with alternatives, in input order. Only one alternative of each order must be paid.
+	alternative_orders: Vec<Vec<PayoutRequirements>>,
+	/// The limits on the payout cells of each payout lock hash and token type hash. When orders have different limits, the strictest are used.
+	payout_cell_limits: BTreeMap<(Vec<u8>, Vec<u8>), PayoutCellLimits>,
+}
+
+/// Decode the order in a Token Buy Lock cell.
+/// When recovery mode is enabled, cells with malformed data are released and None is returned.
+fn decode_order(data: &[u8], version: u8, recovery_mode: bool) -> Result<Option<Order>, Error>
+{
+	match Order::from_data(data, version)
+	{
+		Ok(order) => Ok(Some(order)),
+		Err(_) if recovery_mode => Ok(None),
+		Err(err) => Err(err),
+	}
+}
 
-/// Determine if owner mode is enabled.
-fn check_owner_mode() -> Result<bool, Error>
+/// Determine if owner mode is enabled for an order.
+///
+/// Multiple cells can have multiple owners since the owner hash is stored in data and not in the args.
+/// Owner mode is decided for each cell individually, so an owner can withdraw their cells in the same transaction that other orders are filled.
+///
+/// An order with an owner public key hash can also be withdrawn with a signature over the transaction hash in the witness lock of the order input,
+/// so the owner does not need to spend one of their own cells to cancel.
+///
+/// An order which explicitly pays the owner lock hash is paid into a cell of the owner, which may be an anyone-can-pay cell that anyone can spend.
+/// A cell of the owner never proves ownership of such an order, so it can only be withdrawn with a signature.
+fn check_owner_mode(index: usize, order: &Order, input_lock_hashes: &BTreeSet<Vec<u8>>) -> Result<bool, Error>
 {
-	// Multiple cells can have multiple owners since the owner hash is stored in data and not in the args.
-	// All owners must be checked.
+	if !order.pays_owner_lock() && input_lock_hashes.contains(&order.owner_lock_hash[..])
+	{
+		return Ok(true);
+	}
+
+	let owner_pubkey_hash = match &order.owner_pubkey_hash
+	{
+		Some(owner_pubkey_hash) => owner_pubkey_hash,
+		None => return Ok(false),
+	};
 
-	// Find all the unique owners.
-	let mut owner_lock_hashes = BTreeSet::new();
-	for data in QueryIter::new(load_cell_data, Source::GroupInput)
+	// Load the signature from the witness of the order input. An allowlist proof is never the length of a signature.
+	let witness_args = match load_witness_args(index, Source::Input)
 	{
-		// If the data is less than the required length, return an error.
-		if data.len() < SCRIPT_HASH_LEN + SUDT_DATA_LEN
+		Ok(witness_args) => witness_args,
+		Err(SysError::IndexOutOfBound) => return Ok(false),
+		Err(err) => return Err(err.into()),
+	};
+	let signature = match witness_args.lock().to_opt()
+	{
+		Some(signature) if signature.raw_data().len() == SIGNATURE_LEN => signature.raw_data(),
+		_ => return Ok(false),
+	};
+
+	// A signature which is present must be valid.
+	if !verify_signature(owner_pubkey_hash, &signature)?
+	{
+		return Err(Error::Signature);
+	}
+
+	Ok(true)
+}
+
+/// Verify that the buyer filling an allowlist order is a member of the allowlist.
+///
+/// The witness for the order input must have a lock field containing the buyer lock hash followed by the Merkle proof.
+/// The buyer lock hash must be present in the inputs, which proves the buyer is a party to the transaction.
+fn validate_allowlist(index: usize, order: &Order, input_lock_hashes: &BTreeSet<Vec<u8>>) -> Result<(), Error>
+{
+	// Load the proof from the witness of the order input.
+	let witness_args = match load_witness_args(index, Source::Input)
+	{
+		Ok(witness_args) => witness_args,
+		Err(SysError::IndexOutOfBound) => return Err(Error::Allowlist),
+		Err(err) => return Err(err.into()),
+	};
+	let proof = match witness_args.lock().to_opt()
+	{
+		Some(proof) => proof.raw_data(),
+		None => return Err(Error::Allowlist),
+	};
+
+	// The proof must be the buyer lock hash followed by zero or more sibling hashes.
+	if proof.len() < SCRIPT_HASH_LEN || proof.len() % SCRIPT_HASH_LEN != 0
+	{
+		return Err(Error::Encoding);
+	}
+
+	// The buyer must be present in the inputs and be a member of the allowlist.
+	let buyer_lock_hash = &proof[0..SCRIPT_HASH_LEN];
+	if !input_lock_hashes.contains(buyer_lock_hash) || !verify_proof(&order.allowlist_root, buyer_lock_hash, &proof[SCRIPT_HASH_LEN..])
+	{
+		return Err(Error::Allowlist);
+	}
+
+	Ok(())
+}
+
+/// Load the remainder of a partially filled order from the output at the same index as the order input.
+/// Returns the capacity and order of the remainder, or None if the order was filled completely.
+fn load_remainder(index: usize, order: &Order, version: u8, lock_hash: &[u8]) -> Result<Option<(u64, Order)>, Error>
+{
+	// The remainder is the output at the same index as the order, with the same lock script as the order. If there isn't one, the order is filled completely.
+	let remainder_lock_hash = match load_cell_lock_hash(index, Source::Output)
+	{
+		Ok(lock_hash) => lock_hash,
+		Err(SysError::IndexOutOfBound) => return Ok(None),
+		Err(err) => return Err(err.into()),
+	};
+	if remainder_lock_hash[..] != lock_hash[..]
+	{
+		return Ok(None);
+	}
+
+	// The remainder must keep the same terms as the order, and keep the same type script.
+	let remainder = Order::from_data(&load_cell_data(index, Source::Output)?, version)?;
+	if !remainder.has_same_terms(order)
+	{
+		return Err(Error::Remainder);
+	}
+	if load_cell_type_hash(index, Source::Output)? != load_cell_type_hash(index, Source::Input)?
+	{
+		return Err(Error::Remainder);
+	}
+
+	Ok(Some((load_cell_capacity(index, Source::Output)?, remainder)))
+}
+
+/// Find the latest block number or timestamp proven by the header deps, depending on the auction metric.
+fn load_auction_point(auction_metric: u8) -> Result<Option<u64>, Error>
+{
+	let mut auction_point: Option<u64> = None;
+	for header in QueryIter::new(load_header, Source::HeaderDep)
+	{
+		let raw_header = header.raw();
+		let point: u64 = if auction_metric == AUCTION_METRIC_TIMESTAMP { raw_header.timestamp().unpack() } else { raw_header.number().unpack() };
+		auction_point = Some(auction_point.map_or(point, |auction_point| auction_point.max(point)));
+	}
+
+	Ok(auction_point)
+}
+
+/// Load the price from the oracle cell in the cell deps with the specified type hash.
+/// Returns the numerator and denominator of the price in tokens per shannon.
+///
+/// The oracle cell data is a u128 numerator followed by a u128 denominator. Exactly one oracle cell must be present, so a buyer cannot choose between prices.
+fn load_oracle_price(oracle_type_hash: &[u8]) -> Result<(u128, u128), Error>
+{
+	// Find the data of the oracle cell.
+	let mut oracle_data = None;
+	let cell_type_hashes = QueryIter::new(load_cell_type_hash, Source::CellDep);
+	for (i, cell_type_hash) in cell_type_hashes.enumerate()
+	{
+		if cell_type_hash.map_or(false, |cell_type_hash| cell_type_hash[..] == oracle_type_hash[..])
 		{
-			return Err(Error::DataLength);
+			if oracle_data.is_some()
+			{
+				return Err(Error::Oracle);
+			}
+
+			oracle_data = Some(load_cell_data(i, Source::CellDep)?);
 		}
+	}
+	let oracle_data = oracle_data.ok_or(Error::Oracle)?;
 
-		owner_lock_hashes.insert(data[0..SCRIPT_HASH_LEN].to_vec());
+	// If the data is less than the required length, return an error.
+	if oracle_data.len() < ORACLE_DATA_LEN
+	{
+		return Err(Error::Oracle);
 	}
 
-	// Find all input lock hashes.
-	let mut input_lock_hashes = BTreeSet::new();
-	for input_lock_hash in QueryIter::new(load_cell_lock_hash, Source::Input)
+	// Extract the price. A price with a zero denominator is meaningless.
+	let mut buffer = [0u8; SUDT_DATA_LEN];
+	buffer.copy_from_slice(&oracle_data[0..SUDT_DATA_LEN]);
+	let numerator = u128::from_le_bytes(buffer);
+	buffer.copy_from_slice(&oracle_data[SUDT_DATA_LEN..ORACLE_DATA_LEN]);
+	let denominator = u128::from_le_bytes(buffer);
+	if denominator == 0
 	{
-		input_lock_hashes.insert(input_lock_hash.to_vec());
+		return Err(Error::Oracle);
 	}
 
-	// Check if all owner lock hashes are present in the input lock hashes, indicating owner mode.
-	for owner_lock_hash in owner_lock_hashes
+	Ok((numerator, denominator))
+}
+
+/// Calculate the buy amount for the order cell at the specified input index.
+fn calculate_buy_amount(index: usize, order: &Order, version: u8, lock_hash: &[u8]) -> Result<u128, Error>
+{
+	// Only orders which allow partial fills can leave a remainder.
+	let capacity = load_cell_capacity(index, Source::Input)?;
+	let remainder = if order.is_partial_fill() { load_remainder(index, order, version, lock_hash)? } else { None };
+
+	// Priced orders charge for the capacity consumed, rounding up so the buyer never pays less than the price.
+	if order.is_price() || order.is_oracle()
 	{
-		// If any owner hash is not present, owner mode is not enabled.
-		if !input_lock_hashes.contains(&owner_lock_hash)
+		// Oracle orders use the oracle price, with the spread added on top.
+		let (numerator, denominator) = if order.is_oracle()
 		{
-			return Ok(false)
+			let (numerator, denominator) = load_oracle_price(&order.oracle_type_hash)?;
+			let numerator = numerator.checked_mul(BASIS_POINTS_DENOMINATOR as u128 + order.oracle_spread as u128).ok_or(Error::Overflow)?;
+			let denominator = denominator.checked_mul(BASIS_POINTS_DENOMINATOR as u128).ok_or(Error::Overflow)?;
+			(numerator, denominator)
 		}
+		else
+		{
+			(order.buy_amount, order.price_denominator)
+		};
+
+		let consumed_capacity = match &remainder
+		{
+			Some((remainder_capacity, remainder)) =>
+			{
+				// The remainder must keep the same price.
+				if remainder != order
+				{
+					return Err(Error::Remainder);
+				}
+
+				capacity.saturating_sub(*remainder_capacity)
+			},
+			None => capacity,
+		};
+
+		let amount = (consumed_capacity as u128).checked_mul(numerator).and_then(|amount| amount.checked_add(denominator - 1)).ok_or(Error::Overflow)?;
+		return Ok(amount / denominator);
 	}
 
-	Ok(true)
+	// Auction orders charge an amount which decays over time, measured by the latest header in the header deps.
+	if order.is_auction()
+	{
+		return order.auction_amount(load_auction_point(order.auction_metric)?);
+	}
+
+	// Fixed amount orders charge the full buy amount, less whatever is left in the remainder.
+	match remainder
+	{
+		Some((remainder_capacity, remainder)) =>
+		{
+			// The remainder cannot ask for more tokens per shannon than the original order, otherwise the buyer would pay less than their share.
+			let remainder_rate = remainder.buy_amount.checked_mul(capacity as u128).ok_or(Error::Overflow)?;
+			let order_rate = order.buy_amount.checked_mul(remainder_capacity as u128).ok_or(Error::Overflow)?;
+			if remainder_rate > order_rate
+			{
+				return Err(Error::Remainder);
+			}
+
+			Ok(order.buy_amount.saturating_sub(remainder.buy_amount))
+		},
+		None => Ok(order.buy_amount),
+	}
 }
 
-/// Calculate the required SUDT tokens from all token buy cells, grouped by owner lock hash..
-fn calculate_token_buy_cell_requirements() -> Result<BTreeMap<Vec<u8>, u128>, Error>
+/// Add an amount of tokens to the requirements for a payout lock hash and token type hash.
+fn add_payout_requirement(payouts: &mut PayoutRequirements, payout_lock_hash: &[u8], type_hash: &[u8], amount: u128) -> Result<(), Error>
 {
-	let mut requirements = BTreeMap::new();
+	add_requirement(payouts, (payout_lock_hash.to_vec(), type_hash.to_vec()), amount)?;
+
+	Ok(())
+}
 
-	// Cycle though all Token Buy Lock cell data.
-	let cell_data = QueryIter::new(load_cell_data, Source::GroupInput);
-	for data in cell_data
+/// Split the token amounts of an order between the payout lock hash and the fee lock hash.
+/// Returns the requirements of both, followed by the requirements of the payout lock hash alone.
+fn split_order_payouts(order: &Order, token_amounts: &[(Vec<u8>, u128)]) -> Result<(PayoutRequirements, PayoutRequirements), Error>
+{
+	let mut order_payouts = BTreeMap::new();
+	let mut payout_lock_payouts = BTreeMap::new();
+	for (type_hash, total_amount) in token_amounts
 	{
-		// If the data is less than the required length, return an error.
-		if data.len() < SCRIPT_HASH_LEN + SUDT_DATA_LEN
+		// Split off the share of the buy amount owed to the fee lock hash.
+		let fee_amount = order.fee_amount(*total_amount)?;
+		let buy_amount = total_amount - fee_amount;
+
+		// Add buy amount to requirements for current payout lock hash.
+		add_payout_requirement(&mut order_payouts, &order.payout_lock_hash, type_hash, buy_amount)?;
+		add_payout_requirement(&mut payout_lock_payouts, &order.payout_lock_hash, type_hash, buy_amount)?;
+
+		// Add the fee to the requirements for the fee lock hash.
+		if fee_amount > 0
 		{
-			return Err(Error::DataLength);
+			add_payout_requirement(&mut order_payouts, &order.fee_lock_hash, type_hash, fee_amount)?;
 		}
+	}
+
+	Ok((order_payouts, payout_lock_payouts))
+}
 
-		// Extract owner lock hash and buy amount.
-		let owner_lock_hash = data[0..SCRIPT_HASH_LEN].to_vec();
-		let mut buffer = [0u8; SUDT_DATA_LEN];
-		buffer.copy_from_slice(&data[SCRIPT_HASH_LEN..SCRIPT_HASH_LEN+SUDT_DATA_LEN]);
-		let buy_amount = u128::from_le_bytes(buffer);
+/// Add the payout cell limits of an order for a token type hash, keeping the strictest limits.
+fn add_payout_cell_limits(payout_cell_limits: &mut BTreeMap<(Vec<u8>, Vec<u8>), PayoutCellLimits>, order: &Order, type_hash: Vec<u8>)
+{
+	let limits = payout_cell_limits.entry((order.payout_lock_hash.to_vec(), type_hash)).or_default();
+	limits.min_capacity = limits.min_capacity.max(order.min_payout_capacity);
+	if order.max_payout_cells > 0 && (limits.max_cells == 0 || order.max_payout_cells < limits.max_cells)
+	{
+		limits.max_cells = order.max_payout_cells;
+	}
+}
+
+/// Calculate the required SUDT tokens from all token buy cells, grouped by payout lock hash and token type hash, and by bound order.
+///
+/// Cells with different args are run in different script groups, but they can pay the same payout lock hash. To prevent one payment from filling orders in several groups,
+/// every group counts the orders of all inputs which use the same code hash and hash type as this script. Orders for every SUDT are counted, since a bundle or an alternative can require any token.
+fn calculate_token_buy_cell_requirements(script: &Script) -> Result<TokenBuyRequirements, Error>
+{
+	let mut requirements = TokenBuyRequirements { payouts: BTreeMap::new(), bound_orders: BTreeMap::new(), alternative_orders: Vec::new(), payout_cell_limits: BTreeMap::new() };
+	let input_lock_hashes = load_input_lock_hashes();
 
-		// Add key if it doesn't already exist.
-		if !requirements.contains_key(&owner_lock_hash)
+	// Cycle though all Token Buy Lock cells in the inputs. The input index is needed to locate the remainder of a partial fill.
+	let cell_lock_scripts = QueryIter::new(load_cell_lock, Source::Input);
+	for (i, lock_script) in cell_lock_scripts.enumerate()
+	{
+		if lock_script.code_hash().as_slice() != script.code_hash().as_slice() || lock_script.hash_type().as_slice() != script.hash_type().as_slice()
+		{
+			continue;
+		}
+
+		// Decode the args of the cell, which may belong to another script group with a different layout.
+		let args: Bytes = lock_script.args().unpack();
+		let lock_args = LockArgs::from_args(&args)?;
+		let lock_hash = load_cell_lock_hash(i, Source::Input)?;
+
+		// Recovery mode is enabled by the presence of the recovery lock hash of the cell in the inputs, which allows cells with malformed data to be spent.
+		let recovery_mode = lock_args.recovery_lock_hash.map_or(false, |recovery_lock_hash| input_lock_hashes.contains(&recovery_lock_hash[..]));
+
+		// Extract the order. Malformed cells are released in recovery mode.
+		let order = match decode_order(&load_cell_data(i, Source::Input)?, lock_args.version, recovery_mode)?
+		{
+			Some(order) => order,
+			None => continue,
+		};
+
+		// Cells being withdrawn by their owner do not require payment.
+		if check_owner_mode(i, &order, &input_lock_hashes)?
+		{
+			continue;
+		}
+
+		// Orders with an allowlist can only be filled by an allowed buyer.
+		if order.is_allowlist()
+		{
+			validate_allowlist(i, &order, &input_lock_hashes)?;
+		}
+
+		// Calculate the buy amount of the SUDT in the args, followed by the amounts of any other tokens in a bundle.
+		let mut token_amounts = vec![(lock_args.sudt_type_hash.to_vec(), calculate_buy_amount(i, &order, lock_args.version, &lock_hash)?)];
+		for (type_hash, amount) in &order.bundle
+		{
+			token_amounts.push((type_hash.to_vec(), *amount));
+		}
+
+		let (order_payouts, bound_payouts) = split_order_payouts(&order, &token_amounts)?;
+
+		// Add the limits on the payout cells for every token the order may be paid with.
+		if order.has_payout_cell_limits()
+		{
+			let type_hashes = token_amounts.iter().map(|(type_hash, _)| type_hash.clone()).chain(order.alternatives.iter().map(|(type_hash, _)| type_hash.to_vec()));
+			for type_hash in type_hashes
+			{
+				add_payout_cell_limits(&mut requirements.payout_cell_limits, &order, type_hash);
+			}
+		}
+
+		// Orders with alternatives can be paid with the SUDT in the args or any one of the alternatives, so they are validated separately.
+		if order.has_alternatives()
+		{
+			let mut alternatives = vec![order_payouts];
+			for (type_hash, amount) in &order.alternatives
+			{
+				let (alternative_payouts, _) = split_order_payouts(&order, &[(type_hash.to_vec(), *amount)])?;
+				alternatives.push(alternative_payouts);
+			}
+			requirements.alternative_orders.push(alternatives);
+
+			continue;
+		}
+
+		// Add the order to the requirements for each payout lock hash. Fees are never bound to the order.
+		for ((payout_lock_hash, type_hash), buy_amount) in order_payouts
 		{
-			requirements.insert(owner_lock_hash.clone(), 0);
+			add_payout_requirement(&mut requirements.payouts, &payout_lock_hash, &type_hash, buy_amount)?;
 		}
 
-		// Add buy amount to requirements for current owner lock hash.
-		let amount = requirements.get_mut(&owner_lock_hash).unwrap();
-		*amount += buy_amount;
+		// Orders with a bound payment must also be paid individually.
+		if order.is_bound_payment()
+		{
+			let out_point = load_input_out_point(i, Source::Input)?.as_slice().to_vec();
+			requirements.bound_orders.insert(out_point, bound_payouts);
+		}
 	}
 
 	Ok(requirements)
 }
 
+/// Extract the out point of the order cell an SUDT payment is bound to, if the cell data includes one.
+///
+/// A bound payment has cell data with the SUDT amount followed by the binding marker and the out point of the order cell.
+/// Data without the marker is not bound, whatever its length.
+fn payment_binding(data: &[u8]) -> Option<&[u8]>
+{
+	let offset = SUDT_DATA_LEN + BINDING_MARKER.len();
+	if data.len() < offset + OUT_POINT_LEN || &data[SUDT_DATA_LEN..offset] != BINDING_MARKER
+	{
+		return None;
+	}
+
+	Some(&data[offset..offset+OUT_POINT_LEN])
+}
+
 /// Count the number of tokens in the specified source with the specified lock hash and type hash.
-fn determine_token_amount(source: Source, lock_hash: &[u8], type_hash: &[u8]) -> Result<u128, Error>
+/// Only cells with a payment binding that is accepted by the binding filter are counted.
+fn determine_token_amount<F>(source: Source, lock_hash: &[u8], type_hash: &[u8], binding_filter: F) -> Result<u128, Error>
+	where F: Fn(Option<&[u8]>) -> bool
 {
 	// Track the number of tokens that are counted.
-	let mut total_token_amount = 0;
+	let mut total_token_amount: u128 = 0;
 
 	// Cycle through the data in each cell within the specified source.
 	let cell_data = QueryIter::new(load_cell_data, source);
@@ -118,13 +487,19 @@ fn determine_token_amount(source: Source, lock_hash: &[u8], type_hash: &[u8]) ->
 				return Err(Error::Encoding);
 			}
 
+			// Skip cells which are bound to a payment that is not being counted.
+			if !binding_filter(payment_binding(&data))
+			{
+				continue;
+			}
+
 			// Convert the binary data in the cell to a u128 value.
 			let mut buffer = [0u8; SUDT_DATA_LEN];
 			buffer.copy_from_slice(&data[0..SUDT_DATA_LEN]);
 			let amount = u128::from_le_bytes(buffer);
 
 			// Add the amount of tokens in the cell to the total amount of tokens.
-			total_token_amount += amount;
+			total_token_amount = total_token_amount.checked_add(amount).ok_or(Error::Overflow)?;
 		}
 	}
 
@@ -132,22 +507,142 @@ fn determine_token_amount(source: Source, lock_hash: &[u8], type_hash: &[u8]) ->
 	Ok(total_token_amount)
 }
 
-/// Validate the token buy requirements against the output token amounts.
-fn validate_token_buy_requirements(token_buy_requirements: &BTreeMap<Vec<u8>, u128>, sudt_type_script_hash: &[u8]) -> Result<(), Error>
+/// Count the tokens paid to the payout lock hash, measured by the increase between the inputs and outputs.
+/// Payments bound to cells which are not Token Buy Lock orders in the inputs belong to someone else and are not counted.
+fn determine_payment_amount(payout_lock_hash: &[u8], type_hash: &[u8], bound_orders: &BTreeMap<Vec<u8>, PayoutRequirements>) -> Result<u128, Error>
+{
+	// Count the number of tokens being sent to the payout lock hash.
+	let output_token_amount = determine_token_amount(Source::Output, payout_lock_hash, type_hash, |binding|
+	{
+		binding.map_or(true, |out_point| bound_orders.contains_key(out_point))
+	})?;
+
+	// Subtract the tokens already held by the payout lock hash in the inputs, so payments into an existing anyone-can-pay cell are measured by their increase.
+	let input_token_amount = determine_token_amount(Source::Input, payout_lock_hash, type_hash, |_| true)?;
+
+	Ok(output_token_amount.saturating_sub(input_token_amount))
+}
+
+/// Validate the cells paying the payout lock hash against the payout cell limits.
+/// Payments bound to cells which are not Token Buy Lock orders in the inputs belong to someone else and are not checked.
+fn validate_payout_cells(payout_lock_hash: &[u8], type_hash: &[u8], limits: &PayoutCellLimits, bound_orders: &BTreeMap<Vec<u8>, PayoutRequirements>) -> Result<(), Error>
+{
+	let mut payout_cells = 0;
+
+	// Cycle through the lock hash of each output.
+	let cell_lock_hashes = QueryIter::new(load_cell_lock_hash, Source::Output);
+	for (i, cell_lock_hash) in cell_lock_hashes.enumerate()
+	{
+		// Skip any cells which are not paying the payout lock hash with the token.
+		if cell_lock_hash[..] != payout_lock_hash[..] || load_cell_type_hash(i, Source::Output)?.map_or(true, |cell_type_hash| cell_type_hash[..] != type_hash[..])
+		{
+			continue;
+		}
+		if payment_binding(&load_cell_data(i, Source::Output)?).map_or(false, |out_point| !bound_orders.contains_key(out_point))
+		{
+			continue;
+		}
+
+		// Each payout cell must have enough capacity to be usable.
+		if load_cell_capacity(i, Source::Output)? < limits.min_capacity
+		{
+			return Err(Error::PayoutCells);
+		}
+
+		payout_cells += 1;
+	}
+
+	// The payment cannot be split into more cells than allowed.
+	if limits.max_cells > 0 && payout_cells > limits.max_cells
+	{
+		return Err(Error::PayoutCells);
+	}
+
+	Ok(())
+}
+
+/// Validate the token buy requirements against the token amounts paid to each payout lock hash.
+fn validate_token_buy_requirements(token_buy_requirements: &TokenBuyRequirements) -> Result<(), Error>
 {
-	for (owner_lock_hash, buy_amount) in token_buy_requirements
+	let bound_orders = &token_buy_requirements.bound_orders;
+
+	// Track the payments which are left over after the requirements of each order are met.
+	let mut remaining_payments = BTreeMap::new();
+
+	for ((payout_lock_hash, type_hash), buy_amount) in &token_buy_requirements.payouts
 	{
-		// Count the number of tokens being sent to the owner lock hash.
-		let output_token_amount = determine_token_amount(Source::Output, &owner_lock_hash[..], sudt_type_script_hash)?;
+		let payment_amount = determine_payment_amount(payout_lock_hash, type_hash, bound_orders)?;
 
 		// If not enough tokens we provided, return an error.
-		if output_token_amount < *buy_amount
+		if payment_amount < *buy_amount
+		{
+			// debug!("{:?} {} {:?} {}", payout_lock_hash, *buy_amount, type_hash, payment_amount);
+			return Err(Error::Amount);
+		}
+
+		remaining_payments.insert((payout_lock_hash.clone(), type_hash.clone()), payment_amount - buy_amount);
+	}
+
+	// Orders with alternatives are paid from the remaining payments, in input order. Each order takes the first alternative which is paid in full.
+	for alternatives in &token_buy_requirements.alternative_orders
+	{
+		let mut is_paid = false;
+		for alternative_payouts in alternatives
+		{
+			// Count the payments for any payout lock hash and token type hash which has not been counted yet.
+			for (payout_lock_hash, type_hash) in alternative_payouts.keys()
+			{
+				let key = (payout_lock_hash.clone(), type_hash.clone());
+				if !remaining_payments.contains_key(&key)
+				{
+					let payment_amount = determine_payment_amount(payout_lock_hash, type_hash, bound_orders)?;
+					remaining_payments.insert(key, payment_amount);
+				}
+			}
+
+			// Take the alternative if every part of it is covered by the remaining payments.
+			if alternative_payouts.iter().all(|(key, buy_amount)| remaining_payments[key] >= *buy_amount)
+			{
+				for (key, buy_amount) in alternative_payouts
+				{
+					*remaining_payments.get_mut(key).unwrap() -= buy_amount;
+				}
+
+				is_paid = true;
+				break;
+			}
+		}
+
+		// If none of the alternatives were paid, return an error.
+		if !is_paid
 		{
-			// debug!("{:?} {} {:?} {}", owner_lock_hash, *buy_amount, sudt_type_script_hash, output_token_amount);
 			return Err(Error::Amount);
 		}
 	}
 
+	for ((payout_lock_hash, type_hash), limits) in &token_buy_requirements.payout_cell_limits
+	{
+		validate_payout_cells(payout_lock_hash, type_hash, limits, bound_orders)?;
+	}
+
+	for (out_point, bound_payouts) in bound_orders
+	{
+		for ((payout_lock_hash, type_hash), buy_amount) in bound_payouts
+		{
+			// Count the number of tokens being sent to the payout lock hash which are bound to this order.
+			let output_token_amount = determine_token_amount(Source::Output, &payout_lock_hash[..], &type_hash[..], |binding|
+			{
+				binding == Some(&out_point[..])
+			})?;
+
+			// If not enough tokens we provided, return an error.
+			if output_token_amount < *buy_amount
+			{
+				return Err(Error::Amount);
+			}
+		}
+	}
+
 	Ok(())
 }
 
@@ -158,23 +653,14 @@ pub fn main() -> Result<(), Error>
 	let script = load_script()?;
 	let args: Bytes = script.args().unpack();
 
-	// Verify the the arguments length matches the length of a single Blake2b hash.
-	if args.len() != SCRIPT_HASH_LEN
-	{
-		return Err(Error::ArgsLength);
-	}
-
-	// Check if the script is being run by the owner and immediately return success if true.
-	if check_owner_mode()?
-	{
-		return Ok(());
-	}
+	// Decode the args of this script group. The args of every order cell are decoded again when the orders are counted.
+	LockArgs::from_args(&args)?;
 
-	// Verify the number of Token Buy Lock cells in the transaction is one.
-	let token_buy_requirements = calculate_token_buy_cell_requirements()?;
+	// Calculate the tokens required by each order which is not being withdrawn by its owner.
+	let token_buy_requirements = calculate_token_buy_cell_requirements(&script)?;
 
 	// Ensure that the token buy requirements have been met.
-	validate_token_buy_requirements(&token_buy_requirements, &args[0..SCRIPT_HASH_LEN])?;
+	validate_token_buy_requirements(&token_buy_requirements)?;
 
 	// No errors were found during validation. Return success.
 	Ok(())
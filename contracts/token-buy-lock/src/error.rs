@@ -1,4 +1,5 @@
 use ckb_std::error::SysError;
+use token_lock_common::requirements::Overflow;
 
 /// Error
 #[repr(i8)]
@@ -12,6 +13,12 @@ pub enum Error
 	Amount,
 	ArgsLength,
 	DataLength,
+	Remainder,
+	Allowlist,
+	Oracle,
+	PayoutCells,
+	Overflow,
+	Signature,
 }
 
 impl From<SysError> for Error
@@ -29,3 +36,11 @@ impl From<SysError> for Error
 		}
 	}
 }
+
+impl From<Overflow> for Error
+{
+	fn from(_: Overflow) -> Self
+	{
+		Self::Overflow
+	}
+}
@@ -13,13 +13,16 @@
 // define modules
 mod entry;
 mod error;
+mod merkle;
+mod order;
 
 use ckb_std::{
     default_alloc,
 };
 
 ckb_std::entry!(program_entry);
-default_alloc!();
+// The secp256k1 library needs a heap large enough for its precomputed table.
+default_alloc!(4 * 1024, 1280 * 1024, 64);
 
 /// program entry
 fn program_entry() -> i8 {
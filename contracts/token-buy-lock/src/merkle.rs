@@ -0,0 +1,39 @@
+// Import Blake2b, which is not provided by ckb-std.
+use blake2b_ref::Blake2bBuilder;
+
+// Import the script hash length, which is also the length of a Merkle node.
+use crate::order::SCRIPT_HASH_LEN;
+
+// Constants
+const CKB_HASH_PERSONALIZATION: &[u8] = b"ckb-default-hash"; // The same personalization used by CKB for script hashes.
+
+/// Hash two Merkle nodes into their parent node.
+///
+/// The nodes are sorted before hashing so a proof does not need to record which side each sibling is on.
+fn hash_pair(a: &[u8], b: &[u8]) -> [u8; SCRIPT_HASH_LEN]
+{
+	let (left, right) = if a <= b { (a, b) } else { (b, a) };
+
+	let mut hasher = Blake2bBuilder::new(SCRIPT_HASH_LEN).personal(CKB_HASH_PERSONALIZATION).build();
+	hasher.update(left);
+	hasher.update(right);
+	let mut hash = [0u8; SCRIPT_HASH_LEN];
+	hasher.finalize(&mut hash);
+
+	hash
+}
+
+/// Verify that a leaf is a member of the Merkle tree with the specified root.
+///
+/// The proof is the concatenated sibling nodes from the leaf up to the root. A tree with a single leaf has an empty proof.
+pub fn verify_proof(root: &[u8], leaf: &[u8], proof: &[u8]) -> bool
+{
+	let mut node = [0u8; SCRIPT_HASH_LEN];
+	node.copy_from_slice(leaf);
+	for sibling in proof.chunks(SCRIPT_HASH_LEN)
+	{
+		node = hash_pair(&node, sibling);
+	}
+
+	node[..] == root[..]
+}
@@ -0,0 +1,697 @@
+// Import from alloc core instead of from std since we are in no-std mode.
+use alloc::vec::Vec;
+use core::result::Result;
+
+// Import the Molecule types shared with the tests.
+use token_buy_lock_types::molecule::prelude::*;
+use token_buy_lock_types::token_buy_lock::{OrderArgsReader, OrderDataReader, OrderDataV1Reader, OrderDataV2Reader, TokenAmountVecReader};
+use token_buy_lock_types::{ORDER_VERSION, ORDER_VERSION_V1, ORDER_VERSION_V2};
+
+// Import our local error codes.
+use crate::error::Error;
+
+// Import the length of an owner public key hash.
+use token_lock_common::signature::PUBKEY_HASH_LEN;
+
+// Constants
+pub const SCRIPT_HASH_LEN: usize = 32; // Number of bytes for a lock hash. (Blake2b 256-bit 32 bytes)
+pub const SUDT_DATA_LEN: usize = 16; // SUDT uses a u128, which is 16 bytes.
+const ORDER_DATA_LEN: usize = SCRIPT_HASH_LEN + SUDT_DATA_LEN; // Owner lock hash followed by the buy amount.
+const FLAGS_MARKER: &[u8] = b"TBLF"; // Legacy data must opt in to order flags with this marker, since the original script ignored any data after the buy amount.
+const BASIS_POINTS_LEN: usize = 2; // Fee rates and spreads are stored in basis points as a u16.
+pub const BASIS_POINTS_DENOMINATOR: u16 = 10_000; // The number of basis points in a whole.
+const COUNT_LEN: usize = 1; // Counts and the auction metric are stored as a u8.
+const AUCTION_POINT_LEN: usize = 8; // Block numbers and timestamps are stored as a u64.
+const PAYOUT_CELLS_LEN: usize = 4; // The maximum number of payout cells is stored as a u32.
+pub const LEGACY_VERSION: u8 = 0; // Args and cell data which predate the version byte.
+
+// Auction Metrics
+pub const AUCTION_METRIC_BLOCK_NUMBER: u8 = 0; // The auction start and end are block numbers.
+pub const AUCTION_METRIC_TIMESTAMP: u8 = 1; // The auction start and end are block timestamps in milliseconds.
+
+// Order Flags
+pub const FLAG_PARTIAL_FILL: u16 = 1 << 0; // The order may be partially filled, leaving a remainder cell.
+pub const FLAG_PRICE: u16 = 1 << 1; // The buy amount is the numerator of a price in tokens per shannon.
+pub const FLAG_BOUND_PAYMENT: u16 = 1 << 2; // The payment must be bound to the out point of the order cell.
+pub const FLAG_PAYOUT_LOCK: u16 = 1 << 3; // The payment is sent to a payout lock hash instead of the owner lock hash.
+pub const FLAG_FEE: u16 = 1 << 4; // A share of the payment is sent to a fee lock hash.
+pub const FLAG_AUCTION: u16 = 1 << 5; // The buy amount decays to an end amount over a range of blocks or time.
+pub const FLAG_ALLOWLIST: u16 = 1 << 6; // The order can only be filled by a buyer in a Merkle tree of allowed lock hashes.
+pub const FLAG_ORACLE: u16 = 1 << 7; // The price is read from an oracle cell in the cell deps instead of the buy amount.
+pub const FLAG_BUNDLE: u16 = 1 << 8; // Other tokens are required in addition to the SUDT in the args.
+pub const FLAG_ALTERNATIVES: u16 = 1 << 9; // Other tokens are accepted instead of the SUDT in the args.
+const FLAGS_SUPPORTED: u16 = FLAG_PARTIAL_FILL | FLAG_PRICE | FLAG_BOUND_PAYMENT | FLAG_PAYOUT_LOCK | FLAG_FEE | FLAG_AUCTION | FLAG_ALLOWLIST | FLAG_ORACLE | FLAG_BUNDLE | FLAG_ALTERNATIVES;
+
+/// The args of a Token Buy Lock script.
+pub struct LockArgs
+{
+	pub version: u8,
+	pub sudt_type_hash: [u8; SCRIPT_HASH_LEN],
+	pub recovery_lock_hash: Option<[u8; SCRIPT_HASH_LEN]>,
+}
+
+impl LockArgs
+{
+	/// Decode the args of a Token Buy Lock script.
+	///
+	/// Legacy args are the SUDT type hash, optionally followed by the recovery lock hash.
+	/// Versioned args are the version byte followed by the Molecule encoded `OrderArgs`, which is the same for every version.
+	pub fn from_args(args: &[u8]) -> Result<Self, Error>
+	{
+		// Verify the the arguments length matches the length of a single Blake2b hash, or two when a recovery lock hash is included.
+		if args.len() == SCRIPT_HASH_LEN || args.len() == SCRIPT_HASH_LEN * 2
+		{
+			let sudt_type_hash = to_hash(&args[0..SCRIPT_HASH_LEN]);
+			let recovery_lock_hash = if args.len() == SCRIPT_HASH_LEN * 2 { Some(to_hash(&args[SCRIPT_HASH_LEN..SCRIPT_HASH_LEN*2])) } else { None };
+
+			return Ok(LockArgs { version: LEGACY_VERSION, sudt_type_hash, recovery_lock_hash });
+		}
+
+		// Any other length must be a version this script understands.
+		match args.split_first()
+		{
+			Some((&version, args)) if version == ORDER_VERSION_V1 || version == ORDER_VERSION_V2 || version == ORDER_VERSION =>
+			{
+				let order_args = OrderArgsReader::from_slice(args).map_err(|_| Error::Encoding)?;
+				let sudt_type_hash = to_hash(order_args.sudt_type_hash().raw_data());
+				let recovery_lock_hash = order_args.recovery_lock_hash().to_opt().map(|lock_hash| to_hash(lock_hash.raw_data()));
+
+				Ok(LockArgs { version, sudt_type_hash, recovery_lock_hash })
+			},
+			_ => Err(Error::ArgsLength),
+		}
+	}
+}
+
+/// A Token Buy Lock order decoded from cell data.
+///
+/// When the args are versioned, the cell data is the same version byte followed by the Molecule encoded `OrderData`, or the `OrderDataV1` or `OrderDataV2` of an earlier version.
+/// The fields of `OrderData` match the optional fields below, and are converted to the same flags.
+///
+/// The legacy cell data layout is:
+/// - `[0..32]` Owner lock hash. The owner can withdraw the order.
+/// - `[32..48]` Buy amount. (u128)
+/// - `[48..52]` Flags marker, `TBLF`. (optional)
+/// - `[52..54]` Order flags. (u16, only present after the marker)
+/// - `[54..]` Optional fields, present in the order of their flag bits.
+///
+/// Data without the marker has no flags, and any bytes after the buy amount are ignored the same as the original script.
+///
+/// Optional fields:
+/// - `FLAG_PRICE`: Price denominator. (u128) The buy amount is used as the numerator.
+/// - `FLAG_PAYOUT_LOCK`: Payout lock hash. Without it, the payment is sent to the owner lock hash.
+/// - `FLAG_FEE`: Fee lock hash followed by the fee rate in basis points. (u16)
+/// - `FLAG_AUCTION`: End amount (u128), auction metric (u8), auction start (u64) and auction end. (u64) The buy amount is used as the start amount.
+/// - `FLAG_ALLOWLIST`: Merkle root of the allowed buyer lock hashes.
+/// - `FLAG_ORACLE`: Oracle type hash followed by the spread in basis points. (u16) The buy amount is not used.
+/// - `FLAG_BUNDLE`: Number of bundled tokens (u8), followed by the type hash and buy amount (u128) of each.
+/// - `FLAG_ALTERNATIVES`: Number of alternative tokens (u8), followed by the type hash and buy amount (u128) of each.
+///
+/// Fields which are only available in the versioned layout from version 2:
+/// - `min_payout_capacity`: The minimum capacity of each cell paying the payout lock hash. Zero when not set.
+/// - `max_payout_cells`: The maximum number of cells paying the payout lock hash for each token. Zero when not set.
+///
+/// Fields which are only available in the versioned layout from version 3:
+/// - `owner_pubkey_hash`: The public key hash which can withdraw the order with a signature.
+#[derive(Clone, PartialEq, Eq)]
+pub struct Order
+{
+	pub owner_lock_hash: [u8; SCRIPT_HASH_LEN],
+	pub buy_amount: u128,
+	pub flags: u16,
+	pub price_denominator: u128,
+	pub payout_lock_hash: [u8; SCRIPT_HASH_LEN],
+	pub fee_lock_hash: [u8; SCRIPT_HASH_LEN],
+	pub fee_rate: u16,
+	pub auction_end_amount: u128,
+	pub auction_metric: u8,
+	pub auction_start: u64,
+	pub auction_end: u64,
+	pub allowlist_root: [u8; SCRIPT_HASH_LEN],
+	pub oracle_type_hash: [u8; SCRIPT_HASH_LEN],
+	pub oracle_spread: u16,
+	pub bundle: Vec<([u8; SCRIPT_HASH_LEN], u128)>,
+	pub alternatives: Vec<([u8; SCRIPT_HASH_LEN], u128)>,
+	pub min_payout_capacity: u64,
+	pub max_payout_cells: u32,
+	pub owner_pubkey_hash: Option<[u8; PUBKEY_HASH_LEN]>,
+}
+
+impl Order
+{
+	/// Decode an order from the data of a Token Buy Lock cell, using the layout of the version in the lock script args.
+	pub fn from_data(data: &[u8], version: u8) -> Result<Self, Error>
+	{
+		if version == LEGACY_VERSION
+		{
+			return Self::from_legacy_data(data);
+		}
+
+		// Versioned data must start with the same version byte as the args.
+		match data.split_first()
+		{
+			Some((data_version, data)) if *data_version == version => Self::from_molecule_data(data, version),
+			Some(_) => Err(Error::Encoding),
+			None => Err(Error::DataLength),
+		}
+	}
+
+	/// Decode an order from the original layout of a Token Buy Lock cell, which predates the version byte.
+	fn from_legacy_data(data: &[u8]) -> Result<Self, Error>
+	{
+		// If the data is less than the required length, return an error.
+		if data.len() < ORDER_DATA_LEN
+		{
+			return Err(Error::DataLength);
+		}
+
+		// Extract owner lock hash and buy amount.
+		let mut owner_lock_hash = [0u8; SCRIPT_HASH_LEN];
+		owner_lock_hash.copy_from_slice(&data[0..SCRIPT_HASH_LEN]);
+		let mut buffer = [0u8; SUDT_DATA_LEN];
+		buffer.copy_from_slice(&data[SCRIPT_HASH_LEN..ORDER_DATA_LEN]);
+		let buy_amount = u128::from_le_bytes(buffer);
+
+		// Extract the flags if the data opts in with the flags marker. The original script ignored any data after the buy amount,
+		// so data without the marker uses the original fixed amount behavior and trailing bytes are still ignored.
+		let mut offset = ORDER_DATA_LEN + FLAGS_MARKER.len();
+		let flags = if data.len() >= offset && &data[ORDER_DATA_LEN..offset] == FLAGS_MARKER
+		{
+			read_u16(data, &mut offset)?
+		}
+		else
+		{
+			0
+		};
+
+		// Reject flags this version of the script does not understand.
+		if flags & !FLAGS_SUPPORTED != 0
+		{
+			return Err(Error::Encoding);
+		}
+
+		// Extract the optional fields which follow the flags.
+		let mut price_denominator = 0;
+		if flags & FLAG_PRICE != 0
+		{
+			price_denominator = read_u128(data, &mut offset)?;
+		}
+
+		let mut payout_lock_hash = owner_lock_hash;
+		if flags & FLAG_PAYOUT_LOCK != 0
+		{
+			payout_lock_hash = read_hash(data, &mut offset)?;
+		}
+
+		let mut fee_lock_hash = [0u8; SCRIPT_HASH_LEN];
+		let mut fee_rate = 0;
+		if flags & FLAG_FEE != 0
+		{
+			fee_lock_hash = read_hash(data, &mut offset)?;
+			fee_rate = read_u16(data, &mut offset)?;
+		}
+
+		let mut auction_end_amount = 0;
+		let mut auction_metric = 0;
+		let mut auction_start = 0;
+		let mut auction_end = 0;
+		if flags & FLAG_AUCTION != 0
+		{
+			auction_end_amount = read_u128(data, &mut offset)?;
+			auction_metric = read_u8(data, &mut offset)?;
+			auction_start = read_u64(data, &mut offset)?;
+			auction_end = read_u64(data, &mut offset)?;
+		}
+
+		let mut allowlist_root = [0u8; SCRIPT_HASH_LEN];
+		if flags & FLAG_ALLOWLIST != 0
+		{
+			allowlist_root = read_hash(data, &mut offset)?;
+		}
+
+		let mut oracle_type_hash = [0u8; SCRIPT_HASH_LEN];
+		let mut oracle_spread = 0;
+		if flags & FLAG_ORACLE != 0
+		{
+			oracle_type_hash = read_hash(data, &mut offset)?;
+			oracle_spread = read_u16(data, &mut offset)?;
+		}
+
+		let mut bundle = Vec::new();
+		if flags & FLAG_BUNDLE != 0
+		{
+			bundle = read_token_amounts(data, &mut offset)?;
+		}
+
+		let mut alternatives = Vec::new();
+		if flags & FLAG_ALTERNATIVES != 0
+		{
+			alternatives = read_token_amounts(data, &mut offset)?;
+		}
+
+		let order = Order { owner_lock_hash, buy_amount, flags, price_denominator, payout_lock_hash, fee_lock_hash, fee_rate, auction_end_amount, auction_metric, auction_start, auction_end, allowlist_root, oracle_type_hash, oracle_spread, bundle, alternatives, min_payout_capacity: 0, max_payout_cells: 0, owner_pubkey_hash: None };
+		order.validate()?;
+
+		Ok(order)
+	}
+
+	/// Decode an order from the Molecule encoded data of a versioned Token Buy Lock cell, without the version byte.
+	fn from_molecule_data(data: &[u8], version: u8) -> Result<Self, Error>
+	{
+		// Verify the data against the table of its own version.
+		let mut owner_pubkey_hash = None;
+		match version
+		{
+			ORDER_VERSION_V1 =>
+			{
+				OrderDataV1Reader::from_slice(data).map_err(|_| Error::Encoding)?;
+			},
+			ORDER_VERSION_V2 =>
+			{
+				OrderDataV2Reader::from_slice(data).map_err(|_| Error::Encoding)?;
+			},
+			_ =>
+			{
+				let order_data = OrderDataReader::from_slice(data).map_err(|_| Error::Encoding)?;
+				owner_pubkey_hash = order_data.owner_pubkey_hash().to_opt().map(|pubkey_hash| to_pubkey_hash(pubkey_hash.raw_data()));
+			},
+		}
+
+		// Every version appends fields to the table of the previous one, so the fields of an earlier version are read the same way for all of the later ones.
+		let mut min_payout_capacity = 0;
+		let mut max_payout_cells = 0;
+		if version != ORDER_VERSION_V1
+		{
+			let order_data = OrderDataV2Reader::from_compatible_slice(data).map_err(|_| Error::Encoding)?;
+			min_payout_capacity = order_data.min_payout_capacity().to_opt().map_or(0, |capacity| to_u64(capacity.raw_data()));
+
+			// A limit of zero payout cells could never be filled.
+			max_payout_cells = match order_data.max_payout_cells().to_opt()
+			{
+				Some(cells) if to_u32(cells.raw_data()) == 0 => return Err(Error::Encoding),
+				Some(cells) => to_u32(cells.raw_data()),
+				None => 0,
+			};
+		}
+
+		let order_data = OrderDataV1Reader::from_compatible_slice(data).map_err(|_| Error::Encoding)?;
+
+		let mut flags = 0;
+		if to_bool(order_data.partial_fill().as_slice()[0])?
+		{
+			flags |= FLAG_PARTIAL_FILL;
+		}
+		if to_bool(order_data.bound_payment().as_slice()[0])?
+		{
+			flags |= FLAG_BOUND_PAYMENT;
+		}
+
+		let owner_lock_hash = to_hash(order_data.owner_lock_hash().raw_data());
+		let buy_amount = to_u128(order_data.buy_amount().raw_data());
+
+		let mut price_denominator = 0;
+		if let Some(denominator) = order_data.price_denominator().to_opt()
+		{
+			flags |= FLAG_PRICE;
+			price_denominator = to_u128(denominator.raw_data());
+		}
+
+		let mut payout_lock_hash = owner_lock_hash;
+		if let Some(lock_hash) = order_data.payout_lock_hash().to_opt()
+		{
+			flags |= FLAG_PAYOUT_LOCK;
+			payout_lock_hash = to_hash(lock_hash.raw_data());
+		}
+
+		let mut fee_lock_hash = [0u8; SCRIPT_HASH_LEN];
+		let mut fee_rate = 0;
+		if let Some(fee) = order_data.fee().to_opt()
+		{
+			flags |= FLAG_FEE;
+			fee_lock_hash = to_hash(fee.lock_hash().raw_data());
+			fee_rate = to_u16(fee.rate().raw_data());
+		}
+
+		let mut auction_end_amount = 0;
+		let mut auction_metric = 0;
+		let mut auction_start = 0;
+		let mut auction_end = 0;
+		if let Some(auction) = order_data.auction().to_opt()
+		{
+			flags |= FLAG_AUCTION;
+			auction_end_amount = to_u128(auction.end_amount().raw_data());
+			auction_metric = auction.metric().as_slice()[0];
+			auction_start = to_u64(auction.start().raw_data());
+			auction_end = to_u64(auction.end().raw_data());
+		}
+
+		let mut allowlist_root = [0u8; SCRIPT_HASH_LEN];
+		if let Some(root) = order_data.allowlist_root().to_opt()
+		{
+			flags |= FLAG_ALLOWLIST;
+			allowlist_root = to_hash(root.raw_data());
+		}
+
+		let mut oracle_type_hash = [0u8; SCRIPT_HASH_LEN];
+		let mut oracle_spread = 0;
+		if let Some(oracle) = order_data.oracle().to_opt()
+		{
+			flags |= FLAG_ORACLE;
+			oracle_type_hash = to_hash(oracle.type_hash().raw_data());
+			oracle_spread = to_u16(oracle.spread().raw_data());
+		}
+
+		let bundle = to_token_amounts(order_data.bundle());
+		if !bundle.is_empty()
+		{
+			flags |= FLAG_BUNDLE;
+		}
+
+		let alternatives = to_token_amounts(order_data.alternatives());
+		if !alternatives.is_empty()
+		{
+			flags |= FLAG_ALTERNATIVES;
+		}
+
+		let order = Order { owner_lock_hash, buy_amount, flags, price_denominator, payout_lock_hash, fee_lock_hash, fee_rate, auction_end_amount, auction_metric, auction_start, auction_end, allowlist_root, oracle_type_hash, oracle_spread, bundle, alternatives, min_payout_capacity, max_payout_cells, owner_pubkey_hash };
+		order.validate()?;
+
+		Ok(order)
+	}
+
+	/// Validate the order terms, which is shared by every data layout.
+	fn validate(&self) -> Result<(), Error>
+	{
+		// A price with a zero denominator is meaningless.
+		if self.is_price() && self.price_denominator == 0
+		{
+			return Err(Error::Encoding);
+		}
+
+		// The fee cannot be more than the entire payment.
+		if self.fee_rate > BASIS_POINTS_DENOMINATOR
+		{
+			return Err(Error::Encoding);
+		}
+
+		if self.is_auction()
+		{
+			// The auction must use a known metric and have a duration.
+			if (self.auction_metric != AUCTION_METRIC_BLOCK_NUMBER && self.auction_metric != AUCTION_METRIC_TIMESTAMP) || self.auction_end <= self.auction_start
+			{
+				return Err(Error::Encoding);
+			}
+
+			// The buy amount may only decay. A header dep only proves that a point in time has passed, so a buyer could use an old header to avoid a rising amount.
+			if self.auction_end_amount > self.buy_amount
+			{
+				return Err(Error::Encoding);
+			}
+
+			// Auctions cannot be combined with a price or partial fills, since both scale the buy amount by the capacity.
+			if self.flags & (FLAG_PRICE | FLAG_PARTIAL_FILL) != 0
+			{
+				return Err(Error::Encoding);
+			}
+		}
+
+		// The oracle replaces the price, so it cannot be combined with another way of setting the buy amount.
+		if self.is_oracle() && self.flags & (FLAG_PRICE | FLAG_AUCTION) != 0
+		{
+			return Err(Error::Encoding);
+		}
+
+		// Bundled amounts are fixed, so they cannot be scaled for a partial fill.
+		if self.flags & FLAG_BUNDLE != 0 && self.is_partial_fill()
+		{
+			return Err(Error::Encoding);
+		}
+
+		// Alternative amounts are fixed, so they cannot be scaled for a partial fill, combined with a bundle, or bound to the order before an alternative is chosen.
+		if self.has_alternatives() && self.flags & (FLAG_PARTIAL_FILL | FLAG_BUNDLE | FLAG_BOUND_PAYMENT) != 0
+		{
+			return Err(Error::Encoding);
+		}
+
+		Ok(())
+	}
+
+	/// Determine if another order has the same terms as this one, ignoring the buy amount.
+	pub fn has_same_terms(&self, other: &Order) -> bool
+	{
+		Order { buy_amount: self.buy_amount, ..other.clone() } == *self
+	}
+
+	/// Determine if the order allows partial fills.
+	pub fn is_partial_fill(&self) -> bool
+	{
+		self.flags & FLAG_PARTIAL_FILL != 0
+	}
+
+	/// Determine if the order is priced in tokens per shannon instead of a fixed buy amount.
+	pub fn is_price(&self) -> bool
+	{
+		self.flags & FLAG_PRICE != 0
+	}
+
+	/// Calculate the share of a payment which is sent to the fee lock hash, rounded down.
+	pub fn fee_amount(&self, payment_amount: u128) -> Result<u128, Error>
+	{
+		let fee_amount = payment_amount.checked_mul(self.fee_rate as u128).ok_or(Error::Overflow)?;
+
+		Ok(fee_amount / BASIS_POINTS_DENOMINATOR as u128)
+	}
+
+	/// Calculate the buy amount of an auction at the specified block number or timestamp, rounded up.
+	///
+	/// Without a block number or timestamp the auction has not been proven to start, and the full buy amount is required.
+	pub fn auction_amount(&self, auction_point: Option<u64>) -> Result<u128, Error>
+	{
+		let auction_point = match auction_point
+		{
+			Some(auction_point) if auction_point > self.auction_start => auction_point,
+			_ => return Ok(self.buy_amount),
+		};
+		if auction_point >= self.auction_end
+		{
+			return Ok(self.auction_end_amount);
+		}
+
+		// Rounding the decay down rounds the buy amount up.
+		let elapsed = (auction_point - self.auction_start) as u128;
+		let duration = (self.auction_end - self.auction_start) as u128;
+		let decay = (self.buy_amount - self.auction_end_amount).checked_mul(elapsed).ok_or(Error::Overflow)? / duration;
+
+		Ok(self.buy_amount - decay)
+	}
+
+	/// Determine if the order is an auction with a buy amount which decays over time.
+	pub fn is_auction(&self) -> bool
+	{
+		self.flags & FLAG_AUCTION != 0
+	}
+
+	/// Determine if the order can only be filled by an allowed buyer.
+	pub fn is_allowlist(&self) -> bool
+	{
+		self.flags & FLAG_ALLOWLIST != 0
+	}
+
+	/// Determine if the order is priced by an oracle cell in the cell deps.
+	pub fn is_oracle(&self) -> bool
+	{
+		self.flags & FLAG_ORACLE != 0
+	}
+
+	/// Determine if the order accepts alternative tokens instead of the SUDT in the args.
+	pub fn has_alternatives(&self) -> bool
+	{
+		self.flags & FLAG_ALTERNATIVES != 0
+	}
+
+	/// Determine if the order limits the cells used to pay the payout lock hash.
+	pub fn has_payout_cell_limits(&self) -> bool
+	{
+		self.min_payout_capacity > 0 || self.max_payout_cells > 0
+	}
+
+	/// Determine if the order must be paid with outputs bound to the order cell.
+	pub fn is_bound_payment(&self) -> bool
+	{
+		self.flags & FLAG_BOUND_PAYMENT != 0
+	}
+
+	/// Determine if the order explicitly sets its payout lock hash or fee lock hash to the owner lock hash.
+	/// An owner only does this to be paid into an existing cell of their own, such as an anyone-can-pay cell.
+	pub fn pays_owner_lock(&self) -> bool
+	{
+		(self.flags & FLAG_PAYOUT_LOCK != 0 && self.payout_lock_hash == self.owner_lock_hash) || (self.flags & FLAG_FEE != 0 && self.fee_lock_hash == self.owner_lock_hash)
+	}
+}
+
+/// Read a script hash from the data at the specified offset and advance the offset.
+fn read_hash(data: &[u8], offset: &mut usize) -> Result<[u8; SCRIPT_HASH_LEN], Error>
+{
+	if data.len() < *offset + SCRIPT_HASH_LEN
+	{
+		return Err(Error::DataLength);
+	}
+
+	let mut hash = [0u8; SCRIPT_HASH_LEN];
+	hash.copy_from_slice(&data[*offset..*offset+SCRIPT_HASH_LEN]);
+	*offset += SCRIPT_HASH_LEN;
+
+	Ok(hash)
+}
+
+/// Read a u128 from the data at the specified offset and advance the offset.
+fn read_u128(data: &[u8], offset: &mut usize) -> Result<u128, Error>
+{
+	if data.len() < *offset + SUDT_DATA_LEN
+	{
+		return Err(Error::DataLength);
+	}
+
+	let mut buffer = [0u8; SUDT_DATA_LEN];
+	buffer.copy_from_slice(&data[*offset..*offset+SUDT_DATA_LEN]);
+	*offset += SUDT_DATA_LEN;
+
+	Ok(u128::from_le_bytes(buffer))
+}
+
+/// Read a u16 from the data at the specified offset and advance the offset.
+fn read_u16(data: &[u8], offset: &mut usize) -> Result<u16, Error>
+{
+	if data.len() < *offset + BASIS_POINTS_LEN
+	{
+		return Err(Error::DataLength);
+	}
+
+	let mut buffer = [0u8; BASIS_POINTS_LEN];
+	buffer.copy_from_slice(&data[*offset..*offset+BASIS_POINTS_LEN]);
+	*offset += BASIS_POINTS_LEN;
+
+	Ok(u16::from_le_bytes(buffer))
+}
+
+/// Read a u8 from the data at the specified offset and advance the offset.
+fn read_u8(data: &[u8], offset: &mut usize) -> Result<u8, Error>
+{
+	if data.len() < *offset + COUNT_LEN
+	{
+		return Err(Error::DataLength);
+	}
+
+	let value = data[*offset];
+	*offset += COUNT_LEN;
+
+	Ok(value)
+}
+
+/// Read a u64 from the data at the specified offset and advance the offset.
+fn read_u64(data: &[u8], offset: &mut usize) -> Result<u64, Error>
+{
+	if data.len() < *offset + AUCTION_POINT_LEN
+	{
+		return Err(Error::DataLength);
+	}
+
+	let mut buffer = [0u8; AUCTION_POINT_LEN];
+	buffer.copy_from_slice(&data[*offset..*offset+AUCTION_POINT_LEN]);
+	*offset += AUCTION_POINT_LEN;
+
+	Ok(u64::from_le_bytes(buffer))
+}
+
+/// Read a u8 count from the data at the specified offset, followed by that many type hash and u128 amount pairs, and advance the offset.
+fn read_token_amounts(data: &[u8], offset: &mut usize) -> Result<Vec<([u8; SCRIPT_HASH_LEN], u128)>, Error>
+{
+	let count = read_u8(data, offset)?;
+
+	// A flag with an empty list is rejected, since it requires nothing.
+	if count == 0
+	{
+		return Err(Error::Encoding);
+	}
+
+	let mut token_amounts = Vec::new();
+	for _ in 0..count
+	{
+		let type_hash = read_hash(data, offset)?;
+		let amount = read_u128(data, offset)?;
+		token_amounts.push((type_hash, amount));
+	}
+
+	Ok(token_amounts)
+}
+
+/// Convert a Molecule byte to a bool. Only zero and one are valid.
+fn to_bool(value: u8) -> Result<bool, Error>
+{
+	match value
+	{
+		0 => Ok(false),
+		1 => Ok(true),
+		_ => Err(Error::Encoding),
+	}
+}
+
+/// Convert a slice with the length of a script hash to an array. The length is verified by Molecule or the caller.
+fn to_hash(data: &[u8]) -> [u8; SCRIPT_HASH_LEN]
+{
+	let mut hash = [0u8; SCRIPT_HASH_LEN];
+	hash.copy_from_slice(data);
+
+	hash
+}
+
+/// Convert a Molecule Byte20 to a public key hash.
+fn to_pubkey_hash(data: &[u8]) -> [u8; PUBKEY_HASH_LEN]
+{
+	let mut pubkey_hash = [0u8; PUBKEY_HASH_LEN];
+	pubkey_hash.copy_from_slice(data);
+
+	pubkey_hash
+}
+
+/// Convert a Molecule Uint16 to a u16.
+fn to_u16(data: &[u8]) -> u16
+{
+	let mut buffer = [0u8; BASIS_POINTS_LEN];
+	buffer.copy_from_slice(data);
+
+	u16::from_le_bytes(buffer)
+}
+
+/// Convert a Molecule Uint32 to a u32.
+fn to_u32(data: &[u8]) -> u32
+{
+	let mut buffer = [0u8; PAYOUT_CELLS_LEN];
+	buffer.copy_from_slice(data);
+
+	u32::from_le_bytes(buffer)
+}
+
+/// Convert a Molecule Uint64 to a u64.
+fn to_u64(data: &[u8]) -> u64
+{
+	let mut buffer = [0u8; AUCTION_POINT_LEN];
+	buffer.copy_from_slice(data);
+
+	u64::from_le_bytes(buffer)
+}
+
+/// Convert a Molecule Uint128 to a u128.
+fn to_u128(data: &[u8]) -> u128
+{
+	let mut buffer = [0u8; SUDT_DATA_LEN];
+	buffer.copy_from_slice(data);
+
+	u128::from_le_bytes(buffer)
+}
+
+/// Convert a Molecule TokenAmountVec to type hash and u128 amount pairs.
+fn to_token_amounts(token_amounts: TokenAmountVecReader) -> Vec<([u8; SCRIPT_HASH_LEN], u128)>
+{
+	token_amounts.iter().map(|token_amount| (to_hash(token_amount.type_hash().raw_data()), to_u128(token_amount.amount().raw_data()))).collect()
+}
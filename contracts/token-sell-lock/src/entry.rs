@@ -0,0 +1,154 @@
+// Import from alloc core instead of from std since we are in no-std mode.
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+use core::result::Result;
+
+// Import CKB syscalls and structures.
+// https://nervosnetwork.github.io/ckb-std/riscv64imac-unknown-none-elf/doc/ckb_std/index.html
+// use ckb_std::debug;
+use ckb_std::ckb_constants::Source;
+use ckb_std::ckb_types::{bytes::Bytes, prelude::*};
+use ckb_std::high_level::{load_script, load_cell_capacity, load_cell_lock_hash, load_cell_type_hash, QueryIter};
+
+// Import the owner mode and grouping shared with the other locks.
+use token_lock_common::owner::check_group_owner_mode;
+use token_lock_common::requirements::{add_requirement, calculate_lock_requirements};
+
+// Import our local error codes.
+use crate::error::Error;
+
+// Constants
+const SCRIPT_HASH_LEN: usize = 32; // Number of bytes for a lock hash. (Blake2b 256-bit 32 bytes)
+const SUDT_DATA_LEN: usize = 16; // SUDT uses a u128, which is 16 bytes.
+const CAPACITY_LEN: usize = 8; // Capacity is a u64, which is 8 bytes.
+
+/// Extract the owner lock hash and sell price from Token Sell Lock cell data.
+///
+/// The cell data layout is:
+/// - `[0..16]` SUDT amount. (u128, managed by the SUDT type script)
+/// - `[16..48]` Owner lock hash.
+/// - `[48..56]` Sell price in shannons. (u64)
+fn parse_order_data(data: &[u8]) -> Result<(Vec<u8>, u64), Error>
+{
+	// If the data is less than the required length, return an error.
+	if data.len() < SUDT_DATA_LEN + SCRIPT_HASH_LEN + CAPACITY_LEN
+	{
+		return Err(Error::DataLength);
+	}
+
+	// Extract owner lock hash and sell price.
+	let owner_lock_hash = data[SUDT_DATA_LEN..SUDT_DATA_LEN+SCRIPT_HASH_LEN].to_vec();
+	let mut buffer = [0u8; CAPACITY_LEN];
+	buffer.copy_from_slice(&data[SUDT_DATA_LEN+SCRIPT_HASH_LEN..SUDT_DATA_LEN+SCRIPT_HASH_LEN+CAPACITY_LEN]);
+	let sell_price = u64::from_le_bytes(buffer);
+
+	Ok((owner_lock_hash, sell_price))
+}
+
+/// Verify that every Token Sell Lock cell holds the SUDT specified in the args.
+fn validate_token_type(sudt_type_script_hash: &[u8]) -> Result<(), Error>
+{
+	for type_hash in QueryIter::new(load_cell_type_hash, Source::GroupInput)
+	{
+		match type_hash
+		{
+			Some(type_hash) if type_hash[..] == sudt_type_script_hash[..] => {},
+			_ => return Err(Error::TypeHash),
+		}
+	}
+
+	Ok(())
+}
+
+/// Calculate the required CKBytes from all token sell cells, grouped by owner lock hash.
+///
+/// Every order is paid in CKBytes whatever SUDT it sells, so the cells of other script groups are included and the same capacity is never counted twice.
+fn calculate_token_sell_cell_requirements() -> Result<BTreeMap<Vec<u8>, u64>, Error>
+{
+	let mut requirements = BTreeMap::new();
+	for group_requirements in calculate_lock_requirements(parse_order_data)?.values()
+	{
+		for (owner_lock_hash, sell_price) in group_requirements
+		{
+			add_requirement(&mut requirements, owner_lock_hash.clone(), *sell_price)?;
+		}
+	}
+
+	Ok(requirements)
+}
+
+/// Count the capacity in the specified source with the specified lock hash.
+/// Only cells without a type script are counted so the payment is always spendable by the owner.
+fn determine_capacity_amount(source: Source, lock_hash: &[u8]) -> Result<u64, Error>
+{
+	// Track the amount of capacity that is counted.
+	let mut total_capacity: u64 = 0;
+
+	// Cycle through the lock hash of each cell within the specified source.
+	let cell_lock_hashes = QueryIter::new(load_cell_lock_hash, source);
+	for (i, cell_lock_hash) in cell_lock_hashes.enumerate()
+	{
+		// Skip any cells with a type script.
+		if load_cell_type_hash(i, source)?.is_some() { continue; }
+
+		// Add the capacity of the cell if the lock hash on the cell matches the specified.
+		if cell_lock_hash[..] == lock_hash[..]
+		{
+			let capacity = load_cell_capacity(i, source)?;
+			total_capacity = total_capacity.checked_add(capacity).ok_or(Error::Overflow)?;
+		}
+	}
+
+	// Return the total capacity found in the specified source.
+	Ok(total_capacity)
+}
+
+/// Validate the token sell requirements against the output capacity.
+fn validate_token_sell_requirements(token_sell_requirements: &BTreeMap<Vec<u8>, u64>) -> Result<(), Error>
+{
+	for (owner_lock_hash, sell_price) in token_sell_requirements
+	{
+		// Count the capacity being sent to the owner lock hash.
+		let output_capacity = determine_capacity_amount(Source::Output, &owner_lock_hash[..])?;
+
+		// If not enough capacity was provided, return an error.
+		if output_capacity < *sell_price
+		{
+			return Err(Error::Amount);
+		}
+	}
+
+	Ok(())
+}
+
+// Main entry point.
+pub fn main() -> Result<(), Error>
+{
+	// Load the currently executing script and get the args.
+	let script = load_script()?;
+	let args: Bytes = script.args().unpack();
+
+	// Verify the the arguments length matches the length of a single Blake2b hash.
+	if args.len() != SCRIPT_HASH_LEN
+	{
+		return Err(Error::ArgsLength);
+	}
+
+	// Check if the script is being run by the owner and immediately return success if true.
+	if check_group_owner_mode(|data| parse_order_data(data).map(|(owner_lock_hash, _)| owner_lock_hash))?
+	{
+		return Ok(());
+	}
+
+	// Verify that the cells being sold hold the SUDT specified in the args.
+	validate_token_type(&args[0..SCRIPT_HASH_LEN])?;
+
+	// Calculate the CKBytes required by each owner.
+	let token_sell_requirements = calculate_token_sell_cell_requirements()?;
+
+	// Ensure that the token sell requirements have been met.
+	validate_token_sell_requirements(&token_sell_requirements)?;
+
+	// No errors were found during validation. Return success.
+	Ok(())
+}
@@ -0,0 +1,193 @@
+// Import from alloc core instead of from std since we are in no-std mode.
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+use core::result::Result;
+
+// Import CKB syscalls and structures.
+// https://nervosnetwork.github.io/ckb-std/riscv64imac-unknown-none-elf/doc/ckb_std/index.html
+// use ckb_std::debug;
+use ckb_std::ckb_constants::Source;
+use ckb_std::ckb_types::{bytes::Bytes, prelude::*};
+use ckb_std::ckb_types::packed::Script;
+use ckb_std::high_level::{load_script, load_cell_lock_hash, load_cell_type, load_cell_type_hash, load_cell_data, QueryIter};
+
+// Import the owner mode and grouping shared with the other locks.
+use token_lock_common::owner::check_group_owner_mode;
+use token_lock_common::requirements::calculate_lock_requirements;
+
+// Import our local error codes.
+use crate::error::Error;
+
+// Constants
+const SCRIPT_HASH_LEN: usize = 32; // Number of bytes for a lock hash. (Blake2b 256-bit 32 bytes)
+const SUDT_DATA_LEN: usize = 16; // SUDT uses a u128, which is 16 bytes.
+const BINDING_MARKER: &[u8] = b"TBLB"; // SUDT data with this marker after the amount is bound to a Token Buy Lock order, and is never a swap payment.
+
+/// Extract the owner lock hash and wanted amount from Token Swap Lock cell data.
+///
+/// The cell data layout is:
+/// - `[0..16]` Offered SUDT amount. (u128, managed by the SUDT type script)
+/// - `[16..48]` Owner lock hash.
+/// - `[48..64]` Wanted SUDT amount. (u128)
+fn parse_order_data(data: &[u8]) -> Result<(Vec<u8>, u128), Error>
+{
+	// If the data is less than the required length, return an error.
+	if data.len() < SUDT_DATA_LEN + SCRIPT_HASH_LEN + SUDT_DATA_LEN
+	{
+		return Err(Error::DataLength);
+	}
+
+	// Extract owner lock hash and wanted amount.
+	let owner_lock_hash = data[SUDT_DATA_LEN..SUDT_DATA_LEN+SCRIPT_HASH_LEN].to_vec();
+	let mut buffer = [0u8; SUDT_DATA_LEN];
+	buffer.copy_from_slice(&data[SUDT_DATA_LEN+SCRIPT_HASH_LEN..SUDT_DATA_LEN+SCRIPT_HASH_LEN+SUDT_DATA_LEN]);
+	let wanted_amount = u128::from_le_bytes(buffer);
+
+	Ok((owner_lock_hash, wanted_amount))
+}
+
+/// Determine if the code hash and hash type of two type scripts match, so they are the same kind of token.
+fn is_same_token_code(a: &Script, b: &Script) -> bool
+{
+	a.code_hash().as_slice() == b.code_hash().as_slice() && a.hash_type().as_slice() == b.hash_type().as_slice()
+}
+
+/// Verify that every Token Swap Lock cell holds an SUDT which is different from the wanted SUDT specified in the args.
+///
+/// Returns the type script of the first cell. Every offered cell must use the same SUDT code, which is also required of the payment.
+fn validate_token_type(wanted_type_script_hash: &[u8]) -> Result<Script, Error>
+{
+	let sudt_type_script = load_cell_type(0, Source::GroupInput)?.ok_or(Error::TypeHash)?;
+	for (i, type_hash) in QueryIter::new(load_cell_type_hash, Source::GroupInput).enumerate()
+	{
+		match type_hash
+		{
+			Some(type_hash) if type_hash[..] != wanted_type_script_hash[..] => {},
+			_ => return Err(Error::TypeHash),
+		}
+
+		let type_script = load_cell_type(i, Source::GroupInput)?.ok_or(Error::TypeHash)?;
+		if !is_same_token_code(&type_script, &sudt_type_script)
+		{
+			return Err(Error::TypeHash);
+		}
+	}
+
+	Ok(sudt_type_script)
+}
+
+/// Calculate the required SUDT tokens from all token swap cells wanting the SUDT in the args, grouped by owner lock hash.
+///
+/// Every input with the same code hash and hash type is counted. Orders for other wanted SUDTs are paid in different tokens, so they are validated by their own script groups.
+fn calculate_token_swap_cell_requirements(wanted_type_script_hash: &[u8]) -> Result<BTreeMap<Vec<u8>, u128>, Error>
+{
+	let mut requirements = calculate_lock_requirements(parse_order_data)?;
+
+	Ok(requirements.remove(wanted_type_script_hash).unwrap_or_default())
+}
+
+/// Count the number of tokens in the specified source with the specified lock hash and type hash.
+/// A cell with the type hash must use the SUDT code, otherwise its data is not a token amount.
+/// Cells bound to a Token Buy Lock order are skipped, since they can only pay that order.
+fn determine_token_amount(source: Source, lock_hash: &[u8], type_hash: &[u8], sudt_type_script: &Script) -> Result<u128, Error>
+{
+	// Track the number of tokens that are counted.
+	let mut total_token_amount: u128 = 0;
+
+	// Cycle through the data in each cell within the specified source.
+	let cell_data = QueryIter::new(load_cell_data, source);
+	for (i, data) in cell_data.enumerate()
+	{
+		// Extract the type script hash from the current cell.
+		let cell_type_hash = load_cell_type_hash(i, source)?;
+        if cell_type_hash.is_none() { continue; }
+        let cell_type_hash = cell_type_hash.unwrap();
+
+		// Extract the lock script hash from the current cell.
+		let cell_lock_hash = load_cell_lock_hash(i, source)?;
+
+        // Check that the length of the data is >= 16 bytes, the size of a u128 and that the lock hash on the cell matches the specified.
+		if cell_lock_hash == lock_hash && cell_type_hash == type_hash
+		{
+			// The wanted token must be an SUDT, the same as the offered token.
+			match load_cell_type(i, source)?
+			{
+				Some(type_script) if is_same_token_code(&type_script, sudt_type_script) => {},
+				_ => return Err(Error::TypeHash),
+			}
+
+			// If the data is less than 16 bytes, then return an encoding error.
+			if data.len() < SUDT_DATA_LEN
+			{
+				return Err(Error::Encoding);
+			}
+
+			// Skip payments which are bound to an order of the Token Buy Lock.
+			if data[SUDT_DATA_LEN..].starts_with(BINDING_MARKER)
+			{
+				continue;
+			}
+
+			// Convert the binary data in the cell to a u128 value.
+			let mut buffer = [0u8; SUDT_DATA_LEN];
+			buffer.copy_from_slice(&data[0..SUDT_DATA_LEN]);
+			let amount = u128::from_le_bytes(buffer);
+
+			// Add the amount of tokens in the cell to the total amount of tokens.
+			total_token_amount = total_token_amount.checked_add(amount).ok_or(Error::Overflow)?;
+		}
+	}
+
+	// Return the total amount of tokens found in the specified source.
+	Ok(total_token_amount)
+}
+
+/// Validate the token swap requirements against the output token amounts.
+fn validate_token_swap_requirements(token_swap_requirements: &BTreeMap<Vec<u8>, u128>, wanted_type_script_hash: &[u8], sudt_type_script: &Script) -> Result<(), Error>
+{
+	for (owner_lock_hash, wanted_amount) in token_swap_requirements
+	{
+		// Count the number of wanted tokens being sent to the owner lock hash.
+		let output_token_amount = determine_token_amount(Source::Output, &owner_lock_hash[..], wanted_type_script_hash, sudt_type_script)?;
+
+		// If not enough tokens were provided, return an error.
+		if output_token_amount < *wanted_amount
+		{
+			return Err(Error::Amount);
+		}
+	}
+
+	Ok(())
+}
+
+// Main entry point.
+pub fn main() -> Result<(), Error>
+{
+	// Load the currently executing script and get the args.
+	let script = load_script()?;
+	let args: Bytes = script.args().unpack();
+
+	// Verify the the arguments length matches the length of a single Blake2b hash.
+	if args.len() != SCRIPT_HASH_LEN
+	{
+		return Err(Error::ArgsLength);
+	}
+
+	// Check if the script is being run by the owner and immediately return success if true.
+	if check_group_owner_mode(|data| parse_order_data(data).map(|(owner_lock_hash, _)| owner_lock_hash))?
+	{
+		return Ok(());
+	}
+
+	// Verify that the cells being offered do not hold the wanted SUDT specified in the args.
+	let sudt_type_script = validate_token_type(&args[0..SCRIPT_HASH_LEN])?;
+
+	// Calculate the wanted SUDT tokens required by each owner.
+	let token_swap_requirements = calculate_token_swap_cell_requirements(&args[0..SCRIPT_HASH_LEN])?;
+
+	// Ensure that the token swap requirements have been met.
+	validate_token_swap_requirements(&token_swap_requirements, &args[0..SCRIPT_HASH_LEN], &sudt_type_script)?;
+
+	// No errors were found during validation. Return success.
+	Ok(())
+}
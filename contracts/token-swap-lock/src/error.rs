@@ -0,0 +1,42 @@
+use ckb_std::error::SysError;
+use token_lock_common::requirements::Overflow;
+
+/// Error
+#[repr(i8)]
+pub enum Error
+{
+	IndexOutOfBound = 1,
+	ItemMissing,
+	LengthNotEnough,
+	Encoding,
+	// Add customized errors here...
+	Amount,
+	ArgsLength,
+	DataLength,
+	TypeHash,
+	Overflow,
+}
+
+impl From<SysError> for Error
+{
+	fn from(err: SysError) -> Self
+	{
+		use SysError::*;
+		match err
+		{
+			IndexOutOfBound => Self::IndexOutOfBound,
+			ItemMissing => Self::ItemMissing,
+			LengthNotEnough(_) => Self::LengthNotEnough,
+			Encoding => Self::Encoding,
+			Unknown(err_code) => panic!("unexpected sys error {}", err_code),
+		}
+	}
+}
+
+impl From<Overflow> for Error
+{
+	fn from(_: Overflow) -> Self
+	{
+		Self::Overflow
+	}
+}
@@ -0,0 +1,35 @@
+#!/usr/bin/env bash
+# Build the secp256k1 library from nervosnetwork/ckb-miscellaneous-scripts and place it in the deps directory.
+#
+# The library is only accepted if its data hash matches the one pinned in deps/secp256k1_blake2b_sighash_all_dual.blake2b,
+# which is the same hash the build script of the common crate checks. Set SECP256K1_LIB_REF to build from another ref.
+set -euo pipefail
+
+ROOT="$(cd "$(dirname "${BASH_SOURCE[0]}")/.." && pwd)"
+DEPS="$ROOT/deps"
+NAME="secp256k1_blake2b_sighash_all_dual"
+REPOSITORY="https://github.com/nervosnetwork/ckb-miscellaneous-scripts.git"
+REF="${SECP256K1_LIB_REF:-master}"
+
+EXPECTED="$(tr -d '[:space:]' < "$DEPS/$NAME.blake2b")"
+if [ -z "$EXPECTED" ]; then
+	echo "No data hash is pinned in deps/$NAME.blake2b." >&2
+	exit 1
+fi
+
+WORK="$(mktemp -d)"
+trap 'rm -rf "$WORK"' EXIT
+
+git clone --quiet "$REPOSITORY" "$WORK/scripts"
+git -C "$WORK/scripts" checkout --quiet "$REF"
+git -C "$WORK/scripts" submodule update --quiet --init --recursive
+make -C "$WORK/scripts" all-via-docker
+
+ACTUAL="$(python3 -c 'import hashlib, sys; print(hashlib.blake2b(open(sys.argv[1], "rb").read(), digest_size=32, person=b"ckb-default-hash").hexdigest())' "$WORK/scripts/build/$NAME")"
+if [ "$ACTUAL" != "$EXPECTED" ]; then
+	echo "The data hash of $NAME is $ACTUAL, but $EXPECTED is pinned." >&2
+	exit 1
+fi
+
+cp "$WORK/scripts/build/$NAME" "$DEPS/$NAME"
+echo "Placed $NAME with data hash $ACTUAL in deps."
@@ -0,0 +1,267 @@
+use std::cmp::{Ordering, Reverse};
+use std::collections::BTreeMap;
+
+use ckb_types::core::ScriptHashType;
+use ckb_types::prelude::*;
+
+use crate::cell::LiveCell;
+use crate::error::Error;
+use crate::order::{LockArgs, Order};
+use crate::provider::{CellProvider, CellQuery};
+use crate::SCRIPT_HASH_LEN;
+
+/// The price of an order in shannons per token, kept as an exact ratio.
+///
+/// Prices are compared exactly, so two prices with the same ratio are equal. A price for zero tokens is higher than any price for some tokens,
+/// and prices for zero tokens are compared by capacity alone, so every price has a consistent place in the ordering.
+#[derive(Clone, Copy, Debug)]
+pub struct Price
+{
+	pub capacity: u128,
+	pub amount: u128,
+}
+
+impl Price
+{
+	/// Create the price of a capacity in shannons for an amount of tokens.
+	pub fn new(capacity: u128, amount: u128) -> Self
+	{
+		Price { capacity, amount }
+	}
+
+	/// The approximate price in shannons per token, for display.
+	pub fn as_f64(&self) -> f64
+	{
+		self.capacity as f64 / self.amount as f64
+	}
+}
+
+impl Ord for Price
+{
+	fn cmp(&self, other: &Self) -> Ordering
+	{
+		match (self.amount == 0, other.amount == 0)
+		{
+			// The cross products of a zero amount would be zero, which would make it equal to every price.
+			(true, true) => self.capacity.cmp(&other.capacity),
+			(true, false) => Ordering::Greater,
+			(false, true) => Ordering::Less,
+			// Compare the cross products, which need up to 256 bits.
+			(false, false) => mul_wide(self.capacity, other.amount).cmp(&mul_wide(other.capacity, self.amount)),
+		}
+	}
+}
+
+impl PartialOrd for Price
+{
+	fn partial_cmp(&self, other: &Self) -> Option<Ordering>
+	{
+		Some(self.cmp(other))
+	}
+}
+
+impl PartialEq for Price
+{
+	fn eq(&self, other: &Self) -> bool
+	{
+		self.cmp(other) == Ordering::Equal
+	}
+}
+
+impl Eq for Price {}
+
+/// An order offering capacity for SUDT tokens.
+#[derive(Clone, Debug)]
+pub struct Bid
+{
+	pub cell: LiveCell,
+	pub lock_args: LockArgs,
+	pub order: Order,
+	/// The tokens required to fill the entire order.
+	pub amount: u128,
+	/// The capacity of the order cell divided by the tokens required to fill it.
+	pub price: Price,
+}
+
+/// A Token Buy Lock cell whose args or data cannot be decoded, so it can never be filled.
+#[derive(Clone, Debug)]
+pub struct InvalidCell
+{
+	pub cell: LiveCell,
+	pub error: Error,
+}
+
+/// The total size of a set of bids.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Depth
+{
+	pub orders: usize,
+	pub capacity: u64,
+	pub amount: u128,
+}
+
+/// The Token Buy Lock orders on chain, grouped by the SUDT type hash in their args.
+///
+/// Bids are sorted by price, best first. Auctions are priced at their buy amount, which is the most a buyer will ever pay.
+/// Orders priced by an oracle cannot be ranked without the oracle cell, and orders which need other tokens or an allowlist proof cannot be filled
+/// with the SUDT alone, so they are kept separately.
+#[derive(Clone, Debug, Default)]
+pub struct OrderBook
+{
+	bids: BTreeMap<[u8; SCRIPT_HASH_LEN], Vec<Bid>>,
+	unpriced: Vec<Bid>,
+	invalid: Vec<InvalidCell>,
+}
+
+impl OrderBook
+{
+	/// Load every order cell of the Token Buy Lock with the code hash and hash type from a cell provider.
+	pub fn load<P: CellProvider>(provider: &P, token_buy_lock_code_hash: [u8; SCRIPT_HASH_LEN], hash_type: ScriptHashType) -> Result<Self, Error>
+	{
+		let cells = provider.collect_cells(&CellQuery::by_lock_code_hash(token_buy_lock_code_hash, hash_type))?;
+
+		Ok(OrderBook::from_cells(cells))
+	}
+
+	/// Create an order book from Token Buy Lock order cells.
+	pub fn from_cells<I: IntoIterator<Item = LiveCell>>(cells: I) -> Self
+	{
+		let mut book = OrderBook::default();
+		for cell in cells
+		{
+			match decode_bid(&cell)
+			{
+				Ok((lock_args, order, amount)) =>
+				{
+					let capacity: u64 = cell.output.capacity().unpack();
+					let bid = Bid { cell, lock_args, order, amount, price: Price::new(capacity as u128, amount) };
+					if !is_priced(&bid.order)
+					{
+						book.unpriced.push(bid);
+					}
+					else
+					{
+						book.bids.entry(bid.lock_args.sudt_type_hash).or_insert_with(Vec::new).push(bid);
+					}
+				},
+				Err(error) => book.invalid.push(InvalidCell { cell, error }),
+			}
+		}
+
+		// Sort from the highest price to the lowest. The sort is stable, so equal prices stay in the order they were found.
+		for bids in book.bids.values_mut()
+		{
+			bids.sort_by_key(|bid| Reverse(bid.price));
+		}
+
+		book
+	}
+
+	/// The SUDT type hashes which have bids.
+	pub fn sudt_type_hashes(&self) -> impl Iterator<Item = &[u8; SCRIPT_HASH_LEN]>
+	{
+		self.bids.keys()
+	}
+
+	/// The bids for an SUDT, best first.
+	pub fn bids(&self, sudt_type_hash: &[u8; SCRIPT_HASH_LEN]) -> &[Bid]
+	{
+		self.bids.get(sudt_type_hash).map_or(&[], |bids| &bids[..])
+	}
+
+	/// The bid with the highest price for an SUDT.
+	pub fn best_bid(&self, sudt_type_hash: &[u8; SCRIPT_HASH_LEN]) -> Option<&Bid>
+	{
+		self.bids(sudt_type_hash).first()
+	}
+
+	/// The total size of the bids for an SUDT at or above a price.
+	pub fn depth(&self, sudt_type_hash: &[u8; SCRIPT_HASH_LEN], price: &Price) -> Result<Depth, Error>
+	{
+		let mut depth = Depth::default();
+		for bid in self.bids(sudt_type_hash).iter().take_while(|bid| bid.price >= *price)
+		{
+			add_bid(&mut depth, bid)?;
+		}
+
+		Ok(depth)
+	}
+
+	/// The total size of the bids for an SUDT at each price, best first.
+	pub fn levels(&self, sudt_type_hash: &[u8; SCRIPT_HASH_LEN]) -> Result<Vec<(Price, Depth)>, Error>
+	{
+		let mut levels: Vec<(Price, Depth)> = Vec::new();
+		for bid in self.bids(sudt_type_hash)
+		{
+			match levels.last_mut()
+			{
+				Some((price, depth)) if *price == bid.price => add_bid(depth, bid)?,
+				_ =>
+				{
+					let mut depth = Depth::default();
+					add_bid(&mut depth, bid)?;
+					levels.push((bid.price, depth));
+				},
+			}
+		}
+
+		Ok(levels)
+	}
+
+	/// Valid orders which are priced by an oracle, or need other tokens or an allowlist proof.
+	pub fn unpriced(&self) -> &[Bid]
+	{
+		&self.unpriced
+	}
+
+	/// Cells which cannot be decoded, and must not be shown as fillable.
+	pub fn invalid(&self) -> &[InvalidCell]
+	{
+		&self.invalid
+	}
+}
+
+/// Determine if an order can be ranked by its price in the SUDT of its args alone.
+fn is_priced(order: &Order) -> bool
+{
+	order.oracle.is_none() && order.allowlist_root.is_none() && order.bundle.is_empty() && order.alternatives.is_empty()
+}
+
+/// Decode an order cell and calculate the tokens required to fill all of it.
+fn decode_bid(cell: &LiveCell) -> Result<(LockArgs, Order, u128), Error>
+{
+	let lock_args = LockArgs::from_args(&cell.output.lock().args().raw_data())?;
+	let order = Order::from_data(&cell.data, lock_args.version)?;
+
+	let amount = order.fill_amount(cell.output.capacity().unpack())?;
+
+	Ok((lock_args, order, amount))
+}
+
+/// Add a bid to a depth.
+fn add_bid(depth: &mut Depth, bid: &Bid) -> Result<(), Error>
+{
+	depth.orders += 1;
+	depth.capacity = depth.capacity.checked_add(bid.cell.output.capacity().unpack()).ok_or(Error::Overflow)?;
+	depth.amount = depth.amount.checked_add(bid.amount).ok_or(Error::Overflow)?;
+
+	Ok(())
+}
+
+/// Multiply two u128 values, returning the high and low 128 bits of the 256 bit product.
+fn mul_wide(a: u128, b: u128) -> (u128, u128)
+{
+	const MASK: u128 = u64::MAX as u128;
+	let (a_high, a_low) = (a >> 64, a & MASK);
+	let (b_high, b_low) = (b >> 64, b & MASK);
+
+	let low = a_low * b_low;
+	let cross_a = a_high * b_low;
+	let cross_b = a_low * b_high;
+	let middle = (low >> 64) + (cross_a & MASK) + (cross_b & MASK);
+
+	let high = a_high * b_high + (cross_a >> 64) + (cross_b >> 64) + (middle >> 64);
+	let low = (low & MASK) | (middle << 64);
+
+	(high, low)
+}
@@ -0,0 +1,145 @@
+use std::collections::BTreeMap;
+
+use ckb_types::bytes::Bytes;
+use ckb_types::core::{ScriptHashType, TransactionBuilder, TransactionView};
+use ckb_types::packed::{Byte32, CellDep, CellInput, CellOutput};
+use ckb_types::prelude::*;
+
+use crate::cell::{has_lock_code, occupied_capacity, LiveCell};
+use crate::error::Error;
+use crate::order::{LockArgs, Order};
+use crate::SCRIPT_HASH_LEN;
+
+/// Builds a transaction which cancels Token Buy Lock orders in owner mode.
+///
+/// An order is unlocked when a cell with its owner lock is also an input, so one cell of every owner must be provided.
+/// Owner cells must be plain capacity cells with no type script and no data, since only their capacity is returned.
+/// Each owner receives one output holding the capacity of their orders and their own cells. The transaction fee is paid by the first owner cell.
+/// The witnesses needed to unlock the owner cells are not added.
+///
+/// An order with an owner public key hash can also be cancelled with a signature instead of an owner cell, which this builder does not build.
+/// The owner signs the raw transaction hash, which is not the sighash-all message of the default lock, and the 65 byte recoverable signature is placed in the lock field of the witness for the order input.
+/// The cell deps of the secp256k1 library and the secp256k1_data cell must also be added.
+pub struct CancelBuilder
+{
+	code_hash: Byte32,
+	hash_type: ScriptHashType,
+	cell_deps: Vec<CellDep>,
+	orders: Vec<LiveCell>,
+	owner_cells: Vec<LiveCell>,
+	fee: u64,
+}
+
+impl CancelBuilder
+{
+	/// Create a builder with the code hash and hash type of the deployed Token Buy Lock, and its cell dep.
+	pub fn new(code_hash: Byte32, hash_type: ScriptHashType, token_buy_lock_dep: CellDep) -> Self
+	{
+		CancelBuilder { code_hash, hash_type, cell_deps: vec![token_buy_lock_dep], orders: Vec::new(), owner_cells: Vec::new(), fee: 0 }
+	}
+
+	/// Add a cell dep, such as the cell dep of an owner lock.
+	pub fn cell_dep(mut self, cell_dep: CellDep) -> Self
+	{
+		self.cell_deps.push(cell_dep);
+		self
+	}
+
+	/// Add a Token Buy Lock order cell to cancel.
+	pub fn order(mut self, cell: LiveCell) -> Self
+	{
+		self.orders.push(cell);
+		self
+	}
+
+	/// Add a cell with the lock of an owner, which proves ownership of their orders.
+	pub fn owner_cell(mut self, cell: LiveCell) -> Self
+	{
+		self.owner_cells.push(cell);
+		self
+	}
+
+	/// Set the transaction fee in shannons.
+	pub fn fee(mut self, fee: u64) -> Self
+	{
+		self.fee = fee;
+		self
+	}
+
+	/// Build the cancellation transaction.
+	pub fn build(&self) -> Result<TransactionView, Error>
+	{
+		// Sum the capacity of the owner cells by owner lock hash, keeping the order the owners were added in.
+		let mut owners = Vec::new();
+		let mut capacities: BTreeMap<[u8; SCRIPT_HASH_LEN], u64> = BTreeMap::new();
+		for cell in &self.owner_cells
+		{
+			if cell.output.type_().to_opt().is_some() || !cell.data.is_empty()
+			{
+				return Err(Error::OwnerCell);
+			}
+
+			let lock_hash: [u8; SCRIPT_HASH_LEN] = cell.output.lock().calc_script_hash().unpack();
+			if !capacities.contains_key(&lock_hash)
+			{
+				owners.push(cell.output.lock());
+			}
+			add_capacity(&mut capacities, lock_hash, cell.output.capacity().unpack())?;
+		}
+
+		// Every order must be a Token Buy Lock cell owned by one of the owner cells.
+		for cell in &self.orders
+		{
+			if !has_lock_code(&cell.output, &self.code_hash, self.hash_type)
+			{
+				return Err(Error::CodeHash);
+			}
+
+			let lock_args = LockArgs::from_args(&cell.output.lock().args().raw_data())?;
+			let order = Order::from_data(&cell.data, lock_args.version)?;
+			if !capacities.contains_key(&order.owner_lock_hash)
+			{
+				return Err(Error::MissingLock(order.owner_lock_hash));
+			}
+			add_capacity(&mut capacities, order.owner_lock_hash, cell.output.capacity().unpack())?;
+		}
+
+		// Return the capacity to each owner, with the fee paid by the first.
+		let mut outputs = Vec::new();
+		for (i, lock_script) in owners.into_iter().enumerate()
+		{
+			let lock_hash: [u8; SCRIPT_HASH_LEN] = lock_script.calc_script_hash().unpack();
+			let mut capacity = capacities[&lock_hash];
+			if i == 0
+			{
+				capacity = capacity.checked_sub(self.fee).ok_or(Error::Capacity)?;
+			}
+
+			let output = CellOutput::new_builder().capacity(capacity.pack()).lock(lock_script).build();
+			if capacity < occupied_capacity(&output, 0)?
+			{
+				return Err(Error::Capacity);
+			}
+			outputs.push(output);
+		}
+
+		let inputs = self.orders.iter().chain(self.owner_cells.iter());
+		let tx = TransactionBuilder::default()
+			.inputs(inputs.map(|cell| CellInput::new_builder().previous_output(cell.out_point.clone()).build()))
+			.outputs_data(outputs.iter().map(|_| Bytes::new()).collect::<Vec<_>>().pack())
+			.outputs(outputs)
+			.cell_deps(self.cell_deps.clone())
+			.build();
+
+		Ok(tx)
+	}
+}
+
+/// Add capacity to the total for an owner lock hash.
+fn add_capacity(capacities: &mut BTreeMap<[u8; SCRIPT_HASH_LEN], u64>, lock_hash: [u8; SCRIPT_HASH_LEN], capacity: u64) -> Result<(), Error>
+{
+	let total_capacity = capacities.entry(lock_hash).or_insert(0);
+	*total_capacity = total_capacity.checked_add(capacity).ok_or(Error::Overflow)?;
+
+	Ok(())
+}
@@ -0,0 +1,39 @@
+use ckb_types::bytes::Bytes;
+use ckb_types::core::{Capacity, ScriptHashType};
+use ckb_types::packed::{Byte, Byte32, CellOutput, OutPoint};
+use ckb_types::prelude::*;
+
+use crate::error::Error;
+
+/// A live cell, with everything needed to spend it and to read its data.
+#[derive(Clone, Debug)]
+pub struct LiveCell
+{
+	pub out_point: OutPoint,
+	pub output: CellOutput,
+	pub data: Bytes,
+}
+
+/// Sum the capacity of the cells.
+pub(crate) fn total_capacity<'a, I>(cells: I) -> Result<u64, Error>
+	where I: IntoIterator<Item = &'a CellOutput>
+{
+	cells.into_iter().try_fold(0u64, |total, output| total.checked_add(output.capacity().unpack()).ok_or(Error::Overflow))
+}
+
+/// Calculate the capacity occupied by a cell with data of the specified length.
+pub(crate) fn occupied_capacity(output: &CellOutput, data_len: usize) -> Result<u64, Error>
+{
+	let data_capacity = Capacity::bytes(data_len).map_err(|_| Error::Overflow)?;
+
+	Ok(output.occupied_capacity(data_capacity).map_err(|_| Error::Overflow)?.as_u64())
+}
+
+/// Determine if a cell is locked by the script with the code hash and hash type.
+pub(crate) fn has_lock_code(output: &CellOutput, code_hash: &Byte32, hash_type: ScriptHashType) -> bool
+{
+	let lock_script = output.lock();
+	let hash_type: Byte = hash_type.into();
+
+	lock_script.code_hash().as_slice() == code_hash.as_slice() && lock_script.hash_type().as_slice() == hash_type.as_slice()
+}
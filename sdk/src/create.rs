@@ -0,0 +1,130 @@
+use ckb_types::bytes::Bytes;
+use ckb_types::core::{ScriptHashType, TransactionBuilder, TransactionView};
+use ckb_types::packed::{Byte32, CellDep, CellInput, CellOutput, Script};
+use ckb_types::prelude::*;
+
+use crate::cell::{occupied_capacity, total_capacity, LiveCell};
+use crate::error::Error;
+use crate::order::{LockArgs, Order};
+use crate::SCRIPT_HASH_LEN;
+
+/// An order cell to create.
+struct NewOrder
+{
+	lock_args: LockArgs,
+	order: Order,
+	capacity: u64,
+}
+
+/// Builds a transaction which creates Token Buy Lock order cells from the cells of an owner.
+///
+/// Every order is validated before the transaction is built, so an order cell which the contract would reject, or which the owner could not withdraw, is never created.
+/// The remaining capacity less the transaction fee is returned to the owner lock. The witnesses needed to unlock the owner cells are not added.
+pub struct CreateBuilder
+{
+	code_hash: Byte32,
+	hash_type: ScriptHashType,
+	owner_lock: Script,
+	cell_deps: Vec<CellDep>,
+	inputs: Vec<LiveCell>,
+	orders: Vec<NewOrder>,
+	fee: u64,
+}
+
+impl CreateBuilder
+{
+	/// Create a builder with the code hash and hash type of the deployed Token Buy Lock, and the lock of the owner.
+	pub fn new(code_hash: Byte32, hash_type: ScriptHashType, owner_lock: Script) -> Self
+	{
+		CreateBuilder { code_hash, hash_type, owner_lock, cell_deps: Vec::new(), inputs: Vec::new(), orders: Vec::new(), fee: 0 }
+	}
+
+	/// Add a cell dep, such as the cell dep of the owner lock.
+	pub fn cell_dep(mut self, cell_dep: CellDep) -> Self
+	{
+		self.cell_deps.push(cell_dep);
+		self
+	}
+
+	/// Add a cell of the owner which pays for the orders.
+	pub fn input(mut self, cell: LiveCell) -> Self
+	{
+		self.inputs.push(cell);
+		self
+	}
+
+	/// Add an order cell with the specified lock script args and capacity.
+	pub fn order(mut self, lock_args: LockArgs, order: Order, capacity: u64) -> Self
+	{
+		self.orders.push(NewOrder { lock_args, order, capacity });
+		self
+	}
+
+	/// Set the transaction fee in shannons.
+	pub fn fee(mut self, fee: u64) -> Self
+	{
+		self.fee = fee;
+		self
+	}
+
+	/// Build the creation transaction.
+	pub fn build(&self) -> Result<TransactionView, Error>
+	{
+		let owner_lock_hash: [u8; SCRIPT_HASH_LEN] = self.owner_lock.calc_script_hash().unpack();
+
+		// Create the order cells, validating each one.
+		let mut outputs = Vec::new();
+		let mut outputs_data = Vec::new();
+		for new_order in &self.orders
+		{
+			// The owner must be able to withdraw the order, which needs a signature when the order pays the owner lock.
+			if new_order.order.owner_lock_hash != owner_lock_hash || (new_order.order.pays_owner_lock() && new_order.order.owner_pubkey_hash.is_none())
+			{
+				return Err(Error::OwnerLockHash);
+			}
+
+			// Encoding validates the order terms the same way the contract does.
+			let args = new_order.lock_args.to_args()?;
+			let data = new_order.order.to_data(new_order.lock_args.version)?;
+
+			let lock_script = Script::new_builder()
+				.code_hash(self.code_hash.clone())
+				.hash_type(self.hash_type.into())
+				.args(Bytes::from(args).pack())
+				.build();
+			let output = CellOutput::new_builder().capacity(new_order.capacity.pack()).lock(lock_script).build();
+			if new_order.capacity < occupied_capacity(&output, data.len())?
+			{
+				return Err(Error::Capacity);
+			}
+
+			outputs.push(output);
+			outputs_data.push(Bytes::from(data));
+		}
+
+		// Return the remaining capacity less the fee to the owner.
+		let input_capacity = total_capacity(self.inputs.iter().map(|cell| &cell.output))?;
+		let output_capacity = total_capacity(&outputs)?;
+		let capacity = input_capacity.checked_sub(output_capacity).and_then(|capacity| capacity.checked_sub(self.fee)).ok_or(Error::Capacity)?;
+		if capacity > 0
+		{
+			let output = CellOutput::new_builder().capacity(capacity.pack()).lock(self.owner_lock.clone()).build();
+			if capacity < occupied_capacity(&output, 0)?
+			{
+				return Err(Error::Capacity);
+			}
+
+			outputs.push(output);
+			outputs_data.push(Bytes::new());
+		}
+
+		let tx = TransactionBuilder::default()
+			.inputs(self.inputs.iter().map(|cell| CellInput::new_builder().previous_output(cell.out_point.clone()).build()))
+			.outputs(outputs)
+			.outputs_data(outputs_data.pack())
+			.cell_deps(self.cell_deps.clone())
+			.build();
+
+		Ok(tx)
+	}
+}
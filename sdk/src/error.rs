@@ -0,0 +1,63 @@
+use std::fmt;
+
+/// An error encoding or decoding cell data, building a transaction, or collecting cells.
+///
+/// Where a contract has an error for the same problem, the variant has the same name.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Error
+{
+	/// The data is too short for the layout.
+	DataLength,
+	/// The data has the right length, but does not hold valid values.
+	Encoding,
+	/// The lock script args are not a length which can be decoded.
+	ArgsLength,
+	/// There are not enough SUDT tokens to pay for the orders.
+	Amount,
+	/// A cell holds a different SUDT than the one being used.
+	TypeHash,
+	/// An order cell is not locked by the Token Buy Lock with the code hash and hash type of the builder.
+	CodeHash,
+	/// An amount or capacity is too large to be represented.
+	Overflow,
+	/// The payments would use more payout cells than an order allows.
+	PayoutCells,
+	/// There is not enough capacity to pay for the outputs and the transaction fee.
+	Capacity,
+	/// The order uses a feature which needs data the builder cannot provide, such as an oracle or a header.
+	UnsupportedOrder,
+	/// The lock script with the lock hash was not provided, so an output cannot be created for it.
+	MissingLock([u8; 32]),
+	/// An order would not be withdrawable by the lock which is creating it.
+	OwnerLockHash,
+	/// An owner cell has a type script or data, which would be lost when its capacity is returned.
+	OwnerCell,
+	/// A snapshot file could not be read or written.
+	Io(std::io::ErrorKind),
+}
+
+impl fmt::Display for Error
+{
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+	{
+		match self
+		{
+			Error::DataLength => write!(f, "the data is too short"),
+			Error::Encoding => write!(f, "the data is not encoded correctly"),
+			Error::ArgsLength => write!(f, "the lock script args have an invalid length"),
+			Error::Amount => write!(f, "there are not enough tokens to pay for the orders"),
+			Error::TypeHash => write!(f, "a cell holds a different token"),
+			Error::CodeHash => write!(f, "an order cell is not locked by the Token Buy Lock"),
+			Error::Overflow => write!(f, "an amount is too large"),
+			Error::PayoutCells => write!(f, "the payments need more payout cells than an order allows"),
+			Error::Capacity => write!(f, "there is not enough capacity for the outputs and the transaction fee"),
+			Error::UnsupportedOrder => write!(f, "the order cannot be filled by the builder"),
+			Error::MissingLock(lock_hash) => write!(f, "the lock script with hash 0x{} was not provided", lock_hash.iter().map(|byte| format!("{:02x}", byte)).collect::<String>()),
+			Error::OwnerLockHash => write!(f, "the owner lock hash of the order does not match the owner lock"),
+			Error::OwnerCell => write!(f, "an owner cell has a type script or data"),
+			Error::Io(kind) => write!(f, "the snapshot could not be read or written: {:?}", kind),
+		}
+	}
+}
+
+impl std::error::Error for Error {}
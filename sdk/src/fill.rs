@@ -0,0 +1,288 @@
+use std::collections::BTreeMap;
+
+use ckb_types::bytes::Bytes;
+use ckb_types::core::{ScriptHashType, TransactionBuilder, TransactionView};
+use ckb_types::packed::{Byte32, CellDep, CellInput, CellOutput, Script};
+use ckb_types::prelude::*;
+
+use crate::cell::{has_lock_code, occupied_capacity, total_capacity, LiveCell};
+use crate::error::Error;
+use crate::order::{LockArgs, Order, BASIS_POINTS_DENOMINATOR};
+use crate::sudt::{SudtAmount, OUT_POINT_LEN};
+use crate::SCRIPT_HASH_LEN;
+
+/// An SUDT output paying a payout lock hash or fee lock hash.
+struct Payout
+{
+	lock_hash: [u8; SCRIPT_HASH_LEN],
+	amount: u128,
+	binding: Option<[u8; OUT_POINT_LEN]>,
+}
+
+/// The limits on the cells used to pay a payout lock hash with a token. A maximum of zero cells is unlimited.
+#[derive(Clone, Copy, Default)]
+struct PayoutCellLimits
+{
+	min_capacity: u64,
+	max_cells: u32,
+}
+
+/// A payout lock hash and the type hash of the token paid to it.
+type PayoutKey = ([u8; SCRIPT_HASH_LEN], [u8; SCRIPT_HASH_LEN]);
+
+/// The limits on the payout cells of each payout lock hash and token type hash, keyed the same way as the contract.
+type PayoutCellLimitsMap = BTreeMap<PayoutKey, PayoutCellLimits>;
+
+/// The SUDT payments required by the orders, and the limits on their payout cells.
+type Payouts = (Vec<Payout>, PayoutCellLimitsMap);
+
+/// Builds a transaction which fills Token Buy Lock orders with the SUDT cells of a filler.
+///
+/// The filler pays each order in SUDT and receives the capacity of the order cells. The outputs are:
+/// - One SUDT cell for each payout lock hash and fee lock hash, holding the total owed to it by every order, grouped the same way as the contract.
+/// - One SUDT cell for each order with a bound payment, bound to the out point of the order cell.
+/// - The SUDT change, returned to the filler lock.
+/// - The remaining capacity less the transaction fee, returned to the filler lock.
+///
+/// Every order cell must use the code hash and hash type of the builder. Every order is filled completely. Orders which need an oracle, a header, an allowlist proof, or tokens other than the SUDT of the filler cells are not supported.
+/// The witnesses needed to unlock the filler cells are not added.
+pub struct FillBuilder
+{
+	code_hash: Byte32,
+	hash_type: ScriptHashType,
+	cell_deps: Vec<CellDep>,
+	filler_lock: Script,
+	orders: Vec<LiveCell>,
+	sudt_cells: Vec<LiveCell>,
+	lock_scripts: Vec<Script>,
+	fee: u64,
+}
+
+impl FillBuilder
+{
+	/// Create a builder with the code hash and hash type of the deployed Token Buy Lock, the cell deps of the Token Buy Lock and the SUDT type script, and the lock which receives the capacity and SUDT change.
+	pub fn new(code_hash: Byte32, hash_type: ScriptHashType, token_buy_lock_dep: CellDep, sudt_dep: CellDep, filler_lock: Script) -> Self
+	{
+		FillBuilder { code_hash, hash_type, cell_deps: vec![token_buy_lock_dep, sudt_dep], filler_lock, orders: Vec::new(), sudt_cells: Vec::new(), lock_scripts: Vec::new(), fee: 0 }
+	}
+
+	/// Add a cell dep, such as the cell dep of the filler lock.
+	pub fn cell_dep(mut self, cell_dep: CellDep) -> Self
+	{
+		self.cell_deps.push(cell_dep);
+		self
+	}
+
+	/// Add a Token Buy Lock order cell to fill.
+	pub fn order(mut self, cell: LiveCell) -> Self
+	{
+		self.orders.push(cell);
+		self
+	}
+
+	/// Add an SUDT cell of the filler to pay with.
+	pub fn sudt(mut self, cell: LiveCell) -> Self
+	{
+		self.sudt_cells.push(cell);
+		self
+	}
+
+	/// Add a lock script which is paid by an order. Orders only hold the hash of their payout lock and fee lock, so the scripts must be provided.
+	pub fn lock_script(mut self, lock_script: Script) -> Self
+	{
+		self.lock_scripts.push(lock_script);
+		self
+	}
+
+	/// Set the transaction fee in shannons.
+	pub fn fee(mut self, fee: u64) -> Self
+	{
+		self.fee = fee;
+		self
+	}
+
+	/// Build the fill transaction.
+	pub fn build(&self) -> Result<TransactionView, Error>
+	{
+		// Every SUDT cell must hold the same SUDT, which must be the one every order is buying.
+		let sudt_type_script = match self.sudt_cells.first().and_then(|cell| cell.output.type_().to_opt())
+		{
+			Some(type_script) => type_script,
+			None => return Err(Error::Amount),
+		};
+		let sudt_type_hash: [u8; SCRIPT_HASH_LEN] = sudt_type_script.calc_script_hash().unpack();
+
+		let mut input_token_amount: u128 = 0;
+		for cell in &self.sudt_cells
+		{
+			if !matches!(cell.output.type_().to_opt(), Some(type_script) if type_script.calc_script_hash().as_slice() == &sudt_type_hash[..])
+			{
+				return Err(Error::TypeHash);
+			}
+			input_token_amount = input_token_amount.checked_add(SudtAmount::from_data(&cell.data)?.amount).ok_or(Error::Overflow)?;
+		}
+
+		// Calculate the payments to each payout lock hash and fee lock hash, and to each order with a bound payment.
+		let (payouts, payout_cell_limits) = self.calculate_payouts(&sudt_type_hash)?;
+
+		// Create the payout outputs.
+		let mut outputs = Vec::new();
+		let mut outputs_data = Vec::new();
+		let mut payout_cells: BTreeMap<[u8; SCRIPT_HASH_LEN], u32> = BTreeMap::new();
+		let mut output_token_amount: u128 = 0;
+		for payout in &payouts
+		{
+			let limits = payout_cell_limits.get(&(payout.lock_hash, sudt_type_hash)).copied().unwrap_or_default();
+			let cells = payout_cells.entry(payout.lock_hash).or_insert(0);
+			*cells += 1;
+			if limits.max_cells > 0 && *cells > limits.max_cells
+			{
+				return Err(Error::PayoutCells);
+			}
+
+			let lock_script = self.find_lock_script(&payout.lock_hash)?;
+			let data = SudtAmount { amount: payout.amount, binding: payout.binding }.to_data();
+			let output = sudt_output(lock_script, &sudt_type_script, data.len(), limits.min_capacity)?;
+			outputs.push(output);
+			outputs_data.push(Bytes::from(data));
+			output_token_amount = output_token_amount.checked_add(payout.amount).ok_or(Error::Overflow)?;
+		}
+
+		// Return the SUDT change to the filler.
+		let change_amount = input_token_amount.checked_sub(output_token_amount).ok_or(Error::Amount)?;
+		if change_amount > 0
+		{
+			let data = SudtAmount::new(change_amount).to_data();
+			outputs.push(sudt_output(self.filler_lock.clone(), &sudt_type_script, data.len(), 0)?);
+			outputs_data.push(Bytes::from(data));
+		}
+
+		// Return the remaining capacity less the fee to the filler.
+		let inputs = self.orders.iter().chain(self.sudt_cells.iter());
+		let input_capacity = total_capacity(inputs.clone().map(|cell| &cell.output))?;
+		let output_capacity = total_capacity(&outputs)?;
+		let capacity = input_capacity.checked_sub(output_capacity).and_then(|capacity| capacity.checked_sub(self.fee)).ok_or(Error::Capacity)?;
+		let output = CellOutput::new_builder().capacity(capacity.pack()).lock(self.filler_lock.clone()).build();
+		if capacity < occupied_capacity(&output, 0)?
+		{
+			return Err(Error::Capacity);
+		}
+		outputs.push(output);
+		outputs_data.push(Bytes::new());
+
+		let tx = TransactionBuilder::default()
+			.inputs(inputs.map(|cell| CellInput::new_builder().previous_output(cell.out_point.clone()).build()))
+			.outputs(outputs)
+			.outputs_data(outputs_data.pack())
+			.cell_deps(self.cell_deps.clone())
+			.build();
+
+		Ok(tx)
+	}
+
+	/// Calculate the SUDT payments required by the orders, and the limits on the payout cells of each payout lock hash and token type hash.
+	///
+	/// Payments are grouped by lock hash, except for orders with a bound payment, which are paid individually. Fees are never bound to the order.
+	fn calculate_payouts(&self, sudt_type_hash: &[u8; SCRIPT_HASH_LEN]) -> Result<Payouts, Error>
+	{
+		let mut grouped_payouts: BTreeMap<[u8; SCRIPT_HASH_LEN], u128> = BTreeMap::new();
+		let mut bound_payouts = Vec::new();
+		let mut payout_cell_limits: PayoutCellLimitsMap = BTreeMap::new();
+		for cell in &self.orders
+		{
+			// A cell with another lock is not an order, even if its args and data decode as one.
+			if !has_lock_code(&cell.output, &self.code_hash, self.hash_type)
+			{
+				return Err(Error::CodeHash);
+			}
+
+			let lock_args = LockArgs::from_args(&cell.output.lock().args().raw_data())?;
+			if lock_args.sudt_type_hash != *sudt_type_hash
+			{
+				return Err(Error::TypeHash);
+			}
+
+			let order = Order::from_data(&cell.data, lock_args.version)?;
+			let amount = fill_amount(&order, cell.output.capacity().unpack())?;
+			let fee_amount = order.fee.as_ref().map_or(Ok(0), |fee| amount.checked_mul(fee.rate as u128).map(|fee_amount| fee_amount / BASIS_POINTS_DENOMINATOR as u128).ok_or(Error::Overflow))?;
+			let buy_amount = amount - fee_amount;
+			let payout_lock_hash = order.payout_lock_hash();
+
+			if order.bound_payment
+			{
+				let mut binding = [0u8; OUT_POINT_LEN];
+				binding.copy_from_slice(cell.out_point.as_slice());
+				bound_payouts.push(Payout { lock_hash: payout_lock_hash, amount: buy_amount, binding: Some(binding) });
+			}
+			else
+			{
+				add_payout(&mut grouped_payouts, payout_lock_hash, buy_amount)?;
+			}
+
+			if let Some(fee) = &order.fee
+			{
+				if fee_amount > 0
+				{
+					add_payout(&mut grouped_payouts, fee.lock_hash, fee_amount)?;
+				}
+			}
+
+			// Keep the strictest limits of every order paying the same lock hash with the same token, the same as the contract.
+			if order.min_payout_capacity.is_some() || order.max_payout_cells.is_some()
+			{
+				let limits = payout_cell_limits.entry((payout_lock_hash, *sudt_type_hash)).or_default();
+				limits.min_capacity = limits.min_capacity.max(order.min_payout_capacity.unwrap_or(0));
+				if let Some(max_cells) = order.max_payout_cells
+				{
+					if max_cells > 0 && (limits.max_cells == 0 || max_cells < limits.max_cells)
+					{
+						limits.max_cells = max_cells;
+					}
+				}
+			}
+		}
+
+		let mut payouts: Vec<Payout> = grouped_payouts.into_iter().map(|(lock_hash, amount)| Payout { lock_hash, amount, binding: None }).collect();
+		payouts.extend(bound_payouts);
+
+		Ok((payouts, payout_cell_limits))
+	}
+
+	/// Find a provided lock script by its hash.
+	fn find_lock_script(&self, lock_hash: &[u8; SCRIPT_HASH_LEN]) -> Result<Script, Error>
+	{
+		self.lock_scripts.iter().chain(Some(&self.filler_lock))
+			.find(|lock_script| lock_script.calc_script_hash().as_slice() == &lock_hash[..])
+			.cloned()
+			.ok_or(Error::MissingLock(*lock_hash))
+	}
+}
+
+/// Calculate the SUDT amount required to fill an entire order cell with the specified capacity.
+fn fill_amount(order: &Order, capacity: u64) -> Result<u128, Error>
+{
+	if order.auction.is_some() || order.oracle.is_some() || order.allowlist_root.is_some() || !order.bundle.is_empty() || !order.alternatives.is_empty()
+	{
+		return Err(Error::UnsupportedOrder);
+	}
+
+	order.fill_amount(capacity)
+}
+
+/// Add an amount to the payout for a lock hash.
+fn add_payout(payouts: &mut BTreeMap<[u8; SCRIPT_HASH_LEN], u128>, lock_hash: [u8; SCRIPT_HASH_LEN], amount: u128) -> Result<(), Error>
+{
+	let total_amount = payouts.entry(lock_hash).or_insert(0);
+	*total_amount = total_amount.checked_add(amount).ok_or(Error::Overflow)?;
+
+	Ok(())
+}
+
+/// Create an SUDT output with the occupied capacity of its data, or the minimum capacity if it is larger.
+fn sudt_output(lock_script: Script, sudt_type_script: &Script, data_len: usize, min_capacity: u64) -> Result<CellOutput, Error>
+{
+	let output = CellOutput::new_builder().lock(lock_script).type_(Some(sudt_type_script.clone()).pack()).build();
+	let capacity = occupied_capacity(&output, data_len)?.max(min_capacity);
+
+	Ok(output.as_builder().capacity(capacity.pack()).build())
+}
@@ -0,0 +1,31 @@
+//! Host side encoding and decoding of Token Buy Lock cells.
+//!
+//! The types in this crate encode to the exact cell data layouts which are decoded by the contracts,
+//! so an application never needs to build order data byte by byte. The builders assemble complete transactions from live cells,
+//! which are found with a cell provider.
+
+mod book;
+mod cancel;
+mod cell;
+mod create;
+mod error;
+mod fill;
+mod order;
+mod provider;
+mod sudt;
+
+pub use book::{Bid, Depth, InvalidCell, OrderBook, Price};
+pub use cancel::CancelBuilder;
+pub use cell::LiveCell;
+pub use create::CreateBuilder;
+pub use error::Error;
+pub use fill::FillBuilder;
+pub use order::{Auction, AuctionMetric, Fee, LockArgs, Oracle, Order, TokenAmount, BASIS_POINTS_DENOMINATOR, LEGACY_FLAGS_MARKER, LEGACY_VERSION};
+pub use provider::{CellProvider, CellQuery, FileCellProvider, MemoryCellProvider};
+pub use sudt::{SudtAmount, BINDING_MARKER, OUT_POINT_LEN};
+pub use token_buy_lock_types::{ORDER_VERSION, ORDER_VERSION_V1, ORDER_VERSION_V2};
+
+// Constants
+pub const SCRIPT_HASH_LEN: usize = 32; // Number of bytes for a lock hash. (Blake2b 256-bit 32 bytes)
+pub const PUBKEY_HASH_LEN: usize = 20; // A blake160 hash is the first 20 bytes of the Blake2b hash of a compressed public key.
+pub const SUDT_DATA_LEN: usize = 16; // SUDT uses a u128, which is 16 bytes.
@@ -0,0 +1,784 @@
+use std::convert::TryFrom;
+
+use token_buy_lock_types::molecule::prelude::*;
+use token_buy_lock_types::token_buy_lock as order_types;
+use token_buy_lock_types::{encode_with_version, ORDER_VERSION, ORDER_VERSION_V1, ORDER_VERSION_V2};
+
+use crate::error::Error;
+use crate::{PUBKEY_HASH_LEN, SCRIPT_HASH_LEN, SUDT_DATA_LEN};
+
+// Constants
+const ORDER_DATA_LEN: usize = SCRIPT_HASH_LEN + SUDT_DATA_LEN; // Owner lock hash followed by the buy amount.
+pub const LEGACY_FLAGS_MARKER: &[u8] = b"TBLF"; // Legacy data must opt in to order flags with this marker, since the original script ignored any data after the buy amount.
+const FLAGS_LEN: usize = 2; // Order flags are stored as a u16.
+const BASIS_POINTS_LEN: usize = 2; // Fee rates and spreads are stored in basis points as a u16.
+pub const BASIS_POINTS_DENOMINATOR: u16 = 10_000; // The number of basis points in a whole.
+const COUNT_LEN: usize = 1; // Counts and the auction metric are stored as a u8.
+const AUCTION_POINT_LEN: usize = 8; // Block numbers and timestamps are stored as a u64.
+pub const LEGACY_VERSION: u8 = 0; // Args and cell data which predate the version byte.
+
+// Order Flags
+const FLAG_PARTIAL_FILL: u16 = 1 << 0;
+const FLAG_PRICE: u16 = 1 << 1;
+const FLAG_BOUND_PAYMENT: u16 = 1 << 2;
+const FLAG_PAYOUT_LOCK: u16 = 1 << 3;
+const FLAG_FEE: u16 = 1 << 4;
+const FLAG_AUCTION: u16 = 1 << 5;
+const FLAG_ALLOWLIST: u16 = 1 << 6;
+const FLAG_ORACLE: u16 = 1 << 7;
+const FLAG_BUNDLE: u16 = 1 << 8;
+const FLAG_ALTERNATIVES: u16 = 1 << 9;
+const FLAGS_SUPPORTED: u16 = FLAG_PARTIAL_FILL | FLAG_PRICE | FLAG_BOUND_PAYMENT | FLAG_PAYOUT_LOCK | FLAG_FEE | FLAG_AUCTION | FLAG_ALLOWLIST | FLAG_ORACLE | FLAG_BUNDLE | FLAG_ALTERNATIVES;
+
+/// The args of a Token Buy Lock script.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LockArgs
+{
+	pub version: u8,
+	pub sudt_type_hash: [u8; SCRIPT_HASH_LEN],
+	pub recovery_lock_hash: Option<[u8; SCRIPT_HASH_LEN]>,
+}
+
+impl LockArgs
+{
+	/// Decode the args of a Token Buy Lock script.
+	///
+	/// Legacy args are the SUDT type hash, optionally followed by the recovery lock hash.
+	/// Versioned args are the version byte followed by the Molecule encoded `OrderArgs`, which is the same for every version.
+	pub fn from_args(args: &[u8]) -> Result<Self, Error>
+	{
+		if args.len() == SCRIPT_HASH_LEN || args.len() == SCRIPT_HASH_LEN * 2
+		{
+			let sudt_type_hash = to_hash(&args[0..SCRIPT_HASH_LEN]);
+			let recovery_lock_hash = if args.len() == SCRIPT_HASH_LEN * 2 { Some(to_hash(&args[SCRIPT_HASH_LEN..SCRIPT_HASH_LEN*2])) } else { None };
+
+			return Ok(LockArgs { version: LEGACY_VERSION, sudt_type_hash, recovery_lock_hash });
+		}
+
+		match args.split_first()
+		{
+			Some((&version, args)) if is_versioned(version) =>
+			{
+				let order_args = order_types::OrderArgsReader::from_slice(args).map_err(|_| Error::Encoding)?;
+				let sudt_type_hash = to_hash(order_args.sudt_type_hash().raw_data());
+				let recovery_lock_hash = order_args.recovery_lock_hash().to_opt().map(|lock_hash| to_hash(lock_hash.raw_data()));
+
+				Ok(LockArgs { version, sudt_type_hash, recovery_lock_hash })
+			},
+			_ => Err(Error::ArgsLength),
+		}
+	}
+
+	/// Encode the args of a Token Buy Lock script.
+	pub fn to_args(&self) -> Result<Vec<u8>, Error>
+	{
+		match self.version
+		{
+			LEGACY_VERSION =>
+			{
+				let mut args = self.sudt_type_hash.to_vec();
+				if let Some(recovery_lock_hash) = &self.recovery_lock_hash
+				{
+					args.extend_from_slice(recovery_lock_hash);
+				}
+
+				Ok(args)
+			},
+			version if is_versioned(version) =>
+			{
+				let order_args = order_types::OrderArgs::new_builder()
+					.sudt_type_hash(self.sudt_type_hash.into())
+					.recovery_lock_hash(order_types::Byte32Opt::new_builder().set(self.recovery_lock_hash.map(Into::into)).build())
+					.build();
+
+				Ok(encode_with_version(version, &order_args))
+			},
+			_ => Err(Error::Encoding),
+		}
+	}
+}
+
+/// The point in time an auction is measured by.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AuctionMetric
+{
+	/// The auction start and end are block numbers.
+	BlockNumber = 0,
+	/// The auction start and end are block timestamps in milliseconds.
+	Timestamp = 1,
+}
+
+impl TryFrom<u8> for AuctionMetric
+{
+	type Error = Error;
+
+	fn try_from(value: u8) -> Result<Self, Error>
+	{
+		match value
+		{
+			0 => Ok(AuctionMetric::BlockNumber),
+			1 => Ok(AuctionMetric::Timestamp),
+			_ => Err(Error::Encoding),
+		}
+	}
+}
+
+/// A share of the payment in basis points which is sent to the fee lock hash.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Fee
+{
+	pub lock_hash: [u8; SCRIPT_HASH_LEN],
+	pub rate: u16,
+}
+
+/// A buy amount which decays to the end amount between the start and end.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Auction
+{
+	pub end_amount: u128,
+	pub metric: AuctionMetric,
+	pub start: u64,
+	pub end: u64,
+}
+
+/// A price read from the oracle cell with the type hash, plus a spread in basis points.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Oracle
+{
+	pub type_hash: [u8; SCRIPT_HASH_LEN],
+	pub spread: u16,
+}
+
+/// An amount of the token with the type hash.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TokenAmount
+{
+	pub type_hash: [u8; SCRIPT_HASH_LEN],
+	pub amount: u128,
+}
+
+/// A Token Buy Lock order.
+///
+/// Each optional field corresponds to an order flag in the legacy layout, and to a field of `OrderData` in the versioned layout.
+/// The flags are derived from the fields which are set, so an order can never disagree with its own flags.
+/// An empty bundle or empty alternatives are not set.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Order
+{
+	pub owner_lock_hash: [u8; SCRIPT_HASH_LEN],
+	pub buy_amount: u128,
+	pub partial_fill: bool,
+	pub bound_payment: bool,
+	pub price_denominator: Option<u128>,
+	pub payout_lock_hash: Option<[u8; SCRIPT_HASH_LEN]>,
+	pub fee: Option<Fee>,
+	pub auction: Option<Auction>,
+	pub allowlist_root: Option<[u8; SCRIPT_HASH_LEN]>,
+	pub oracle: Option<Oracle>,
+	pub bundle: Vec<TokenAmount>,
+	pub alternatives: Vec<TokenAmount>,
+	/// Only available in the versioned layout from version 2.
+	pub min_payout_capacity: Option<u64>,
+	/// Only available in the versioned layout from version 2.
+	pub max_payout_cells: Option<u32>,
+	/// Only available in the versioned layout from version 3.
+	/// The owner can withdraw the order with a 65 byte recoverable secp256k1 signature of the raw transaction hash, which is not the sighash-all message of the default lock.
+	pub owner_pubkey_hash: Option<[u8; PUBKEY_HASH_LEN]>,
+}
+
+impl Order
+{
+	/// Create a fixed amount order with no optional fields.
+	pub fn new(owner_lock_hash: [u8; SCRIPT_HASH_LEN], buy_amount: u128) -> Self
+	{
+		Order
+		{
+			owner_lock_hash,
+			buy_amount,
+			partial_fill: false,
+			bound_payment: false,
+			price_denominator: None,
+			payout_lock_hash: None,
+			fee: None,
+			auction: None,
+			allowlist_root: None,
+			oracle: None,
+			bundle: Vec::new(),
+			alternatives: Vec::new(),
+			min_payout_capacity: None,
+			max_payout_cells: None,
+			owner_pubkey_hash: None,
+		}
+	}
+
+	/// Decode an order from the data of a Token Buy Lock cell, using the layout of the version in the lock script args.
+	pub fn from_data(data: &[u8], version: u8) -> Result<Self, Error>
+	{
+		if version == LEGACY_VERSION
+		{
+			return Self::from_legacy_data(data);
+		}
+		if !is_versioned(version)
+		{
+			return Err(Error::Encoding);
+		}
+
+		// Versioned data must start with the same version byte as the args.
+		match data.split_first()
+		{
+			Some((data_version, data)) if *data_version == version => Self::from_molecule_data(data, version),
+			Some(_) => Err(Error::Encoding),
+			None => Err(Error::DataLength),
+		}
+	}
+
+	/// Encode the order to the data of a Token Buy Lock cell, using the layout of the version in the lock script args.
+	///
+	/// Orders which would be rejected by the contract cannot be encoded.
+	/// Earlier versions can be encoded for the remainder of an order created with them, but only with the fields they support.
+	pub fn to_data(&self, version: u8) -> Result<Vec<u8>, Error>
+	{
+		self.validate()?;
+
+		match version
+		{
+			LEGACY_VERSION => self.to_legacy_data(),
+			ORDER_VERSION_V1 => Ok(encode_with_version(version, &self.to_molecule_data_v1()?)),
+			ORDER_VERSION_V2 => Ok(encode_with_version(version, &self.to_molecule_data_v2()?)),
+			ORDER_VERSION => Ok(encode_with_version(version, &self.to_molecule_data())),
+			_ => Err(Error::Encoding),
+		}
+	}
+
+	/// The flags of the order in the legacy layout.
+	pub fn flags(&self) -> u16
+	{
+		let mut flags = 0;
+		if self.partial_fill { flags |= FLAG_PARTIAL_FILL; }
+		if self.price_denominator.is_some() { flags |= FLAG_PRICE; }
+		if self.bound_payment { flags |= FLAG_BOUND_PAYMENT; }
+		if self.payout_lock_hash.is_some() { flags |= FLAG_PAYOUT_LOCK; }
+		if self.fee.is_some() { flags |= FLAG_FEE; }
+		if self.auction.is_some() { flags |= FLAG_AUCTION; }
+		if self.allowlist_root.is_some() { flags |= FLAG_ALLOWLIST; }
+		if self.oracle.is_some() { flags |= FLAG_ORACLE; }
+		if !self.bundle.is_empty() { flags |= FLAG_BUNDLE; }
+		if !self.alternatives.is_empty() { flags |= FLAG_ALTERNATIVES; }
+
+		flags
+	}
+
+	/// The lock hash which receives the payment.
+	pub fn payout_lock_hash(&self) -> [u8; SCRIPT_HASH_LEN]
+	{
+		self.payout_lock_hash.unwrap_or(self.owner_lock_hash)
+	}
+
+	/// Determine if the payout lock hash or fee lock hash is explicitly set to the owner lock hash.
+	/// A cell of the owner never proves ownership of such an order, so it can only be withdrawn with a signature.
+	pub fn pays_owner_lock(&self) -> bool
+	{
+		self.payout_lock_hash == Some(self.owner_lock_hash) || matches!(&self.fee, Some(fee) if fee.lock_hash == self.owner_lock_hash)
+	}
+
+	/// Calculate the SUDT amount required to fill an entire order cell with the specified capacity.
+	/// Priced orders charge for the capacity consumed, rounding up the same as the contract.
+	pub fn fill_amount(&self, capacity: u64) -> Result<u128, Error>
+	{
+		match self.price_denominator
+		{
+			Some(denominator) =>
+			{
+				let amount = (capacity as u128).checked_mul(self.buy_amount).and_then(|amount| amount.checked_add(denominator - 1)).ok_or(Error::Overflow)?;
+				Ok(amount / denominator)
+			},
+			None => Ok(self.buy_amount),
+		}
+	}
+
+	/// Decode an order from the original layout of a Token Buy Lock cell, which predates the version byte.
+	fn from_legacy_data(data: &[u8]) -> Result<Self, Error>
+	{
+		if data.len() < ORDER_DATA_LEN
+		{
+			return Err(Error::DataLength);
+		}
+
+		let mut offset = 0;
+		let mut order = Order::new(read_hash(data, &mut offset)?, read_u128(data, &mut offset)?);
+
+		// Orders without the flags marker use the original fixed amount behavior, and any bytes after the buy amount are ignored.
+		let flags = if data[ORDER_DATA_LEN..].starts_with(LEGACY_FLAGS_MARKER)
+		{
+			offset += LEGACY_FLAGS_MARKER.len();
+			to_u16(read_bytes(data, &mut offset, FLAGS_LEN)?)
+		}
+		else
+		{
+			0
+		};
+		if flags & !FLAGS_SUPPORTED != 0
+		{
+			return Err(Error::Encoding);
+		}
+
+		order.partial_fill = flags & FLAG_PARTIAL_FILL != 0;
+		order.bound_payment = flags & FLAG_BOUND_PAYMENT != 0;
+		if flags & FLAG_PRICE != 0
+		{
+			order.price_denominator = Some(read_u128(data, &mut offset)?);
+		}
+		if flags & FLAG_PAYOUT_LOCK != 0
+		{
+			order.payout_lock_hash = Some(read_hash(data, &mut offset)?);
+		}
+		if flags & FLAG_FEE != 0
+		{
+			order.fee = Some(Fee { lock_hash: read_hash(data, &mut offset)?, rate: read_u16(data, &mut offset)? });
+		}
+		if flags & FLAG_AUCTION != 0
+		{
+			let end_amount = read_u128(data, &mut offset)?;
+			let metric = AuctionMetric::try_from(read_u8(data, &mut offset)?)?;
+			order.auction = Some(Auction { end_amount, metric, start: read_u64(data, &mut offset)?, end: read_u64(data, &mut offset)? });
+		}
+		if flags & FLAG_ALLOWLIST != 0
+		{
+			order.allowlist_root = Some(read_hash(data, &mut offset)?);
+		}
+		if flags & FLAG_ORACLE != 0
+		{
+			order.oracle = Some(Oracle { type_hash: read_hash(data, &mut offset)?, spread: read_u16(data, &mut offset)? });
+		}
+		if flags & FLAG_BUNDLE != 0
+		{
+			order.bundle = read_token_amounts(data, &mut offset)?;
+		}
+		if flags & FLAG_ALTERNATIVES != 0
+		{
+			order.alternatives = read_token_amounts(data, &mut offset)?;
+		}
+
+		order.validate()?;
+
+		Ok(order)
+	}
+
+	/// Encode the order to the original layout of a Token Buy Lock cell. Fields which are only available in the versioned layout cannot be encoded.
+	fn to_legacy_data(&self) -> Result<Vec<u8>, Error>
+	{
+		if self.min_payout_capacity.is_some() || self.max_payout_cells.is_some() || self.owner_pubkey_hash.is_some()
+		{
+			return Err(Error::Encoding);
+		}
+
+		let mut data = self.owner_lock_hash.to_vec();
+		data.extend_from_slice(&self.buy_amount.to_le_bytes());
+
+		// The flags are omitted when there are none, matching the original layout.
+		let flags = self.flags();
+		if flags == 0
+		{
+			return Ok(data);
+		}
+
+		data.extend_from_slice(LEGACY_FLAGS_MARKER);
+		data.extend_from_slice(&flags.to_le_bytes());
+		if let Some(price_denominator) = self.price_denominator
+		{
+			data.extend_from_slice(&price_denominator.to_le_bytes());
+		}
+		if let Some(payout_lock_hash) = &self.payout_lock_hash
+		{
+			data.extend_from_slice(payout_lock_hash);
+		}
+		if let Some(fee) = &self.fee
+		{
+			data.extend_from_slice(&fee.lock_hash);
+			data.extend_from_slice(&fee.rate.to_le_bytes());
+		}
+		if let Some(auction) = &self.auction
+		{
+			data.extend_from_slice(&auction.end_amount.to_le_bytes());
+			data.push(auction.metric as u8);
+			data.extend_from_slice(&auction.start.to_le_bytes());
+			data.extend_from_slice(&auction.end.to_le_bytes());
+		}
+		if let Some(allowlist_root) = &self.allowlist_root
+		{
+			data.extend_from_slice(allowlist_root);
+		}
+		if let Some(oracle) = &self.oracle
+		{
+			data.extend_from_slice(&oracle.type_hash);
+			data.extend_from_slice(&oracle.spread.to_le_bytes());
+		}
+		if !self.bundle.is_empty()
+		{
+			write_token_amounts(&mut data, &self.bundle)?;
+		}
+		if !self.alternatives.is_empty()
+		{
+			write_token_amounts(&mut data, &self.alternatives)?;
+		}
+
+		Ok(data)
+	}
+
+	/// Decode an order from the Molecule encoded data of a versioned Token Buy Lock cell, without the version byte.
+	fn from_molecule_data(data: &[u8], version: u8) -> Result<Self, Error>
+	{
+		// Verify the data against the table of its own version.
+		let mut owner_pubkey_hash = None;
+		match version
+		{
+			ORDER_VERSION_V1 =>
+			{
+				order_types::OrderDataV1Reader::from_slice(data).map_err(|_| Error::Encoding)?;
+			},
+			ORDER_VERSION_V2 =>
+			{
+				order_types::OrderDataV2Reader::from_slice(data).map_err(|_| Error::Encoding)?;
+			},
+			_ =>
+			{
+				let order_data = order_types::OrderDataReader::from_slice(data).map_err(|_| Error::Encoding)?;
+				owner_pubkey_hash = order_data.owner_pubkey_hash().to_opt().map(|pubkey_hash| to_pubkey_hash(pubkey_hash.raw_data()));
+			},
+		}
+
+		// Every version appends fields to the table of the previous one, so the fields of an earlier version are read the same way for all of the later ones.
+		let mut min_payout_capacity = None;
+		let mut max_payout_cells = None;
+		if version != ORDER_VERSION_V1
+		{
+			let order_data = order_types::OrderDataV2Reader::from_compatible_slice(data).map_err(|_| Error::Encoding)?;
+			min_payout_capacity = order_data.min_payout_capacity().to_opt().map(|capacity| to_u64(capacity.raw_data()));
+			max_payout_cells = order_data.max_payout_cells().to_opt().map(|cells| to_u32(cells.raw_data()));
+		}
+
+		let order_data = order_types::OrderDataV1Reader::from_compatible_slice(data).map_err(|_| Error::Encoding)?;
+
+		let mut order = Order::new(to_hash(order_data.owner_lock_hash().raw_data()), to_u128(order_data.buy_amount().raw_data()));
+		order.partial_fill = to_bool(order_data.partial_fill().as_slice()[0])?;
+		order.bound_payment = to_bool(order_data.bound_payment().as_slice()[0])?;
+		order.price_denominator = order_data.price_denominator().to_opt().map(|denominator| to_u128(denominator.raw_data()));
+		order.payout_lock_hash = order_data.payout_lock_hash().to_opt().map(|lock_hash| to_hash(lock_hash.raw_data()));
+		order.fee = order_data.fee().to_opt().map(|fee| Fee { lock_hash: to_hash(fee.lock_hash().raw_data()), rate: to_u16(fee.rate().raw_data()) });
+		order.auction = match order_data.auction().to_opt()
+		{
+			Some(auction) =>
+			{
+				let metric = AuctionMetric::try_from(auction.metric().as_slice()[0])?;
+				Some(Auction { end_amount: to_u128(auction.end_amount().raw_data()), metric, start: to_u64(auction.start().raw_data()), end: to_u64(auction.end().raw_data()) })
+			},
+			None => None,
+		};
+		order.allowlist_root = order_data.allowlist_root().to_opt().map(|root| to_hash(root.raw_data()));
+		order.oracle = order_data.oracle().to_opt().map(|oracle| Oracle { type_hash: to_hash(oracle.type_hash().raw_data()), spread: to_u16(oracle.spread().raw_data()) });
+		order.bundle = to_token_amounts(order_data.bundle());
+		order.alternatives = to_token_amounts(order_data.alternatives());
+		order.min_payout_capacity = min_payout_capacity;
+		order.max_payout_cells = max_payout_cells;
+		order.owner_pubkey_hash = owner_pubkey_hash;
+
+		order.validate()?;
+
+		Ok(order)
+	}
+
+	/// Encode the order to the Molecule `OrderData`, without the version byte.
+	fn to_molecule_data(&self) -> order_types::OrderData
+	{
+		let fee = self.fee.as_ref().map(|fee| order_types::Fee::new_builder().lock_hash(fee.lock_hash.into()).rate(fee.rate.into()).build());
+		let auction = self.auction.as_ref().map(|auction| order_types::Auction::new_builder()
+			.end_amount(auction.end_amount.into())
+			.metric((auction.metric as u8).into())
+			.start(auction.start.into())
+			.end(auction.end.into())
+			.build());
+		let oracle = self.oracle.as_ref().map(|oracle| order_types::Oracle::new_builder().type_hash(oracle.type_hash.into()).spread(oracle.spread.into()).build());
+
+		order_types::OrderData::new_builder()
+			.owner_lock_hash(self.owner_lock_hash.into())
+			.buy_amount(self.buy_amount.into())
+			.partial_fill((self.partial_fill as u8).into())
+			.bound_payment((self.bound_payment as u8).into())
+			.price_denominator(order_types::Uint128Opt::new_builder().set(self.price_denominator.map(Into::into)).build())
+			.payout_lock_hash(order_types::Byte32Opt::new_builder().set(self.payout_lock_hash.map(Into::into)).build())
+			.fee(order_types::FeeOpt::new_builder().set(fee).build())
+			.auction(order_types::AuctionOpt::new_builder().set(auction).build())
+			.allowlist_root(order_types::Byte32Opt::new_builder().set(self.allowlist_root.map(Into::into)).build())
+			.oracle(order_types::OracleOpt::new_builder().set(oracle).build())
+			.bundle(to_token_amount_vec(&self.bundle))
+			.alternatives(to_token_amount_vec(&self.alternatives))
+			.min_payout_capacity(order_types::Uint64Opt::new_builder().set(self.min_payout_capacity.map(Into::into)).build())
+			.max_payout_cells(order_types::Uint32Opt::new_builder().set(self.max_payout_cells.map(Into::into)).build())
+			.owner_pubkey_hash(order_types::Byte20Opt::new_builder().set(self.owner_pubkey_hash.map(Into::into)).build())
+			.build()
+	}
+
+	/// Encode the order to the Molecule `OrderDataV1`, without the version byte. Fields which were added after version 1 cannot be encoded.
+	fn to_molecule_data_v1(&self) -> Result<order_types::OrderDataV1, Error>
+	{
+		if self.min_payout_capacity.is_some() || self.max_payout_cells.is_some() || self.owner_pubkey_hash.is_some()
+		{
+			return Err(Error::Encoding);
+		}
+
+		let order_data = self.to_molecule_data();
+		let order_data_v1 = order_types::OrderDataV1::new_builder()
+			.owner_lock_hash(order_data.owner_lock_hash())
+			.buy_amount(order_data.buy_amount())
+			.partial_fill(order_data.partial_fill())
+			.bound_payment(order_data.bound_payment())
+			.price_denominator(order_data.price_denominator())
+			.payout_lock_hash(order_data.payout_lock_hash())
+			.fee(order_data.fee())
+			.auction(order_data.auction())
+			.allowlist_root(order_data.allowlist_root())
+			.oracle(order_data.oracle())
+			.bundle(order_data.bundle())
+			.alternatives(order_data.alternatives())
+			.build();
+
+		Ok(order_data_v1)
+	}
+
+	/// Encode the order to the Molecule `OrderDataV2`, without the version byte. Fields which were added after version 2 cannot be encoded.
+	fn to_molecule_data_v2(&self) -> Result<order_types::OrderDataV2, Error>
+	{
+		if self.owner_pubkey_hash.is_some()
+		{
+			return Err(Error::Encoding);
+		}
+
+		let order_data = self.to_molecule_data();
+		let order_data_v2 = order_types::OrderDataV2::new_builder()
+			.owner_lock_hash(order_data.owner_lock_hash())
+			.buy_amount(order_data.buy_amount())
+			.partial_fill(order_data.partial_fill())
+			.bound_payment(order_data.bound_payment())
+			.price_denominator(order_data.price_denominator())
+			.payout_lock_hash(order_data.payout_lock_hash())
+			.fee(order_data.fee())
+			.auction(order_data.auction())
+			.allowlist_root(order_data.allowlist_root())
+			.oracle(order_data.oracle())
+			.bundle(order_data.bundle())
+			.alternatives(order_data.alternatives())
+			.min_payout_capacity(order_data.min_payout_capacity())
+			.max_payout_cells(order_data.max_payout_cells())
+			.build();
+
+		Ok(order_data_v2)
+	}
+
+	/// Validate the order terms with the same rules as the contract.
+	fn validate(&self) -> Result<(), Error>
+	{
+		if self.price_denominator == Some(0)
+		{
+			return Err(Error::Encoding);
+		}
+
+		if matches!(&self.fee, Some(fee) if fee.rate > BASIS_POINTS_DENOMINATOR)
+		{
+			return Err(Error::Encoding);
+		}
+
+		if let Some(auction) = &self.auction
+		{
+			if auction.end <= auction.start || auction.end_amount > self.buy_amount || self.price_denominator.is_some() || self.partial_fill
+			{
+				return Err(Error::Encoding);
+			}
+		}
+
+		if self.oracle.is_some() && (self.price_denominator.is_some() || self.auction.is_some())
+		{
+			return Err(Error::Encoding);
+		}
+
+		if !self.bundle.is_empty() && self.partial_fill
+		{
+			return Err(Error::Encoding);
+		}
+
+		if !self.alternatives.is_empty() && (self.partial_fill || !self.bundle.is_empty() || self.bound_payment)
+		{
+			return Err(Error::Encoding);
+		}
+
+		if self.max_payout_cells == Some(0)
+		{
+			return Err(Error::Encoding);
+		}
+
+		Ok(())
+	}
+}
+
+/// Determine if a version byte is a versioned layout this SDK understands.
+fn is_versioned(version: u8) -> bool
+{
+	version == ORDER_VERSION_V1 || version == ORDER_VERSION_V2 || version == ORDER_VERSION
+}
+
+/// Read a fixed number of bytes from the data at the specified offset and advance the offset.
+fn read_bytes<'a>(data: &'a [u8], offset: &mut usize, len: usize) -> Result<&'a [u8], Error>
+{
+	if data.len() < *offset + len
+	{
+		return Err(Error::DataLength);
+	}
+
+	let bytes = &data[*offset..*offset+len];
+	*offset += len;
+
+	Ok(bytes)
+}
+
+/// Read a script hash from the data at the specified offset and advance the offset.
+fn read_hash(data: &[u8], offset: &mut usize) -> Result<[u8; SCRIPT_HASH_LEN], Error>
+{
+	Ok(to_hash(read_bytes(data, offset, SCRIPT_HASH_LEN)?))
+}
+
+/// Read a u128 from the data at the specified offset and advance the offset.
+fn read_u128(data: &[u8], offset: &mut usize) -> Result<u128, Error>
+{
+	Ok(to_u128(read_bytes(data, offset, SUDT_DATA_LEN)?))
+}
+
+/// Read a u16 from the data at the specified offset and advance the offset.
+fn read_u16(data: &[u8], offset: &mut usize) -> Result<u16, Error>
+{
+	Ok(to_u16(read_bytes(data, offset, BASIS_POINTS_LEN)?))
+}
+
+/// Read a u8 from the data at the specified offset and advance the offset.
+fn read_u8(data: &[u8], offset: &mut usize) -> Result<u8, Error>
+{
+	Ok(read_bytes(data, offset, COUNT_LEN)?[0])
+}
+
+/// Read a u64 from the data at the specified offset and advance the offset.
+fn read_u64(data: &[u8], offset: &mut usize) -> Result<u64, Error>
+{
+	Ok(to_u64(read_bytes(data, offset, AUCTION_POINT_LEN)?))
+}
+
+/// Read a u8 count from the data at the specified offset, followed by that many type hash and u128 amount pairs, and advance the offset.
+fn read_token_amounts(data: &[u8], offset: &mut usize) -> Result<Vec<TokenAmount>, Error>
+{
+	let count = read_u8(data, offset)?;
+
+	// An empty list would decode the same as a missing flag.
+	if count == 0
+	{
+		return Err(Error::Encoding);
+	}
+
+	let mut token_amounts = Vec::new();
+	for _ in 0..count
+	{
+		token_amounts.push(TokenAmount { type_hash: read_hash(data, offset)?, amount: read_u128(data, offset)? });
+	}
+
+	Ok(token_amounts)
+}
+
+/// Write a u8 count followed by each type hash and u128 amount pair. A legacy layout cannot hold more than 255 tokens.
+fn write_token_amounts(data: &mut Vec<u8>, token_amounts: &[TokenAmount]) -> Result<(), Error>
+{
+	let count = u8::try_from(token_amounts.len()).map_err(|_| Error::Encoding)?;
+
+	data.push(count);
+	for token_amount in token_amounts
+	{
+		data.extend_from_slice(&token_amount.type_hash);
+		data.extend_from_slice(&token_amount.amount.to_le_bytes());
+	}
+
+	Ok(())
+}
+
+/// Convert a Molecule byte to a bool. Only zero and one are valid.
+fn to_bool(value: u8) -> Result<bool, Error>
+{
+	match value
+	{
+		0 => Ok(false),
+		1 => Ok(true),
+		_ => Err(Error::Encoding),
+	}
+}
+
+/// Convert a slice with the length of a script hash to an array. The length is verified by Molecule or the caller.
+fn to_hash(data: &[u8]) -> [u8; SCRIPT_HASH_LEN]
+{
+	let mut hash = [0u8; SCRIPT_HASH_LEN];
+	hash.copy_from_slice(data);
+
+	hash
+}
+
+/// Convert a Molecule Byte20 to a public key hash.
+fn to_pubkey_hash(data: &[u8]) -> [u8; PUBKEY_HASH_LEN]
+{
+	let mut pubkey_hash = [0u8; PUBKEY_HASH_LEN];
+	pubkey_hash.copy_from_slice(data);
+
+	pubkey_hash
+}
+
+/// Convert a little endian slice of two bytes to a u16.
+fn to_u16(data: &[u8]) -> u16
+{
+	let mut buffer = [0u8; 2];
+	buffer.copy_from_slice(data);
+
+	u16::from_le_bytes(buffer)
+}
+
+/// Convert a little endian slice of four bytes to a u32.
+fn to_u32(data: &[u8]) -> u32
+{
+	let mut buffer = [0u8; 4];
+	buffer.copy_from_slice(data);
+
+	u32::from_le_bytes(buffer)
+}
+
+/// Convert a little endian slice of eight bytes to a u64.
+fn to_u64(data: &[u8]) -> u64
+{
+	let mut buffer = [0u8; 8];
+	buffer.copy_from_slice(data);
+
+	u64::from_le_bytes(buffer)
+}
+
+/// Convert a little endian slice of sixteen bytes to a u128.
+fn to_u128(data: &[u8]) -> u128
+{
+	let mut buffer = [0u8; 16];
+	buffer.copy_from_slice(data);
+
+	u128::from_le_bytes(buffer)
+}
+
+/// Convert a Molecule TokenAmountVec to a list of token amounts.
+fn to_token_amounts(token_amounts: order_types::TokenAmountVecReader) -> Vec<TokenAmount>
+{
+	token_amounts.iter().map(|token_amount| TokenAmount { type_hash: to_hash(token_amount.type_hash().raw_data()), amount: to_u128(token_amount.amount().raw_data()) }).collect()
+}
+
+/// Convert a list of token amounts to a Molecule TokenAmountVec.
+fn to_token_amount_vec(token_amounts: &[TokenAmount]) -> order_types::TokenAmountVec
+{
+	let token_amounts = token_amounts.iter().map(|token_amount| order_types::TokenAmount::new_builder().type_hash(token_amount.type_hash.into()).amount(token_amount.amount.into()).build());
+
+	order_types::TokenAmountVec::new_builder().set(token_amounts.collect()).build()
+}
@@ -0,0 +1,296 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use ckb_types::bytes::Bytes;
+use ckb_types::core::{ScriptHashType, TransactionView};
+use ckb_types::packed::{CellOutput, OutPoint};
+use ckb_types::prelude::*;
+
+use crate::cell::LiveCell;
+use crate::error::Error;
+use crate::sudt::OUT_POINT_LEN;
+use crate::SCRIPT_HASH_LEN;
+
+// Constants
+const LENGTH_LEN: usize = 4; // Lengths in a snapshot are a u32, which is 4 bytes.
+
+/// A search for live cells. Every field which is set must match.
+///
+/// Order cells are found by the code hash and hash type of the Token Buy Lock and a prefix of the args, such as the SUDT type hash of legacy args.
+/// SUDT cells are found by their type hash and the lock hash of the holder.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CellQuery
+{
+	pub lock_code_hash: Option<[u8; SCRIPT_HASH_LEN]>,
+	pub lock_hash_type: Option<u8>,
+	pub lock_args_prefix: Vec<u8>,
+	pub lock_hash: Option<[u8; SCRIPT_HASH_LEN]>,
+	pub type_hash: Option<[u8; SCRIPT_HASH_LEN]>,
+}
+
+impl CellQuery
+{
+	/// Search for cells with a lock script which uses the code hash and hash type.
+	pub fn by_lock_code_hash(code_hash: [u8; SCRIPT_HASH_LEN], hash_type: ScriptHashType) -> Self
+	{
+		CellQuery { lock_code_hash: Some(code_hash), lock_hash_type: Some(hash_type as u8), ..Default::default() }
+	}
+
+	/// Search for cells with the type script hash.
+	pub fn by_type_hash(type_hash: [u8; SCRIPT_HASH_LEN]) -> Self
+	{
+		CellQuery { type_hash: Some(type_hash), ..Default::default() }
+	}
+
+	/// Only match cells whose lock script args start with the prefix.
+	pub fn lock_args_prefix(mut self, prefix: &[u8]) -> Self
+	{
+		self.lock_args_prefix = prefix.to_vec();
+		self
+	}
+
+	/// Only match cells with the lock hash.
+	pub fn lock_hash(mut self, lock_hash: [u8; SCRIPT_HASH_LEN]) -> Self
+	{
+		self.lock_hash = Some(lock_hash);
+		self
+	}
+
+	/// Determine if a cell matches the search.
+	pub fn matches(&self, cell: &LiveCell) -> bool
+	{
+		let lock_script = cell.output.lock();
+		if let Some(code_hash) = &self.lock_code_hash
+		{
+			if lock_script.code_hash().as_slice() != &code_hash[..]
+			{
+				return false;
+			}
+		}
+
+		if let Some(hash_type) = self.lock_hash_type
+		{
+			if lock_script.hash_type().as_slice() != [hash_type]
+			{
+				return false;
+			}
+		}
+
+		if !lock_script.args().raw_data().starts_with(&self.lock_args_prefix)
+		{
+			return false;
+		}
+
+		if let Some(lock_hash) = &self.lock_hash
+		{
+			if lock_script.calc_script_hash().as_slice() != &lock_hash[..]
+			{
+				return false;
+			}
+		}
+
+		if let Some(type_hash) = &self.type_hash
+		{
+			match cell.output.type_().to_opt()
+			{
+				Some(type_script) if type_script.calc_script_hash().as_slice() == &type_hash[..] => {},
+				_ => return false,
+			}
+		}
+
+		true
+	}
+}
+
+/// A source of live cells, such as an indexer or a snapshot.
+pub trait CellProvider
+{
+	/// Collect every live cell which matches the search, ordered by transaction hash and then by output index.
+	fn collect_cells(&self, query: &CellQuery) -> Result<Vec<LiveCell>, Error>;
+}
+
+/// Live cells held in memory, for tests and for tools which track their own cells.
+#[derive(Clone, Debug, Default)]
+pub struct MemoryCellProvider
+{
+	cells: BTreeMap<([u8; SCRIPT_HASH_LEN], u32), LiveCell>,
+}
+
+impl MemoryCellProvider
+{
+	/// Create a provider with no cells.
+	pub fn new() -> Self
+	{
+		MemoryCellProvider::default()
+	}
+
+	/// Create a provider with every cell in a test context, including the deployed contracts.
+	#[cfg(feature = "testtool")]
+	pub fn from_context(context: &ckb_testtool::context::Context) -> Self
+	{
+		let mut provider = MemoryCellProvider::new();
+		for (out_point, (output, data)) in &context.cells
+		{
+			provider.insert(LiveCell { out_point: out_point.clone(), output: output.clone(), data: data.clone() });
+		}
+
+		provider
+	}
+
+	/// Add a live cell, replacing any cell with the same out point.
+	pub fn insert(&mut self, cell: LiveCell)
+	{
+		self.cells.insert(out_point_key(&cell.out_point), cell);
+	}
+
+	/// Remove a cell, returning it if it was live.
+	pub fn remove(&mut self, out_point: &OutPoint) -> Option<LiveCell>
+	{
+		self.cells.remove(&out_point_key(out_point))
+	}
+
+	/// Consume the inputs of a transaction and add its outputs, the same as when the transaction is committed.
+	pub fn apply_transaction(&mut self, tx: &TransactionView)
+	{
+		for out_point in tx.input_pts_iter()
+		{
+			self.remove(&out_point);
+		}
+
+		for (index, (output, data)) in tx.outputs_with_data_iter().enumerate()
+		{
+			let out_point = OutPoint::new(tx.hash(), index as u32);
+			self.insert(LiveCell { out_point, output, data });
+		}
+	}
+
+	/// Iterate over every live cell, ordered by transaction hash and then by output index.
+	pub fn cells(&self) -> impl Iterator<Item = &LiveCell>
+	{
+		self.cells.values()
+	}
+}
+
+impl CellProvider for MemoryCellProvider
+{
+	fn collect_cells(&self, query: &CellQuery) -> Result<Vec<LiveCell>, Error>
+	{
+		Ok(self.cells.values().filter(|cell| query.matches(cell)).cloned().collect())
+	}
+}
+
+/// Live cells loaded from a snapshot file, so tools can be run against a fixed set of cells without a node.
+///
+/// A snapshot is a list of cells, each stored as:
+/// - The 36 byte out point.
+/// - The length of the Molecule encoded `CellOutput`, followed by the `CellOutput`. (u32)
+/// - The length of the cell data, followed by the data. (u32)
+#[derive(Clone, Debug)]
+pub struct FileCellProvider
+{
+	path: PathBuf,
+	cells: MemoryCellProvider,
+}
+
+impl FileCellProvider
+{
+	/// Load a snapshot file.
+	pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, Error>
+	{
+		let snapshot = fs::read(path.as_ref()).map_err(|e| Error::Io(e.kind()))?;
+		let cells = decode_snapshot(&snapshot)?;
+
+		Ok(FileCellProvider { path: path.as_ref().to_path_buf(), cells })
+	}
+
+	/// Write a snapshot file of the cells and load it.
+	pub fn create<'a, P, I>(path: P, cells: I) -> Result<Self, Error>
+		where P: AsRef<Path>, I: IntoIterator<Item = &'a LiveCell>
+	{
+		let mut snapshot = Vec::new();
+		for cell in cells
+		{
+			snapshot.extend_from_slice(cell.out_point.as_slice());
+			encode_length_prefixed(&mut snapshot, cell.output.as_slice())?;
+			encode_length_prefixed(&mut snapshot, &cell.data)?;
+		}
+		fs::write(path.as_ref(), &snapshot).map_err(|e| Error::Io(e.kind()))?;
+
+		FileCellProvider::open(path)
+	}
+
+	/// The path of the snapshot file.
+	pub fn path(&self) -> &Path
+	{
+		&self.path
+	}
+}
+
+impl CellProvider for FileCellProvider
+{
+	fn collect_cells(&self, query: &CellQuery) -> Result<Vec<LiveCell>, Error>
+	{
+		self.cells.collect_cells(query)
+	}
+}
+
+/// Create the key which orders cells by out point, by the transaction hash and then by the numeric output index.
+fn out_point_key(out_point: &OutPoint) -> ([u8; SCRIPT_HASH_LEN], u32)
+{
+	(out_point.tx_hash().unpack(), out_point.index().unpack())
+}
+
+/// Append the u32 length of a value followed by the value.
+fn encode_length_prefixed(buffer: &mut Vec<u8>, value: &[u8]) -> Result<(), Error>
+{
+	if value.len() > u32::MAX as usize
+	{
+		return Err(Error::Overflow);
+	}
+	buffer.extend_from_slice(&(value.len() as u32).to_le_bytes());
+	buffer.extend_from_slice(value);
+
+	Ok(())
+}
+
+/// Split a value which is prefixed by its u32 length from the front of the data.
+fn decode_length_prefixed(data: &[u8]) -> Result<(&[u8], &[u8]), Error>
+{
+	if data.len() < LENGTH_LEN
+	{
+		return Err(Error::DataLength);
+	}
+
+	let mut buffer = [0u8; LENGTH_LEN];
+	buffer.copy_from_slice(&data[0..LENGTH_LEN]);
+	let length = u32::from_le_bytes(buffer) as usize;
+	if data.len() - LENGTH_LEN < length
+	{
+		return Err(Error::DataLength);
+	}
+
+	Ok(data[LENGTH_LEN..].split_at(length))
+}
+
+/// Decode every cell in a snapshot.
+fn decode_snapshot(mut snapshot: &[u8]) -> Result<MemoryCellProvider, Error>
+{
+	let mut cells = MemoryCellProvider::new();
+	while !snapshot.is_empty()
+	{
+		if snapshot.len() < OUT_POINT_LEN
+		{
+			return Err(Error::DataLength);
+		}
+		let out_point = OutPoint::from_slice(&snapshot[0..OUT_POINT_LEN]).map_err(|_| Error::Encoding)?;
+		let (output, rest) = decode_length_prefixed(&snapshot[OUT_POINT_LEN..])?;
+		let output = CellOutput::from_slice(output).map_err(|_| Error::Encoding)?;
+		let (data, rest) = decode_length_prefixed(rest)?;
+
+		cells.insert(LiveCell { out_point, output, data: Bytes::from(data.to_vec()) });
+		snapshot = rest;
+	}
+
+	Ok(cells)
+}
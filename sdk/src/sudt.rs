@@ -0,0 +1,77 @@
+use crate::error::Error;
+use crate::SUDT_DATA_LEN;
+
+// Constants
+pub const OUT_POINT_LEN: usize = 36; // An out point is a 32 byte transaction hash followed by a u32 index.
+pub const BINDING_MARKER: &[u8] = b"TBLB"; // A bound payment must opt in with this marker, so other data after the SUDT amount is never read as a binding.
+
+/// The data of an SUDT cell.
+///
+/// The cell data layout is:
+/// - `[0..16]` SUDT amount. (u128)
+/// - `[16..20]` Binding marker, `TBLB`. (optional)
+/// - `[20..56]` Out point of the order cell a payment is bound to. (only present after the marker)
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SudtAmount
+{
+	pub amount: u128,
+	pub binding: Option<[u8; OUT_POINT_LEN]>,
+}
+
+impl SudtAmount
+{
+	/// Create the data for an SUDT cell which is not bound to an order.
+	pub fn new(amount: u128) -> Self
+	{
+		SudtAmount { amount, binding: None }
+	}
+
+	/// Create the data for an SUDT payment which is bound to the out point of an order cell.
+	pub fn bound(amount: u128, out_point: [u8; OUT_POINT_LEN]) -> Self
+	{
+		SudtAmount { amount, binding: Some(out_point) }
+	}
+
+	/// Decode the data of an SUDT cell.
+	///
+	/// Data which is too short for the amount is an encoding error, the same as in the SUDT type script.
+	pub fn from_data(data: &[u8]) -> Result<Self, Error>
+	{
+		if data.len() < SUDT_DATA_LEN
+		{
+			return Err(Error::Encoding);
+		}
+
+		let mut buffer = [0u8; SUDT_DATA_LEN];
+		buffer.copy_from_slice(&data[0..SUDT_DATA_LEN]);
+		let amount = u128::from_le_bytes(buffer);
+
+		// Data without the binding marker is not bound, the same as in the Token Buy Lock.
+		let offset = SUDT_DATA_LEN + BINDING_MARKER.len();
+		let binding = if data.len() >= offset + OUT_POINT_LEN && &data[SUDT_DATA_LEN..offset] == BINDING_MARKER
+		{
+			let mut out_point = [0u8; OUT_POINT_LEN];
+			out_point.copy_from_slice(&data[offset..offset+OUT_POINT_LEN]);
+			Some(out_point)
+		}
+		else
+		{
+			None
+		};
+
+		Ok(SudtAmount { amount, binding })
+	}
+
+	/// Encode the data of an SUDT cell.
+	pub fn to_data(&self) -> Vec<u8>
+	{
+		let mut data = self.amount.to_le_bytes().to_vec();
+		if let Some(out_point) = &self.binding
+		{
+			data.extend_from_slice(BINDING_MARKER);
+			data.extend_from_slice(out_point);
+		}
+
+		data
+	}
+}
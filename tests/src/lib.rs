@@ -1,15 +1,28 @@
+use ckb_testtool::context::Context;
 use ckb_tool::ckb_types::bytes::Bytes;
+use ckb_tool::ckb_types::packed::CellDep;
+use ckb_tool::ckb_types::prelude::*;
 use std::env;
 use std::fs;
 use std::path::PathBuf;
 use std::str::FromStr;
 
+#[cfg(test)]
+mod mock_oracle;
+#[cfg(test)]
+mod sdk;
 #[cfg(test)]
 mod sudt;
 #[cfg(test)]
 mod token_buy_lock;
+#[cfg(test)]
+mod token_sell_lock;
+#[cfg(test)]
+mod token_swap_lock;
 
 const TEST_ENV_VAR: &str = "CAPSULE_TEST_ENV";
+const SECP256K1_LIB: &str = "secp256k1_blake2b_sighash_all_dual";
+const SECP256K1_DATA: &str = "specs/cells/secp256k1_data";
 
 pub enum TestEnv {
     Debug,
@@ -61,3 +74,23 @@ impl Loader {
         fs::read(path).expect("binary").into()
     }
 }
+
+/// Load a binary which is not built by this project from the deps directory.
+pub fn load_dependency(name: &str) -> Bytes {
+    let mut path = env::current_dir().unwrap();
+    path.push("..");
+    path.push("deps");
+    path.push(name);
+    fs::read(path).expect("dependency in deps, run scripts/fetch-deps.sh").into()
+}
+
+/// Deploy the secp256k1 library which verifies owner signatures, and the secp256k1_data cell it loads, returning their cell deps.
+pub fn deploy_secp256k1(context: &mut Context) -> (CellDep, CellDep) {
+    let out_point_secp256k1_lib = context.deploy_cell(load_dependency(SECP256K1_LIB));
+    let secp256k1_data = ckb_system_scripts::BUNDLED_CELL.get(SECP256K1_DATA).expect("secp256k1 data");
+    let out_point_secp256k1_data = context.deploy_cell(Bytes::from(secp256k1_data.to_vec()));
+
+    let secp256k1_lib_dep = CellDep::new_builder().out_point(out_point_secp256k1_lib).build();
+    let secp256k1_data_dep = CellDep::new_builder().out_point(out_point_secp256k1_data).build();
+    (secp256k1_lib_dep, secp256k1_data_dep)
+}
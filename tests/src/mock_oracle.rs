@@ -0,0 +1,238 @@
+use super::*;
+use ckb_testtool::{builtin::ALWAYS_SUCCESS, context::Context};
+use ckb_tool::{ckb_error::assert_error_eq, ckb_script::ScriptError};
+use ckb_tool::ckb_types::{bytes::Bytes, packed::*, prelude::*};
+use ckb_tool::ckb_types::core::{TransactionBuilder};
+
+// Constants
+const MAX_CYCLES: u64 = 100_000_000;
+
+// Error Codes
+const ERROR_DATA_LEN: i8 = 6;
+const ERROR_DENOMINATOR: i8 = 7;
+const ERROR_OWNER: i8 = 8;
+
+#[test]
+fn test_mock_oracle_update_by_owner()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_mock_oracle = context.deploy_cell(Loader::default().load_binary("mock-oracle"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let mock_oracle_dep = CellDep::new_builder().out_point(out_point_mock_oracle.clone()).build();
+
+	// Prepare Identities
+	let oracle_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![4u8; 1])).expect("script");
+	let oracle_lock_script_hash: [u8; 32] = oracle_lock_script.calc_script_hash().unpack();
+	let other_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![5u8; 1])).expect("script");
+
+	// Prepare Mock Oracle Type Script
+	let script_args: Bytes = oracle_lock_script_hash.to_vec().into();
+	let oracle_type_script = context.build_script(&out_point_mock_oracle, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(1_000_000_000_000_u64.pack()).lock(oracle_lock_script.clone()).build(), Bytes::new());
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(1_000_000_000_000_u64.pack()).lock(other_lock_script.clone()).type_(Some(oracle_type_script.clone()).pack()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	let mut data = vec!();
+	data.extend(1u128.to_le_bytes().to_vec());
+	data.extend(1_000_000_000u128.to_le_bytes().to_vec());
+	outputs_data.push(Bytes::from(data));
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(mock_oracle_dep)
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Run
+	let _cycles = context.verify_tx(&tx, MAX_CYCLES).expect("pass verification");
+	// println!("consume cycles: {}", cycles);
+}
+
+#[test]
+fn test_mock_oracle_update_without_owner()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_mock_oracle = context.deploy_cell(Loader::default().load_binary("mock-oracle"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let mock_oracle_dep = CellDep::new_builder().out_point(out_point_mock_oracle.clone()).build();
+
+	// Prepare Identities
+	let oracle_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![4u8; 1])).expect("script");
+	let oracle_lock_script_hash: [u8; 32] = oracle_lock_script.calc_script_hash().unpack();
+	let other_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![5u8; 1])).expect("script");
+
+	// Prepare Mock Oracle Type Script
+	let script_args: Bytes = oracle_lock_script_hash.to_vec().into();
+	let oracle_type_script = context.build_script(&out_point_mock_oracle, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(1_000_000_000_000_u64.pack()).lock(other_lock_script.clone()).build(), Bytes::new());
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(1_000_000_000_000_u64.pack()).lock(other_lock_script.clone()).type_(Some(oracle_type_script.clone()).pack()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	let mut data = vec!();
+	data.extend(1u128.to_le_bytes().to_vec());
+	data.extend(1_000_000_000u128.to_le_bytes().to_vec());
+	outputs_data.push(Bytes::from(data));
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(mock_oracle_dep)
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Run
+	let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+	assert_error_eq!(err, ScriptError::ValidationFailure(ERROR_OWNER).output_type_script(0));
+}
+
+#[test]
+fn test_mock_oracle_trailing_data()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_mock_oracle = context.deploy_cell(Loader::default().load_binary("mock-oracle"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let mock_oracle_dep = CellDep::new_builder().out_point(out_point_mock_oracle.clone()).build();
+
+	// Prepare Identities
+	let oracle_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![4u8; 1])).expect("script");
+	let oracle_lock_script_hash: [u8; 32] = oracle_lock_script.calc_script_hash().unpack();
+	let other_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![5u8; 1])).expect("script");
+
+	// Prepare Mock Oracle Type Script
+	let script_args: Bytes = oracle_lock_script_hash.to_vec().into();
+	let oracle_type_script = context.build_script(&out_point_mock_oracle, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(1_000_000_000_000_u64.pack()).lock(oracle_lock_script.clone()).build(), Bytes::new());
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(1_000_000_000_000_u64.pack()).lock(other_lock_script.clone()).type_(Some(oracle_type_script.clone()).pack()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	let mut data = vec!();
+	data.extend(1u128.to_le_bytes().to_vec());
+	data.extend(1_000_000_000u128.to_le_bytes().to_vec());
+	data.extend(vec![0u8; 1]);
+	outputs_data.push(Bytes::from(data));
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(mock_oracle_dep)
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Run
+	let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+	assert_error_eq!(err, ScriptError::ValidationFailure(ERROR_DATA_LEN).output_type_script(0));
+}
+
+#[test]
+fn test_mock_oracle_zero_denominator()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_mock_oracle = context.deploy_cell(Loader::default().load_binary("mock-oracle"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let mock_oracle_dep = CellDep::new_builder().out_point(out_point_mock_oracle.clone()).build();
+
+	// Prepare Identities
+	let oracle_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![4u8; 1])).expect("script");
+	let oracle_lock_script_hash: [u8; 32] = oracle_lock_script.calc_script_hash().unpack();
+	let other_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![5u8; 1])).expect("script");
+
+	// Prepare Mock Oracle Type Script
+	let script_args: Bytes = oracle_lock_script_hash.to_vec().into();
+	let oracle_type_script = context.build_script(&out_point_mock_oracle, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(1_000_000_000_000_u64.pack()).lock(oracle_lock_script.clone()).build(), Bytes::new());
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(1_000_000_000_000_u64.pack()).lock(other_lock_script.clone()).type_(Some(oracle_type_script.clone()).pack()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	let mut data = vec!();
+	data.extend(1u128.to_le_bytes().to_vec());
+	data.extend(0u128.to_le_bytes().to_vec());
+	outputs_data.push(Bytes::from(data));
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(mock_oracle_dep)
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Run
+	let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+	assert_error_eq!(err, ScriptError::ValidationFailure(ERROR_DENOMINATOR).output_type_script(0));
+}
@@ -0,0 +1,1585 @@
+use super::*;
+use ckb_testtool::{builtin::ALWAYS_SUCCESS, context::Context};
+use ckb_tool::ckb_types::{bytes::Bytes, packed::*, prelude::*};
+use ckb_tool::ckb_types::core::{ScriptHashType, TransactionBuilder};
+use token_buy_lock_sdk::{Auction, AuctionMetric, CancelBuilder, CellProvider, CellQuery, CreateBuilder, Depth, Error, Fee, FileCellProvider, FillBuilder, LiveCell, LockArgs, MemoryCellProvider, Oracle, Order, OrderBook, Price, SudtAmount, TokenAmount, BINDING_MARKER, LEGACY_FLAGS_MARKER, LEGACY_VERSION, ORDER_VERSION, ORDER_VERSION_V1, ORDER_VERSION_V2};
+use token_buy_lock_types::{encode_versioned, encode_with_version, token_buy_lock as order_types};
+
+// Constants
+const MAX_CYCLES: u64 = 100_000_000;
+
+#[test]
+fn test_sdk_sudt_amount_round_trip()
+{
+	let sudt_amount = SudtAmount::new(9_000u128);
+	let data = sudt_amount.to_data();
+	assert_eq!(data, 9_000u128.to_le_bytes().to_vec());
+	assert_eq!(SudtAmount::from_data(&data), Ok(sudt_amount));
+}
+
+#[test]
+fn test_sdk_sudt_amount_bound_round_trip()
+{
+	let sudt_amount = SudtAmount::bound(100u128, [7u8; 36]);
+	let data = sudt_amount.to_data();
+	let mut expected = 100u128.to_le_bytes().to_vec();
+	expected.extend(BINDING_MARKER.to_vec());
+	expected.extend(vec![7u8; 36]);
+	assert_eq!(data, expected);
+	assert_eq!(SudtAmount::from_data(&data), Ok(sudt_amount));
+}
+
+#[test]
+fn test_sdk_sudt_amount_short_binding()
+{
+	// Trailing data which is too short for an out point is not a binding.
+	let mut data = 100u128.to_le_bytes().to_vec();
+	data.extend(vec![7u8; 35]);
+	assert_eq!(SudtAmount::from_data(&data), Ok(SudtAmount::new(100u128)));
+}
+
+#[test]
+fn test_sdk_sudt_amount_unmarked_binding()
+{
+	// Trailing data which is long enough for an out point is not a binding without the binding marker.
+	let mut data = 100u128.to_le_bytes().to_vec();
+	data.extend(vec![7u8; 36]);
+	assert_eq!(SudtAmount::from_data(&data), Ok(SudtAmount::new(100u128)));
+}
+
+#[test]
+fn test_sdk_sudt_amount_short()
+{
+	assert_eq!(SudtAmount::from_data(&1u32.to_le_bytes()), Err(Error::Encoding));
+}
+
+#[test]
+fn test_sdk_order_legacy_round_trip()
+{
+	let order = Order::new([1u8; 32], 100u128);
+	let data = order.to_data(LEGACY_VERSION).expect("encode");
+	let mut expected = vec!();
+	expected.extend([1u8; 32].to_vec());
+	expected.extend(100u128.to_le_bytes().to_vec());
+	assert_eq!(data, expected);
+	assert_eq!(Order::from_data(&data, LEGACY_VERSION), Ok(order));
+}
+
+#[test]
+fn test_sdk_order_legacy_flags_round_trip()
+{
+	let mut order = Order::new([1u8; 32], 100u128);
+	order.partial_fill = true;
+	order.price_denominator = Some(1_000_000_000u128);
+	order.bound_payment = true;
+	order.payout_lock_hash = Some([2u8; 32]);
+	order.fee = Some(Fee { lock_hash: [3u8; 32], rate: 250u16 });
+	order.allowlist_root = Some([4u8; 32]);
+	let data = order.to_data(LEGACY_VERSION).expect("encode");
+	let mut expected = vec!();
+	expected.extend([1u8; 32].to_vec());
+	expected.extend(100u128.to_le_bytes().to_vec());
+	expected.extend(LEGACY_FLAGS_MARKER.to_vec());
+	expected.extend(0b0101_1111u16.to_le_bytes().to_vec());
+	expected.extend(1_000_000_000u128.to_le_bytes().to_vec());
+	expected.extend([2u8; 32].to_vec());
+	expected.extend([3u8; 32].to_vec());
+	expected.extend(250u16.to_le_bytes().to_vec());
+	expected.extend([4u8; 32].to_vec());
+	assert_eq!(data, expected);
+	assert_eq!(Order::from_data(&data, LEGACY_VERSION), Ok(order));
+}
+
+#[test]
+fn test_sdk_order_legacy_auction_round_trip()
+{
+	let mut order = Order::new([1u8; 32], 100u128);
+	order.auction = Some(Auction { end_amount: 50u128, metric: AuctionMetric::Timestamp, start: 1_000u64, end: 2_000u64 });
+	let data = order.to_data(LEGACY_VERSION).expect("encode");
+	let mut expected = vec!();
+	expected.extend([1u8; 32].to_vec());
+	expected.extend(100u128.to_le_bytes().to_vec());
+	expected.extend(LEGACY_FLAGS_MARKER.to_vec());
+	expected.extend((1u16 << 5).to_le_bytes().to_vec());
+	expected.extend(50u128.to_le_bytes().to_vec());
+	expected.push(1u8);
+	expected.extend(1_000u64.to_le_bytes().to_vec());
+	expected.extend(2_000u64.to_le_bytes().to_vec());
+	assert_eq!(data, expected);
+	assert_eq!(Order::from_data(&data, LEGACY_VERSION), Ok(order));
+}
+
+#[test]
+fn test_sdk_order_legacy_oracle_bundle_round_trip()
+{
+	let mut order = Order::new([1u8; 32], 100u128);
+	order.oracle = Some(Oracle { type_hash: [5u8; 32], spread: 50u16 });
+	order.bundle = vec![TokenAmount { type_hash: [6u8; 32], amount: 10u128 }, TokenAmount { type_hash: [7u8; 32], amount: 20u128 }];
+	let data = order.to_data(LEGACY_VERSION).expect("encode");
+	let mut expected = vec!();
+	expected.extend([1u8; 32].to_vec());
+	expected.extend(100u128.to_le_bytes().to_vec());
+	expected.extend(LEGACY_FLAGS_MARKER.to_vec());
+	expected.extend(((1u16 << 7) | (1u16 << 8)).to_le_bytes().to_vec());
+	expected.extend([5u8; 32].to_vec());
+	expected.extend(50u16.to_le_bytes().to_vec());
+	expected.push(2u8);
+	expected.extend([6u8; 32].to_vec());
+	expected.extend(10u128.to_le_bytes().to_vec());
+	expected.extend([7u8; 32].to_vec());
+	expected.extend(20u128.to_le_bytes().to_vec());
+	assert_eq!(data, expected);
+	assert_eq!(Order::from_data(&data, LEGACY_VERSION), Ok(order));
+}
+
+#[test]
+fn test_sdk_order_legacy_alternatives_round_trip()
+{
+	let mut order = Order::new([1u8; 32], 100u128);
+	order.alternatives = vec![TokenAmount { type_hash: [6u8; 32], amount: 10u128 }];
+	let data = order.to_data(LEGACY_VERSION).expect("encode");
+	assert_eq!(Order::from_data(&data, LEGACY_VERSION), Ok(order));
+}
+
+#[test]
+fn test_sdk_order_legacy_versioned_only_field()
+{
+	let mut order = Order::new([1u8; 32], 100u128);
+	order.min_payout_capacity = Some(61_000_000_000u64);
+	assert_eq!(order.to_data(LEGACY_VERSION), Err(Error::Encoding));
+}
+
+#[test]
+fn test_sdk_order_legacy_short()
+{
+	let data = [1u8; 47];
+	assert_eq!(Order::from_data(&data, LEGACY_VERSION), Err(Error::DataLength));
+}
+
+#[test]
+fn test_sdk_order_legacy_truncated_field()
+{
+	let mut order = Order::new([1u8; 32], 100u128);
+	order.payout_lock_hash = Some([2u8; 32]);
+	let data = order.to_data(LEGACY_VERSION).expect("encode");
+	assert_eq!(Order::from_data(&data[..data.len()-1], LEGACY_VERSION), Err(Error::DataLength));
+}
+
+#[test]
+fn test_sdk_order_legacy_unsupported_flag()
+{
+	let mut data = vec!();
+	data.extend([1u8; 32].to_vec());
+	data.extend(100u128.to_le_bytes().to_vec());
+	data.extend(LEGACY_FLAGS_MARKER.to_vec());
+	data.extend((1u16 << 15).to_le_bytes().to_vec());
+	assert_eq!(Order::from_data(&data, LEGACY_VERSION), Err(Error::Encoding));
+}
+
+#[test]
+fn test_sdk_order_legacy_empty_bundle()
+{
+	let mut data = vec!();
+	data.extend([1u8; 32].to_vec());
+	data.extend(100u128.to_le_bytes().to_vec());
+	data.extend(LEGACY_FLAGS_MARKER.to_vec());
+	data.extend((1u16 << 8).to_le_bytes().to_vec());
+	data.push(0u8);
+	assert_eq!(Order::from_data(&data, LEGACY_VERSION), Err(Error::Encoding));
+}
+
+#[test]
+fn test_sdk_order_legacy_trailing_data()
+{
+	let order = Order::new([1u8; 32], 100u128);
+	let mut data = order.to_data(LEGACY_VERSION).expect("encode");
+	data.push(1u8);
+	assert_eq!(Order::from_data(&data, LEGACY_VERSION), Ok(order.clone()));
+	data.extend((1u16 << 15).to_le_bytes().to_vec());
+	assert_eq!(Order::from_data(&data, LEGACY_VERSION), Ok(order));
+}
+
+#[test]
+fn test_sdk_order_invalid_terms()
+{
+	let mut order = Order::new([1u8; 32], 100u128);
+	order.fee = Some(Fee { lock_hash: [3u8; 32], rate: 10_001u16 });
+	assert_eq!(order.to_data(LEGACY_VERSION), Err(Error::Encoding));
+	assert_eq!(order.to_data(ORDER_VERSION), Err(Error::Encoding));
+
+	let mut order = Order::new([1u8; 32], 100u128);
+	order.partial_fill = true;
+	order.alternatives = vec![TokenAmount { type_hash: [6u8; 32], amount: 10u128 }];
+	assert_eq!(order.to_data(ORDER_VERSION), Err(Error::Encoding));
+}
+
+#[test]
+fn test_sdk_order_versioned_round_trip()
+{
+	let mut order = Order::new([1u8; 32], 100u128);
+	order.payout_lock_hash = Some([2u8; 32]);
+	order.fee = Some(Fee { lock_hash: [3u8; 32], rate: 250u16 });
+	order.auction = Some(Auction { end_amount: 50u128, metric: AuctionMetric::BlockNumber, start: 1_000u64, end: 2_000u64 });
+	order.allowlist_root = Some([4u8; 32]);
+	order.bundle = vec![TokenAmount { type_hash: [6u8; 32], amount: 10u128 }];
+	order.min_payout_capacity = Some(61_000_000_000u64);
+	order.max_payout_cells = Some(2u32);
+	order.owner_pubkey_hash = Some([8u8; 20]);
+	let data = order.to_data(ORDER_VERSION).expect("encode");
+	assert_eq!(data[0], ORDER_VERSION);
+	assert_eq!(Order::from_data(&data, ORDER_VERSION), Ok(order));
+}
+
+#[test]
+fn test_sdk_order_versioned_matches_schema()
+{
+	let mut order = Order::new([1u8; 32], 100u128);
+	order.partial_fill = true;
+	let order_data = order_types::OrderData::new_builder()
+		.owner_lock_hash([1u8; 32].into())
+		.buy_amount(100u128.into())
+		.partial_fill(1u8.into())
+		.build();
+	assert_eq!(order.to_data(ORDER_VERSION), Ok(encode_versioned(&order_data)));
+}
+
+#[test]
+fn test_sdk_order_versioned_mismatch()
+{
+	let order = Order::new([1u8; 32], 100u128);
+	let data = order.to_data(ORDER_VERSION).expect("encode");
+	assert_eq!(Order::from_data(&data, LEGACY_VERSION), Err(Error::Encoding));
+	assert_eq!(Order::from_data(&data[1..], ORDER_VERSION), Err(Error::Encoding));
+	assert_eq!(Order::from_data(&[], ORDER_VERSION), Err(Error::DataLength));
+}
+
+#[test]
+fn test_sdk_order_versioned_v1()
+{
+	// Version 1 data predates the payout cell limits and the owner public key hash.
+	let order_data = order_types::OrderDataV1::new_builder()
+		.owner_lock_hash([1u8; 32].into())
+		.buy_amount(100u128.into())
+		.payout_lock_hash(order_types::Byte32Opt::new_builder().set(Some([2u8; 32].into())).build())
+		.build();
+	let data = encode_with_version(ORDER_VERSION_V1, &order_data);
+	let mut order = Order::new([1u8; 32], 100u128);
+	order.payout_lock_hash = Some([2u8; 32]);
+	assert_eq!(Order::from_data(&data, ORDER_VERSION_V1), Ok(order.clone()));
+	assert_eq!(order.to_data(ORDER_VERSION_V1), Ok(data.clone()));
+
+	// The data of each version is only decoded with the table of that version.
+	assert_eq!(Order::from_data(&data, ORDER_VERSION), Err(Error::Encoding));
+	let data = order.to_data(ORDER_VERSION).expect("encode");
+	let mut data_v1 = data.clone();
+	data_v1[0] = ORDER_VERSION_V1;
+	assert_eq!(Order::from_data(&data_v1, ORDER_VERSION_V1), Err(Error::Encoding));
+
+	// Fields which were added after version 1 cannot be encoded with it.
+	order.min_payout_capacity = Some(61_000_000_000u64);
+	assert_eq!(order.to_data(ORDER_VERSION_V1), Err(Error::Encoding));
+}
+
+#[test]
+fn test_sdk_order_versioned_v2()
+{
+	// Version 2 data has the payout cell limits, but predates the owner public key hash.
+	let order_data = order_types::OrderDataV2::new_builder()
+		.owner_lock_hash([1u8; 32].into())
+		.buy_amount(100u128.into())
+		.max_payout_cells(order_types::Uint32Opt::new_builder().set(Some(2u32.into())).build())
+		.build();
+	let data = encode_with_version(ORDER_VERSION_V2, &order_data);
+	let mut order = Order::new([1u8; 32], 100u128);
+	order.max_payout_cells = Some(2u32);
+	assert_eq!(Order::from_data(&data, ORDER_VERSION_V2), Ok(order.clone()));
+	assert_eq!(order.to_data(ORDER_VERSION_V2), Ok(data.clone()));
+	assert_eq!(Order::from_data(&data, ORDER_VERSION), Err(Error::Encoding));
+
+	// The owner public key hash cannot be encoded with version 2.
+	order.owner_pubkey_hash = Some([8u8; 20]);
+	assert_eq!(order.to_data(ORDER_VERSION_V2), Err(Error::Encoding));
+	let mut data = order.to_data(ORDER_VERSION).expect("encode");
+	data[0] = ORDER_VERSION_V2;
+	assert_eq!(Order::from_data(&data, ORDER_VERSION_V2), Err(Error::Encoding));
+}
+
+#[test]
+fn test_sdk_lock_args_versioned_v1()
+{
+	let lock_args = LockArgs { version: ORDER_VERSION_V1, sudt_type_hash: [1u8; 32], recovery_lock_hash: None };
+	let args = lock_args.to_args().expect("encode");
+	assert_eq!(args[0], ORDER_VERSION_V1);
+	assert_eq!(LockArgs::from_args(&args), Ok(lock_args));
+}
+
+#[test]
+fn test_sdk_order_versioned_zero_payout_cells()
+{
+	let mut order = Order::new([1u8; 32], 100u128);
+	order.max_payout_cells = Some(0u32);
+	assert_eq!(order.to_data(ORDER_VERSION), Err(Error::Encoding));
+}
+
+#[test]
+fn test_sdk_order_accepted_by_contract()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_buy_lock = context.deploy_cell(Loader::default().load_binary("token-buy-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_buy_lock_dep = CellDep::new_builder().out_point(out_point_token_buy_lock.clone()).build();
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	let fee_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![2u8; 1])).expect("script");
+	let fee_lock_script_hash: [u8; 32] = fee_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Token Buy Lock Script
+	let script_args: Bytes = sudt_type_script_hash.to_vec().into();
+	let token_buy_lock_script = context.build_script(&out_point_token_buy_lock, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let mut order = Order::new(buyer_lock_script_hash, 100u128);
+	order.fee = Some(Fee { lock_hash: fee_lock_script_hash, rate: 250u16 });
+	let data = order.to_data(LEGACY_VERSION).expect("encode");
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let data = SudtAmount::new(9_000u128).to_data();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(fee_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	outputs_data.push(Bytes::from(SudtAmount::new(98u128).to_data()));
+	outputs_data.push(Bytes::from(SudtAmount::new(2u128).to_data()));
+	outputs_data.push(Bytes::from(SudtAmount::new(8_900u128).to_data()));
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.cell_dep(token_buy_lock_dep)
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Run
+	let _cycles = context.verify_tx(&tx, MAX_CYCLES).expect("pass verification");
+	// println!("consume cycles: {}", cycles);
+}
+
+#[test]
+fn test_sdk_fill()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_buy_lock = context.deploy_cell(Loader::default().load_binary("token-buy-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_buy_lock_dep = CellDep::new_builder().out_point(out_point_token_buy_lock.clone()).build();
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	let fee_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![2u8; 1])).expect("script");
+	let fee_lock_script_hash: [u8; 32] = fee_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Token Buy Lock Script
+	let script_args: Bytes = sudt_type_script_hash.to_vec().into();
+	let token_buy_lock_script = context.build_script(&out_point_token_buy_lock, script_args).expect("script");
+
+	// Prepare Order Cells
+	let mut order_cells = vec![];
+	let order = Order::new(buyer_lock_script_hash, 100u128);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build();
+	let data = Bytes::from(order.to_data(LEGACY_VERSION).expect("encode"));
+	let out_point = context.create_cell(output.clone(), data.clone());
+	order_cells.push(LiveCell { out_point, output, data });
+	let mut order = Order::new(buyer_lock_script_hash, 100u128);
+	order.fee = Some(Fee { lock_hash: fee_lock_script_hash, rate: 250u16 });
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build();
+	let data = Bytes::from(order.to_data(LEGACY_VERSION).expect("encode"));
+	let out_point = context.create_cell(output.clone(), data.clone());
+	order_cells.push(LiveCell { out_point, output, data });
+
+	// Prepare SUDT Cells
+	let mut sudt_cells = vec![];
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	let data = Bytes::from(SudtAmount::new(9_000u128).to_data());
+	let out_point = context.create_cell(output.clone(), data.clone());
+	sudt_cells.push(LiveCell { out_point, output, data });
+
+	// Build Transaction
+	let mut builder = FillBuilder::new(token_buy_lock_script.code_hash(), ScriptHashType::Data, token_buy_lock_dep, sudt_dep, seller_lock_script.clone())
+		.cell_dep(always_success_dep)
+		.lock_script(buyer_lock_script.clone())
+		.lock_script(fee_lock_script.clone())
+		.fee(100_000_u64);
+	for order_cell in order_cells
+	{
+		builder = builder.order(order_cell);
+	}
+	for sudt_cell in sudt_cells
+	{
+		builder = builder.sudt(sudt_cell);
+	}
+	let tx = builder.build().expect("build");
+
+	// Run
+	// The builder adds every cell dep, so the transaction is not completed by the context.
+	let _cycles = context.verify_tx(&tx, MAX_CYCLES).expect("pass verification");
+	// println!("consume cycles: {}", cycles);
+
+	// Check Outputs
+	let outputs_data: Vec<SudtAmount> = tx.outputs_data().into_iter().take(3).map(|data| SudtAmount::from_data(&data.raw_data()).expect("decode")).collect();
+	assert_eq!(tx.outputs().len(), 4);
+	assert_eq!(outputs_data.iter().map(|data| data.amount).sum::<u128>(), 9_000u128);
+	assert!(outputs_data.contains(&SudtAmount::new(198u128)));
+	assert!(outputs_data.contains(&SudtAmount::new(2u128)));
+	assert!(outputs_data.contains(&SudtAmount::new(8_800u128)));
+}
+
+#[test]
+fn test_sdk_fill_bound_payment_and_price()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_buy_lock = context.deploy_cell(Loader::default().load_binary("token-buy-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_buy_lock_dep = CellDep::new_builder().out_point(out_point_token_buy_lock.clone()).build();
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	let fee_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![2u8; 1])).expect("script");
+	// let fee_lock_script_hash: [u8; 32] = fee_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Token Buy Lock Script
+	let script_args: Bytes = sudt_type_script_hash.to_vec().into();
+	let token_buy_lock_script = context.build_script(&out_point_token_buy_lock, script_args).expect("script");
+
+	// Prepare Order Cells
+	let mut order_cells = vec![];
+	let mut order = Order::new(buyer_lock_script_hash, 100u128);
+	order.bound_payment = true;
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build();
+	let data = Bytes::from(order.to_data(LEGACY_VERSION).expect("encode"));
+	let out_point = context.create_cell(output.clone(), data.clone());
+	order_cells.push(LiveCell { out_point, output, data });
+	let mut order = Order::new(buyer_lock_script_hash, 1u128);
+	order.price_denominator = Some(1_000_000_000u128);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build();
+	let data = Bytes::from(order.to_data(LEGACY_VERSION).expect("encode"));
+	let out_point = context.create_cell(output.clone(), data.clone());
+	order_cells.push(LiveCell { out_point, output, data });
+
+	// Prepare SUDT Cells
+	let mut sudt_cells = vec![];
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	let data = Bytes::from(SudtAmount::new(9_000u128).to_data());
+	let out_point = context.create_cell(output.clone(), data.clone());
+	sudt_cells.push(LiveCell { out_point, output, data });
+
+	// Build Transaction
+	let mut builder = FillBuilder::new(token_buy_lock_script.code_hash(), ScriptHashType::Data, token_buy_lock_dep, sudt_dep, seller_lock_script.clone())
+		.cell_dep(always_success_dep)
+		.lock_script(buyer_lock_script.clone())
+		.lock_script(fee_lock_script.clone())
+		.fee(100_000_u64);
+	for order_cell in order_cells
+	{
+		builder = builder.order(order_cell);
+	}
+	for sudt_cell in sudt_cells
+	{
+		builder = builder.sudt(sudt_cell);
+	}
+	let tx = builder.build().expect("build");
+
+	// Run
+	// The builder adds every cell dep, so the transaction is not completed by the context.
+	let _cycles = context.verify_tx(&tx, MAX_CYCLES).expect("pass verification");
+	// println!("consume cycles: {}", cycles);
+}
+
+#[test]
+fn test_sdk_fill_not_enough_tokens()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_buy_lock = context.deploy_cell(Loader::default().load_binary("token-buy-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_buy_lock_dep = CellDep::new_builder().out_point(out_point_token_buy_lock.clone()).build();
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	let fee_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![2u8; 1])).expect("script");
+	// let fee_lock_script_hash: [u8; 32] = fee_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Token Buy Lock Script
+	let script_args: Bytes = sudt_type_script_hash.to_vec().into();
+	let token_buy_lock_script = context.build_script(&out_point_token_buy_lock, script_args).expect("script");
+
+	// Prepare Order Cells
+	let mut order_cells = vec![];
+	let order = Order::new(buyer_lock_script_hash, 100u128);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build();
+	let data = Bytes::from(order.to_data(LEGACY_VERSION).expect("encode"));
+	let out_point = context.create_cell(output.clone(), data.clone());
+	order_cells.push(LiveCell { out_point, output, data });
+
+	// Prepare SUDT Cells
+	let mut sudt_cells = vec![];
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	let data = Bytes::from(SudtAmount::new(99u128).to_data());
+	let out_point = context.create_cell(output.clone(), data.clone());
+	sudt_cells.push(LiveCell { out_point, output, data });
+
+	// Build Transaction
+	let mut builder = FillBuilder::new(token_buy_lock_script.code_hash(), ScriptHashType::Data, token_buy_lock_dep, sudt_dep, seller_lock_script.clone())
+		.cell_dep(always_success_dep)
+		.lock_script(buyer_lock_script.clone())
+		.lock_script(fee_lock_script.clone())
+		.fee(100_000_u64);
+	for order_cell in order_cells
+	{
+		builder = builder.order(order_cell);
+	}
+	for sudt_cell in sudt_cells
+	{
+		builder = builder.sudt(sudt_cell);
+	}
+	assert_eq!(builder.build().unwrap_err(), Error::Amount);
+}
+
+#[test]
+fn test_sdk_fill_missing_lock()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_buy_lock = context.deploy_cell(Loader::default().load_binary("token-buy-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_buy_lock_dep = CellDep::new_builder().out_point(out_point_token_buy_lock.clone()).build();
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	let fee_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![2u8; 1])).expect("script");
+	// let fee_lock_script_hash: [u8; 32] = fee_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Token Buy Lock Script
+	let script_args: Bytes = sudt_type_script_hash.to_vec().into();
+	let token_buy_lock_script = context.build_script(&out_point_token_buy_lock, script_args).expect("script");
+
+	// Prepare Order Cells
+	let mut order_cells = vec![];
+	let mut order = Order::new(buyer_lock_script_hash, 100u128);
+	order.payout_lock_hash = Some([9u8; 32]);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build();
+	let data = Bytes::from(order.to_data(LEGACY_VERSION).expect("encode"));
+	let out_point = context.create_cell(output.clone(), data.clone());
+	order_cells.push(LiveCell { out_point, output, data });
+
+	// Prepare SUDT Cells
+	let mut sudt_cells = vec![];
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	let data = Bytes::from(SudtAmount::new(9_000u128).to_data());
+	let out_point = context.create_cell(output.clone(), data.clone());
+	sudt_cells.push(LiveCell { out_point, output, data });
+
+	// Build Transaction
+	let mut builder = FillBuilder::new(token_buy_lock_script.code_hash(), ScriptHashType::Data, token_buy_lock_dep, sudt_dep, seller_lock_script.clone())
+		.cell_dep(always_success_dep)
+		.lock_script(buyer_lock_script.clone())
+		.lock_script(fee_lock_script.clone())
+		.fee(100_000_u64);
+	for order_cell in order_cells
+	{
+		builder = builder.order(order_cell);
+	}
+	for sudt_cell in sudt_cells
+	{
+		builder = builder.sudt(sudt_cell);
+	}
+	assert_eq!(builder.build().unwrap_err(), Error::MissingLock([9u8; 32]));
+}
+
+#[test]
+fn test_sdk_fill_unsupported_order()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_buy_lock = context.deploy_cell(Loader::default().load_binary("token-buy-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_buy_lock_dep = CellDep::new_builder().out_point(out_point_token_buy_lock.clone()).build();
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	let fee_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![2u8; 1])).expect("script");
+	// let fee_lock_script_hash: [u8; 32] = fee_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Token Buy Lock Script
+	let script_args: Bytes = sudt_type_script_hash.to_vec().into();
+	let token_buy_lock_script = context.build_script(&out_point_token_buy_lock, script_args).expect("script");
+
+	// Prepare Order Cells
+	let mut order_cells = vec![];
+	let mut order = Order::new(buyer_lock_script_hash, 100u128);
+	order.auction = Some(Auction { end_amount: 50u128, metric: AuctionMetric::BlockNumber, start: 1_000u64, end: 2_000u64 });
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build();
+	let data = Bytes::from(order.to_data(LEGACY_VERSION).expect("encode"));
+	let out_point = context.create_cell(output.clone(), data.clone());
+	order_cells.push(LiveCell { out_point, output, data });
+
+	// Prepare SUDT Cells
+	let mut sudt_cells = vec![];
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	let data = Bytes::from(SudtAmount::new(9_000u128).to_data());
+	let out_point = context.create_cell(output.clone(), data.clone());
+	sudt_cells.push(LiveCell { out_point, output, data });
+
+	// Build Transaction
+	let mut builder = FillBuilder::new(token_buy_lock_script.code_hash(), ScriptHashType::Data, token_buy_lock_dep, sudt_dep, seller_lock_script.clone())
+		.cell_dep(always_success_dep)
+		.lock_script(buyer_lock_script.clone())
+		.lock_script(fee_lock_script.clone())
+		.fee(100_000_u64);
+	for order_cell in order_cells
+	{
+		builder = builder.order(order_cell);
+	}
+	for sudt_cell in sudt_cells
+	{
+		builder = builder.sudt(sudt_cell);
+	}
+	assert_eq!(builder.build().unwrap_err(), Error::UnsupportedOrder);
+}
+
+#[test]
+fn test_sdk_fill_wrong_lock()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_buy_lock = context.deploy_cell(Loader::default().load_binary("token-buy-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_buy_lock_dep = CellDep::new_builder().out_point(out_point_token_buy_lock.clone()).build();
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	let fee_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![2u8; 1])).expect("script");
+	// let fee_lock_script_hash: [u8; 32] = fee_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Token Buy Lock Script
+	let script_args: Bytes = sudt_type_script_hash.to_vec().into();
+	let token_buy_lock_script = context.build_script(&out_point_token_buy_lock, script_args.clone()).expect("script");
+
+	// Prepare Fake Order Lock Script
+	let fake_lock_script = context.build_script(&out_point_always_success, script_args).expect("script");
+
+	// Prepare Order Cells
+	let mut order_cells = vec![];
+	let order = Order::new(buyer_lock_script_hash, 100u128);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(fake_lock_script.clone()).build();
+	let data = Bytes::from(order.to_data(LEGACY_VERSION).expect("encode"));
+	let out_point = context.create_cell(output.clone(), data.clone());
+	order_cells.push(LiveCell { out_point, output, data });
+
+	// Prepare SUDT Cells
+	let mut sudt_cells = vec![];
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	let data = Bytes::from(SudtAmount::new(9_000u128).to_data());
+	let out_point = context.create_cell(output.clone(), data.clone());
+	sudt_cells.push(LiveCell { out_point, output, data });
+
+	// Build Transaction
+	let mut builder = FillBuilder::new(token_buy_lock_script.code_hash(), ScriptHashType::Data, token_buy_lock_dep, sudt_dep, seller_lock_script.clone())
+		.cell_dep(always_success_dep)
+		.lock_script(buyer_lock_script.clone())
+		.lock_script(fee_lock_script.clone())
+		.fee(100_000_u64);
+	for order_cell in order_cells
+	{
+		builder = builder.order(order_cell);
+	}
+	for sudt_cell in sudt_cells
+	{
+		builder = builder.sudt(sudt_cell);
+	}
+	assert_eq!(builder.build().unwrap_err(), Error::CodeHash);
+}
+
+#[test]
+fn test_sdk_create_and_fill()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_buy_lock = context.deploy_cell(Loader::default().load_binary("token-buy-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_buy_lock_dep = CellDep::new_builder().out_point(out_point_token_buy_lock.clone()).build();
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Token Buy Lock Script
+	// The context builds scripts with the data hash of the deployed binary.
+	let token_buy_lock_script = context.build_script(&out_point_token_buy_lock, Bytes::new()).expect("script");
+
+	// Prepare Owner Cells
+	let output = CellOutput::new_builder().capacity(1_000_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).build();
+	let data = Bytes::new();
+	let out_point = context.create_cell(output.clone(), data.clone());
+	let owner_cell = LiveCell { out_point, output, data };
+
+	// Build Creation Transaction
+	let lock_args = LockArgs { version: ORDER_VERSION, sudt_type_hash: sudt_type_script_hash, recovery_lock_hash: None };
+	let order = Order::new(buyer_lock_script_hash, 100u128);
+	let tx = CreateBuilder::new(token_buy_lock_script.code_hash(), ScriptHashType::Data, buyer_lock_script.clone())
+		.cell_dep(always_success_dep.clone())
+		.input(owner_cell)
+		.order(lock_args.clone(), order.clone(), 200_000_000_000_u64)
+		.fee(100_000_u64)
+		.build()
+		.expect("build");
+
+	// Run
+	let _cycles = context.verify_tx(&tx, MAX_CYCLES).expect("pass verification");
+	// println!("consume cycles: {}", cycles);
+
+	// Check Outputs
+	assert_eq!(tx.outputs().len(), 2);
+	let order_output = tx.outputs().get(0).expect("output");
+	let order_data = tx.outputs_data().get(0).expect("data").raw_data();
+	assert_eq!(LockArgs::from_args(&order_output.lock().args().raw_data()), Ok(lock_args));
+	assert_eq!(Order::from_data(&order_data, ORDER_VERSION), Ok(order));
+	let change_capacity: u64 = tx.outputs().get(1).expect("output").capacity().unpack();
+	assert_eq!(change_capacity, 1_000_000_000_000_u64 - 200_000_000_000_u64 - 100_000_u64);
+
+	// Prepare Order Cells
+	let out_point = context.create_cell(order_output.clone(), order_data.clone());
+	let order_cell = LiveCell { out_point, output: order_output, data: order_data };
+
+	// Prepare SUDT Cells
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	let data = Bytes::from(SudtAmount::new(9_000u128).to_data());
+	let out_point = context.create_cell(output.clone(), data.clone());
+	let sudt_cell = LiveCell { out_point, output, data };
+
+	// Build Fill Transaction
+	let tx = FillBuilder::new(token_buy_lock_script.code_hash(), ScriptHashType::Data, token_buy_lock_dep, sudt_dep, seller_lock_script.clone())
+		.cell_dep(always_success_dep)
+		.lock_script(buyer_lock_script.clone())
+		.order(order_cell)
+		.sudt(sudt_cell)
+		.fee(100_000_u64)
+		.build()
+		.expect("build");
+
+	// Run
+	let _cycles = context.verify_tx(&tx, MAX_CYCLES).expect("pass verification");
+	// println!("consume cycles: {}", cycles);
+}
+
+#[test]
+fn test_sdk_create_not_enough_capacity()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_token_buy_lock = context.deploy_cell(Loader::default().load_binary("token-buy-lock"));
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+
+	// Prepare Token Buy Lock Script
+	let token_buy_lock_script = context.build_script(&out_point_token_buy_lock, Bytes::new()).expect("script");
+
+	// Prepare Owner Cells
+	let output = CellOutput::new_builder().capacity(1_000_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).build();
+	let data = Bytes::new();
+	let out_point = context.create_cell(output.clone(), data.clone());
+	let owner_cell = LiveCell { out_point, output, data };
+
+	// Build Transaction
+	// The order cell occupies 121 CKBytes, so 100 CKBytes is not enough.
+	let lock_args = LockArgs { version: LEGACY_VERSION, sudt_type_hash: [5u8; 32], recovery_lock_hash: None };
+	let order = Order::new(buyer_lock_script_hash, 100u128);
+	let builder = CreateBuilder::new(token_buy_lock_script.code_hash(), ScriptHashType::Data, buyer_lock_script.clone())
+		.input(owner_cell)
+		.order(lock_args, order, 10_000_000_000_u64);
+	assert_eq!(builder.build().unwrap_err(), Error::Capacity);
+}
+
+#[test]
+fn test_sdk_create_invalid_terms()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_token_buy_lock = context.deploy_cell(Loader::default().load_binary("token-buy-lock"));
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+
+	// Prepare Token Buy Lock Script
+	let token_buy_lock_script = context.build_script(&out_point_token_buy_lock, Bytes::new()).expect("script");
+
+	// Prepare Owner Cells
+	let output = CellOutput::new_builder().capacity(1_000_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).build();
+	let data = Bytes::new();
+	let out_point = context.create_cell(output.clone(), data.clone());
+	let owner_cell = LiveCell { out_point, output, data };
+
+	// Build Transaction
+	let lock_args = LockArgs { version: LEGACY_VERSION, sudt_type_hash: [5u8; 32], recovery_lock_hash: None };
+	let mut order = Order::new(buyer_lock_script_hash, 100u128);
+	order.fee = Some(Fee { lock_hash: [3u8; 32], rate: 10_001u16 });
+	let builder = CreateBuilder::new(token_buy_lock_script.code_hash(), ScriptHashType::Data, buyer_lock_script.clone())
+		.input(owner_cell)
+		.order(lock_args, order, 200_000_000_000_u64);
+	assert_eq!(builder.build().unwrap_err(), Error::Encoding);
+}
+
+#[test]
+fn test_sdk_create_wrong_owner()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_token_buy_lock = context.deploy_cell(Loader::default().load_binary("token-buy-lock"));
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	let buyer_lock_script2 = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	let buyer_lock_script2_hash: [u8; 32] = buyer_lock_script2.calc_script_hash().unpack();
+
+	// Prepare Token Buy Lock Script
+	let token_buy_lock_script = context.build_script(&out_point_token_buy_lock, Bytes::new()).expect("script");
+
+	// Prepare Owner Cells
+	let output = CellOutput::new_builder().capacity(1_000_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).build();
+	let data = Bytes::new();
+	let out_point = context.create_cell(output.clone(), data.clone());
+	let owner_cell = LiveCell { out_point, output, data };
+
+	// Build Transaction
+	let lock_args = LockArgs { version: LEGACY_VERSION, sudt_type_hash: [5u8; 32], recovery_lock_hash: None };
+	let order = Order::new(buyer_lock_script2_hash, 100u128);
+	let builder = CreateBuilder::new(token_buy_lock_script.code_hash(), ScriptHashType::Data, buyer_lock_script.clone())
+		.input(owner_cell)
+		.order(lock_args, order, 200_000_000_000_u64);
+	assert_eq!(builder.build().unwrap_err(), Error::OwnerLockHash);
+}
+
+#[test]
+fn test_sdk_create_owner_payout()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_token_buy_lock = context.deploy_cell(Loader::default().load_binary("token-buy-lock"));
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+
+	// Prepare Token Buy Lock Script
+	let token_buy_lock_script = context.build_script(&out_point_token_buy_lock, Bytes::new()).expect("script");
+
+	// Prepare Owner Cells
+	let output = CellOutput::new_builder().capacity(1_000_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).build();
+	let data = Bytes::new();
+	let out_point = context.create_cell(output.clone(), data.clone());
+	let owner_cell = LiveCell { out_point, output, data };
+
+	// Build Transaction
+	// An order which pays the owner lock explicitly can only be withdrawn with a signature.
+	let lock_args = LockArgs { version: ORDER_VERSION, sudt_type_hash: [5u8; 32], recovery_lock_hash: None };
+	let mut order = Order::new(buyer_lock_script_hash, 100u128);
+	order.payout_lock_hash = Some(buyer_lock_script_hash);
+	let builder = CreateBuilder::new(token_buy_lock_script.code_hash(), ScriptHashType::Data, buyer_lock_script.clone())
+		.input(owner_cell.clone())
+		.order(lock_args.clone(), order.clone(), 200_000_000_000_u64);
+	assert_eq!(builder.build().unwrap_err(), Error::OwnerLockHash);
+	order.owner_pubkey_hash = Some([7u8; 20]);
+	let builder = CreateBuilder::new(token_buy_lock_script.code_hash(), ScriptHashType::Data, buyer_lock_script.clone())
+		.input(owner_cell)
+		.order(lock_args, order, 200_000_000_000_u64);
+	assert!(builder.build().is_ok());
+}
+
+#[test]
+fn test_sdk_cancel_by_multiple_owners()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_buy_lock = context.deploy_cell(Loader::default().load_binary("token-buy-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let token_buy_lock_dep = CellDep::new_builder().out_point(out_point_token_buy_lock.clone()).build();
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let buyer_lock_script2 = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	let buyer_lock_script2_hash: [u8; 32] = buyer_lock_script2.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Token Buy Lock Script
+	let script_args: Bytes = sudt_type_script_hash.to_vec().into();
+	let token_buy_lock_script = context.build_script(&out_point_token_buy_lock, script_args).expect("script");
+
+	// Prepare Order Cells
+	let mut order_cells = vec![];
+	for owner_lock_hash in &[buyer_lock_script_hash, buyer_lock_script2_hash]
+	{
+		let order = Order::new(*owner_lock_hash, 100u128);
+		let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build();
+		let data = Bytes::from(order.to_data(LEGACY_VERSION).expect("encode"));
+		let out_point = context.create_cell(output.clone(), data.clone());
+		order_cells.push(LiveCell { out_point, output, data });
+	}
+
+	// Prepare Owner Cells
+	let mut owner_cells = vec![];
+	for owner_lock_script in &[buyer_lock_script.clone(), buyer_lock_script2.clone()]
+	{
+		let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(owner_lock_script.clone()).build();
+		let data = Bytes::new();
+		let out_point = context.create_cell(output.clone(), data.clone());
+		owner_cells.push(LiveCell { out_point, output, data });
+	}
+
+	// Build Transaction
+	let mut builder = CancelBuilder::new(token_buy_lock_script.code_hash(), ScriptHashType::Data, token_buy_lock_dep)
+		.cell_dep(always_success_dep)
+		.fee(100_000_u64);
+	for order_cell in order_cells
+	{
+		builder = builder.order(order_cell);
+	}
+	for owner_cell in owner_cells
+	{
+		builder = builder.owner_cell(owner_cell);
+	}
+	let tx = builder.build().expect("build");
+
+	// Run
+	let _cycles = context.verify_tx(&tx, MAX_CYCLES).expect("pass verification");
+	// println!("consume cycles: {}", cycles);
+
+	// Check Outputs
+	let capacities: Vec<u64> = tx.outputs().into_iter().map(|output| output.capacity().unpack()).collect();
+	assert_eq!(capacities, vec![200_000_000_000_u64 - 100_000_u64, 200_000_000_000_u64]);
+}
+
+#[test]
+fn test_sdk_cancel_missing_owner()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_buy_lock = context.deploy_cell(Loader::default().load_binary("token-buy-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let token_buy_lock_dep = CellDep::new_builder().out_point(out_point_token_buy_lock.clone()).build();
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let buyer_lock_script2 = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	let buyer_lock_script2_hash: [u8; 32] = buyer_lock_script2.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Token Buy Lock Script
+	let script_args: Bytes = sudt_type_script_hash.to_vec().into();
+	let token_buy_lock_script = context.build_script(&out_point_token_buy_lock, script_args).expect("script");
+
+	// Prepare Order Cells
+	let mut order_cells = vec![];
+	for owner_lock_hash in &[buyer_lock_script_hash, buyer_lock_script2_hash]
+	{
+		let order = Order::new(*owner_lock_hash, 100u128);
+		let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build();
+		let data = Bytes::from(order.to_data(LEGACY_VERSION).expect("encode"));
+		let out_point = context.create_cell(output.clone(), data.clone());
+		order_cells.push(LiveCell { out_point, output, data });
+	}
+
+	// Prepare Owner Cells
+	// Only the first owner provides a cell.
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).build();
+	let data = Bytes::new();
+	let out_point = context.create_cell(output.clone(), data.clone());
+	let owner_cell = LiveCell { out_point, output, data };
+
+	// Build Transaction
+	let mut builder = CancelBuilder::new(token_buy_lock_script.code_hash(), ScriptHashType::Data, token_buy_lock_dep)
+		.cell_dep(always_success_dep)
+		.owner_cell(owner_cell)
+		.fee(100_000_u64);
+	for order_cell in order_cells
+	{
+		builder = builder.order(order_cell);
+	}
+	assert_eq!(builder.build().unwrap_err(), Error::MissingLock(buyer_lock_script2_hash));
+}
+
+#[test]
+fn test_sdk_cancel_owner_cell_with_type()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_buy_lock = context.deploy_cell(Loader::default().load_binary("token-buy-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let token_buy_lock_dep = CellDep::new_builder().out_point(out_point_token_buy_lock.clone()).build();
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Token Buy Lock Script
+	let script_args: Bytes = sudt_type_script_hash.to_vec().into();
+	let token_buy_lock_script = context.build_script(&out_point_token_buy_lock, script_args).expect("script");
+
+	// Prepare Order Cells
+	let order = Order::new(buyer_lock_script_hash, 100u128);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build();
+	let data = Bytes::from(order.to_data(LEGACY_VERSION).expect("encode"));
+	let out_point = context.create_cell(output.clone(), data.clone());
+	let order_cell = LiveCell { out_point, output, data };
+
+	// Prepare Owner Cells
+	// The owner cell holds tokens, which would be burned if only its capacity was returned.
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	let data = Bytes::from(SudtAmount::new(1_000u128).to_data());
+	let out_point = context.create_cell(output.clone(), data.clone());
+	let owner_cell = LiveCell { out_point, output, data };
+
+	// Build Transaction
+	let builder = CancelBuilder::new(token_buy_lock_script.code_hash(), ScriptHashType::Data, token_buy_lock_dep)
+		.cell_dep(always_success_dep)
+		.order(order_cell)
+		.owner_cell(owner_cell)
+		.fee(100_000_u64);
+	assert_eq!(builder.build().unwrap_err(), Error::OwnerCell);
+}
+
+#[test]
+fn test_sdk_cancel_wrong_lock()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_buy_lock = context.deploy_cell(Loader::default().load_binary("token-buy-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let token_buy_lock_dep = CellDep::new_builder().out_point(out_point_token_buy_lock.clone()).build();
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Token Buy Lock Script
+	let script_args: Bytes = sudt_type_script_hash.to_vec().into();
+	let token_buy_lock_script = context.build_script(&out_point_token_buy_lock, script_args.clone()).expect("script");
+
+	// Prepare Fake Order Lock Script
+	let fake_lock_script = context.build_script(&out_point_always_success, script_args).expect("script");
+
+	// Prepare Order Cells
+	let order = Order::new(buyer_lock_script_hash, 100u128);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(fake_lock_script.clone()).build();
+	let data = Bytes::from(order.to_data(LEGACY_VERSION).expect("encode"));
+	let out_point = context.create_cell(output.clone(), data.clone());
+	let order_cell = LiveCell { out_point, output, data };
+
+	// Prepare Owner Cells
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).build();
+	let data = Bytes::new();
+	let out_point = context.create_cell(output.clone(), data.clone());
+	let owner_cell = LiveCell { out_point, output, data };
+
+	// Build Transaction
+	let builder = CancelBuilder::new(token_buy_lock_script.code_hash(), ScriptHashType::Data, token_buy_lock_dep)
+		.cell_dep(always_success_dep)
+		.order(order_cell)
+		.owner_cell(owner_cell)
+		.fee(100_000_u64);
+	assert_eq!(builder.build().unwrap_err(), Error::CodeHash);
+}
+
+#[test]
+fn test_sdk_memory_provider_fill()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_buy_lock = context.deploy_cell(Loader::default().load_binary("token-buy-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_buy_lock_dep = CellDep::new_builder().out_point(out_point_token_buy_lock.clone()).build();
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Token Buy Lock Script
+	let script_args: Bytes = sudt_type_script_hash.to_vec().into();
+	let token_buy_lock_script = context.build_script(&out_point_token_buy_lock, script_args).expect("script");
+	let token_buy_lock_code_hash: [u8; 32] = token_buy_lock_script.code_hash().unpack();
+
+	// Prepare Cells
+	for _ in 0..2
+	{
+		let order = Order::new(buyer_lock_script_hash, 100u128);
+		let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build();
+		context.create_cell(output, Bytes::from(order.to_data(LEGACY_VERSION).expect("encode")));
+	}
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	context.create_cell(output, Bytes::from(SudtAmount::new(9_000u128).to_data()));
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	context.create_cell(output, Bytes::from(SudtAmount::new(500u128).to_data()));
+
+	// Collect Cells
+	let mut provider = MemoryCellProvider::from_context(&context);
+	let order_query = CellQuery::by_lock_code_hash(token_buy_lock_code_hash, ScriptHashType::Data).lock_args_prefix(&sudt_type_script_hash);
+	let order_cells = provider.collect_cells(&order_query).expect("collect");
+	assert_eq!(order_cells.len(), 2);
+	let sudt_query = CellQuery::by_type_hash(sudt_type_script_hash).lock_hash(seller_lock_script_hash);
+	let sudt_cells = provider.collect_cells(&sudt_query).expect("collect");
+	assert_eq!(sudt_cells.len(), 1);
+	assert_eq!(provider.collect_cells(&CellQuery::by_type_hash(sudt_type_script_hash)).expect("collect").len(), 2);
+	assert_eq!(provider.collect_cells(&CellQuery::by_lock_code_hash(token_buy_lock_code_hash, ScriptHashType::Data).lock_args_prefix(&[9u8; 32])).expect("collect").len(), 0);
+	assert_eq!(provider.collect_cells(&CellQuery::by_lock_code_hash(token_buy_lock_code_hash, ScriptHashType::Type)).expect("collect").len(), 0);
+
+	// Build Transaction
+	let mut builder = FillBuilder::new(token_buy_lock_script.code_hash(), ScriptHashType::Data, token_buy_lock_dep, sudt_dep, seller_lock_script.clone())
+		.cell_dep(always_success_dep)
+		.lock_script(buyer_lock_script.clone())
+		.fee(100_000_u64);
+	for order_cell in order_cells
+	{
+		builder = builder.order(order_cell);
+	}
+	for sudt_cell in sudt_cells
+	{
+		builder = builder.sudt(sudt_cell);
+	}
+	let tx = builder.build().expect("build");
+
+	// Run
+	let _cycles = context.verify_tx(&tx, MAX_CYCLES).expect("pass verification");
+	// println!("consume cycles: {}", cycles);
+
+	// Apply Transaction
+	// The orders are consumed, and the payment and change are now live.
+	provider.apply_transaction(&tx);
+	assert_eq!(provider.collect_cells(&order_query).expect("collect").len(), 0);
+	let sudt_cells = provider.collect_cells(&CellQuery::by_type_hash(sudt_type_script_hash).lock_hash(buyer_lock_script_hash)).expect("collect");
+	let amounts: Vec<u128> = sudt_cells.iter().map(|cell| SudtAmount::from_data(&cell.data).expect("decode").amount).collect();
+	assert_eq!(amounts.iter().sum::<u128>(), 700u128);
+	let sudt_cells = provider.collect_cells(&sudt_query).expect("collect");
+	assert_eq!(sudt_cells.len(), 1);
+	assert_eq!(SudtAmount::from_data(&sudt_cells[0].data), Ok(SudtAmount::new(8_800u128)));
+
+	// Cells are ordered by the numeric output index, not by its little endian bytes.
+	let mut provider = MemoryCellProvider::new();
+	for index in &[256u32, 1u32]
+	{
+		let out_point = OutPoint::new(tx.hash(), *index);
+		provider.insert(LiveCell { out_point, output: sudt_cells[0].output.clone(), data: sudt_cells[0].data.clone() });
+	}
+	let indexes: Vec<u32> = provider.cells().map(|cell| cell.out_point.index().unpack()).collect();
+	assert_eq!(indexes, vec![1u32, 256u32]);
+}
+
+#[test]
+fn test_sdk_file_provider_round_trip()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_buy_lock = context.deploy_cell(Loader::default().load_binary("token-buy-lock"));
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Token Buy Lock Script
+	let script_args: Bytes = sudt_type_script_hash.to_vec().into();
+	let token_buy_lock_script = context.build_script(&out_point_token_buy_lock, script_args).expect("script");
+	let token_buy_lock_code_hash: [u8; 32] = token_buy_lock_script.code_hash().unpack();
+
+	// Prepare Cells
+	let order = Order::new(buyer_lock_script_hash, 100u128);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build();
+	context.create_cell(output, Bytes::from(order.to_data(LEGACY_VERSION).expect("encode")));
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	context.create_cell(output, Bytes::from(SudtAmount::new(500u128).to_data()));
+
+	// Write Snapshot
+	let memory_provider = MemoryCellProvider::from_context(&context);
+	let path = std::env::temp_dir().join(format!("token-buy-lock-snapshot-{}.bin", std::process::id()));
+	let file_provider = FileCellProvider::create(&path, memory_provider.cells()).expect("create");
+
+	// Compare Cells
+	for query in &[CellQuery::default(), CellQuery::by_lock_code_hash(token_buy_lock_code_hash, ScriptHashType::Data), CellQuery::by_type_hash(sudt_type_script_hash)]
+	{
+		let expected = memory_provider.collect_cells(query).expect("collect");
+		let cells = file_provider.collect_cells(query).expect("collect");
+		assert_eq!(cells.len(), expected.len());
+		for (cell, expected) in cells.iter().zip(expected.iter())
+		{
+			assert_eq!(cell.out_point.as_slice(), expected.out_point.as_slice());
+			assert_eq!(cell.output.as_slice(), expected.output.as_slice());
+			assert_eq!(cell.data, expected.data);
+		}
+	}
+	let order_cells = file_provider.collect_cells(&CellQuery::by_lock_code_hash(token_buy_lock_code_hash, ScriptHashType::Data)).expect("collect");
+	assert_eq!(order_cells.len(), 1);
+	assert_eq!(Order::from_data(&order_cells[0].data, LEGACY_VERSION), Ok(order));
+
+	// Truncate Snapshot
+	let snapshot = std::fs::read(&path).expect("read");
+	std::fs::write(&path, &snapshot[0..snapshot.len() - 1]).expect("write");
+	assert_eq!(FileCellProvider::open(&path).unwrap_err(), Error::DataLength);
+	std::fs::remove_file(&path).expect("remove");
+	assert_eq!(FileCellProvider::open(&path).unwrap_err(), Error::Io(std::io::ErrorKind::NotFound));
+}
+
+#[test]
+fn test_sdk_order_book()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_buy_lock = context.deploy_cell(Loader::default().load_binary("token-buy-lock"));
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare SUDT Type Scripts
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+	let sudt_type_script2 = context.build_script(&out_point_sudt, Bytes::from(vec![4u8; 32])).expect("script");
+	let sudt_type_script2_hash: [u8; 32] = sudt_type_script2.calc_script_hash().unpack();
+
+	// Prepare Token Buy Lock Scripts
+	let token_buy_lock_script = context.build_script(&out_point_token_buy_lock, sudt_type_script_hash.to_vec().into()).expect("script");
+	let token_buy_lock_script2 = context.build_script(&out_point_token_buy_lock, sudt_type_script2_hash.to_vec().into()).expect("script");
+	let token_buy_lock_code_hash: [u8; 32] = token_buy_lock_script.code_hash().unpack();
+
+	// Prepare Order Cells
+	// 1,000 CKBytes for 100 tokens, 10 CKBytes per token.
+	let order = Order::new(buyer_lock_script_hash, 100u128);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build();
+	context.create_cell(output, Bytes::from(order.to_data(LEGACY_VERSION).expect("encode")));
+	// 1,000 CKBytes for 50 tokens, 20 CKBytes per token.
+	let order = Order::new(buyer_lock_script_hash, 50u128);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build();
+	context.create_cell(output, Bytes::from(order.to_data(LEGACY_VERSION).expect("encode")));
+	// 2,000 CKBytes at 1 token per 2,000,000,000 shannons, 20 CKBytes per token.
+	let mut order = Order::new(buyer_lock_script_hash, 1u128);
+	order.price_denominator = Some(2_000_000_000u128);
+	let output = CellOutput::new_builder().capacity(200_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build();
+	context.create_cell(output, Bytes::from(order.to_data(LEGACY_VERSION).expect("encode")));
+	// 1,000 CKBytes for 1,000 tokens of another SUDT.
+	let order = Order::new(buyer_lock_script_hash, 1_000u128);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script2.clone()).build();
+	context.create_cell(output, Bytes::from(order.to_data(LEGACY_VERSION).expect("encode")));
+	// Priced by an oracle.
+	let mut order = Order::new(buyer_lock_script_hash, 1u128);
+	order.oracle = Some(Oracle { type_hash: [8u8; 32], spread: 0u16 });
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build();
+	context.create_cell(output, Bytes::from(order.to_data(LEGACY_VERSION).expect("encode")));
+	// Needs a bundled token.
+	let mut order = Order::new(buyer_lock_script_hash, 1u128);
+	order.bundle = vec![TokenAmount { type_hash: sudt_type_script2_hash, amount: 10u128 }];
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build();
+	context.create_cell(output, Bytes::from(order.to_data(LEGACY_VERSION).expect("encode")));
+	// Accepts an alternative token.
+	let mut order = Order::new(buyer_lock_script_hash, 1u128);
+	order.alternatives = vec![TokenAmount { type_hash: sudt_type_script2_hash, amount: 10u128 }];
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build();
+	context.create_cell(output, Bytes::from(order.to_data(LEGACY_VERSION).expect("encode")));
+	// Needs an allowlist proof.
+	let mut order = Order::new(buyer_lock_script_hash, 1u128);
+	order.allowlist_root = Some([9u8; 32]);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build();
+	context.create_cell(output, Bytes::from(order.to_data(LEGACY_VERSION).expect("encode")));
+	// Data too short to hold a buy amount.
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build();
+	context.create_cell(output, Bytes::from(buyer_lock_script_hash.to_vec()));
+
+	// Load Order Book
+	let provider = MemoryCellProvider::from_context(&context);
+	let book = OrderBook::load(&provider, token_buy_lock_code_hash, ScriptHashType::Data).expect("load");
+	assert_eq!(book.sudt_type_hashes().count(), 2);
+	assert_eq!(book.unpriced().len(), 4);
+	assert_eq!(book.invalid().len(), 1);
+	assert_eq!(book.invalid()[0].error, Error::DataLength);
+
+	// Check Bids
+	let bids = book.bids(&sudt_type_script_hash);
+	assert_eq!(bids.len(), 3);
+	assert_eq!(bids.iter().map(|bid| bid.amount).collect::<Vec<u128>>().iter().sum::<u128>(), 250u128);
+	assert_eq!(bids[0].price, Price::new(2_000_000_000u128, 1u128));
+	assert_eq!(bids[1].price, Price::new(2_000_000_000u128, 1u128));
+	assert_eq!(bids[2].price, Price::new(1_000_000_000u128, 1u128));
+	assert_eq!(book.best_bid(&sudt_type_script_hash).expect("bid").price.as_f64(), 2_000_000_000f64);
+	assert_eq!(book.best_bid(&sudt_type_script2_hash).expect("bid").price, Price::new(100_000_000u128, 1u128));
+	assert!(book.best_bid(&[9u8; 32]).is_none());
+
+	// Check Depth
+	let depth = book.depth(&sudt_type_script_hash, &Price::new(1_500_000_000u128, 1u128)).expect("depth");
+	assert_eq!(depth, Depth { orders: 2, capacity: 300_000_000_000_u64, amount: 150u128 });
+	let depth = book.depth(&sudt_type_script_hash, &Price::new(1_000_000_000u128, 1u128)).expect("depth");
+	assert_eq!(depth, Depth { orders: 3, capacity: 400_000_000_000_u64, amount: 250u128 });
+	let levels = book.levels(&sudt_type_script_hash).expect("levels");
+	assert_eq!(levels, vec![
+		(Price::new(2_000_000_000u128, 1u128), Depth { orders: 2, capacity: 300_000_000_000_u64, amount: 150u128 }),
+		(Price::new(1_000_000_000u128, 1u128), Depth { orders: 1, capacity: 100_000_000_000_u64, amount: 100u128 }),
+	]);
+}
+
+#[test]
+fn test_sdk_order_book_price_ordering()
+{
+	// Prices are compared exactly, even when the cross products need more than 128 bits.
+	assert_eq!(Price::new(u128::MAX, u128::MAX), Price::new(1u128, 1u128));
+	assert!(Price::new(u128::MAX, u128::MAX - 1) > Price::new(1u128, 1u128));
+	assert!(Price::new(u128::MAX - 1, u128::MAX) < Price::new(1u128, 1u128));
+	assert!(Price::new(u64::MAX as u128, 3u128) > Price::new(u64::MAX as u128 - 1, 3u128));
+	assert!(Price::new(1u128, 0u128) > Price::new(u128::MAX, 1u128));
+
+	// Prices for zero tokens are ordered by capacity, so a zero price is not equal to every other price.
+	assert!(Price::new(2u128, 0u128) > Price::new(1u128, 0u128));
+	assert!(Price::new(0u128, 0u128) > Price::new(u128::MAX, 1u128));
+	assert!(Price::new(0u128, 0u128) < Price::new(1u128, 0u128));
+	assert_eq!(Price::new(0u128, 0u128), Price::new(0u128, 0u128));
+}
@@ -1,7 +1,9 @@
 use super::*;
 use ckb_testtool::{builtin::ALWAYS_SUCCESS, context::Context};
+use ckb_tool::ckb_crypto::secp::Privkey;
+use ckb_tool::ckb_hash::blake2b_256;
 use ckb_tool::{ckb_error::assert_error_eq, ckb_script::ScriptError};
-use ckb_tool::ckb_types::{bytes::Bytes, packed::*, prelude::*};
+use ckb_tool::ckb_types::{bytes::Bytes, packed::*, prelude::*, H256};
 use ckb_tool::ckb_types::core::{TransactionBuilder};
 
 // Constants
@@ -11,6 +13,8 @@ const MAX_CYCLES: u64 = 100_000_000;
 const ERROR_SUDT_ENCODING: i8 = 4;
 const ERROR_SUDT_AMOUNT: i8 = 5;
 const ERROR_SUDT_ARGS_LENGTH: i8 = 6;
+const ERROR_SUDT_OVERFLOW: i8 = 7;
+const ERROR_SUDT_SIGNATURE: i8 = 8;
 
 #[test]
 fn test_sudt_burn()
@@ -312,6 +316,189 @@ fn test_sudt_create_no_owner()
 	assert_error_eq!(err, ScriptError::ValidationFailure(ERROR_SUDT_AMOUNT).output_type_script(0));
 }
 
+#[test]
+fn test_sudt_create_signature()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let (secp256k1_lib_dep, secp256k1_data_dep) = deploy_secp256k1(&mut context);
+
+	// Prepare Scripts
+	let lock_script = context.build_script(&out_point_always_success, Default::default()).expect("script");
+	let lock_script_hash_zero = [0u8; 32];
+	let owner_privkey = Privkey::from_slice(&[7u8; 32]);
+	let owner_pubkey = owner_privkey.pubkey().expect("pubkey");
+	let owner_pubkey_hash = blake2b_256(owner_pubkey.serialize())[0..20].to_vec();
+	let script_args: Bytes = [lock_script_hash_zero.to_vec(), owner_pubkey_hash].concat().into();
+	let type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let data = vec![];
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(10_000_000_000_u64.pack()).lock(lock_script.clone()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(10_000_000_000_u64.pack()).lock(lock_script.clone()).type_(Some(type_script.clone()).pack()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	let data = 9_000u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.cell_dep(secp256k1_lib_dep)
+		.cell_dep(secp256k1_data_dep)
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Sign Transaction
+	let tx_hash: [u8; 32] = tx.hash().unpack();
+	let signature = owner_privkey.sign_recoverable(&H256::from(tx_hash)).expect("sign");
+	let witness = WitnessArgs::new_builder().output_type(Some(Bytes::from(signature.serialize())).pack()).build();
+	let tx = tx.as_advanced_builder().witness(witness.as_bytes().pack()).build();
+
+	// Run
+	let _cycles = context.verify_tx(&tx, MAX_CYCLES).expect("pass verification");
+	// println!("consume cycles: {}", cycles);
+}
+
+#[test]
+fn test_sudt_create_signature_wrong_key()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let (secp256k1_lib_dep, secp256k1_data_dep) = deploy_secp256k1(&mut context);
+
+	// Prepare Scripts
+	let lock_script = context.build_script(&out_point_always_success, Default::default()).expect("script");
+	let lock_script_hash_zero = [0u8; 32];
+	let owner_privkey = Privkey::from_slice(&[7u8; 32]);
+	let owner_pubkey = owner_privkey.pubkey().expect("pubkey");
+	let owner_pubkey_hash = blake2b_256(owner_pubkey.serialize())[0..20].to_vec();
+	let script_args: Bytes = [lock_script_hash_zero.to_vec(), owner_pubkey_hash].concat().into();
+	let type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let data = vec![];
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(10_000_000_000_u64.pack()).lock(lock_script.clone()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(10_000_000_000_u64.pack()).lock(lock_script.clone()).type_(Some(type_script.clone()).pack()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	let data = 9_000u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.cell_dep(secp256k1_lib_dep)
+		.cell_dep(secp256k1_data_dep)
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Sign Transaction
+	let tx_hash: [u8; 32] = tx.hash().unpack();
+	let signature = Privkey::from_slice(&[8u8; 32]).sign_recoverable(&H256::from(tx_hash)).expect("sign");
+	let witness = WitnessArgs::new_builder().output_type(Some(Bytes::from(signature.serialize())).pack()).build();
+	let tx = tx.as_advanced_builder().witness(witness.as_bytes().pack()).build();
+
+	// Run
+	let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+	assert_error_eq!(err, ScriptError::ValidationFailure(ERROR_SUDT_SIGNATURE).output_type_script(0));
+}
+
+#[test]
+fn test_sudt_create_signature_missing()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+
+	// Prepare Scripts
+	let lock_script = context.build_script(&out_point_always_success, Default::default()).expect("script");
+	let lock_script_hash_zero = [0u8; 32];
+	let owner_privkey = Privkey::from_slice(&[7u8; 32]);
+	let owner_pubkey = owner_privkey.pubkey().expect("pubkey");
+	let owner_pubkey_hash = blake2b_256(owner_pubkey.serialize())[0..20].to_vec();
+	let script_args: Bytes = [lock_script_hash_zero.to_vec(), owner_pubkey_hash].concat().into();
+	let type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let data = vec![];
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(10_000_000_000_u64.pack()).lock(lock_script.clone()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(10_000_000_000_u64.pack()).lock(lock_script.clone()).type_(Some(type_script.clone()).pack()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	let data = 9_000u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Run
+	let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+	assert_error_eq!(err, ScriptError::ValidationFailure(ERROR_SUDT_AMOUNT).output_type_script(0));
+}
+
 #[test]
 fn test_sudt_create_zero_token_cell()
 {
@@ -803,6 +990,132 @@ fn test_sudt_transfer_multiple()
 	// println!("consume cycles: {}", cycles);
 }
 
+#[test]
+fn test_sudt_transfer_input_overflow()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+
+	// Prepare Scripts
+	let lock_script = context.build_script(&out_point_always_success, Default::default()).expect("script");
+	let lock_script_hash_zero = [0u8; 32];
+	let script_args: Bytes = lock_script_hash_zero.to_vec().into();
+	let type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let mut data = vec!();
+	data.append(&mut u128::MAX.to_le_bytes().to_vec());
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(10_000_000_000_u64.pack()).lock(lock_script.clone()).type_(Some(type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let mut data = vec!();
+	data.append(&mut 1u128.to_le_bytes().to_vec());
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(10_000_000_000_u64.pack()).lock(lock_script.clone()).type_(Some(type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(10_000_000_000_u64.pack()).lock(lock_script.clone()).type_(Some(type_script.clone()).pack()).build();
+	outputs.push(output.clone());
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	let mut data = vec!();
+	data.append(&mut u128::MAX.to_le_bytes().to_vec());
+	outputs_data.push(Bytes::from(data));
+	let mut data = vec!();
+	data.append(&mut 0u128.to_le_bytes().to_vec());
+	outputs_data.push(Bytes::from(data));
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Run
+	let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+	assert_error_eq!(err, ScriptError::ValidationFailure(ERROR_SUDT_OVERFLOW).input_type_script(0));
+}
+
+#[test]
+fn test_sudt_transfer_output_overflow()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+
+	// Prepare Scripts
+	let lock_script = context.build_script(&out_point_always_success, Default::default()).expect("script");
+	let lock_script_hash_zero = [0u8; 32];
+	let script_args: Bytes = lock_script_hash_zero.to_vec().into();
+	let type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let mut data = vec!();
+	data.append(&mut u128::MAX.to_le_bytes().to_vec());
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(10_000_000_000_u64.pack()).lock(lock_script.clone()).type_(Some(type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let mut data = vec!();
+	data.append(&mut 0u128.to_le_bytes().to_vec());
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(10_000_000_000_u64.pack()).lock(lock_script.clone()).type_(Some(type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(10_000_000_000_u64.pack()).lock(lock_script.clone()).type_(Some(type_script.clone()).pack()).build();
+	outputs.push(output.clone());
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	let mut data = vec!();
+	data.append(&mut u128::MAX.to_le_bytes().to_vec());
+	outputs_data.push(Bytes::from(data));
+	let mut data = vec!();
+	data.append(&mut 1u128.to_le_bytes().to_vec());
+	outputs_data.push(Bytes::from(data));
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Run
+	let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+	assert_error_eq!(err, ScriptError::ValidationFailure(ERROR_SUDT_OVERFLOW).input_type_script(0));
+}
+
 #[test]
 fn test_sudt_transfer_invalid_input_data()
 {
@@ -1,16 +1,46 @@
 use super::*;
 use ckb_testtool::{builtin::ALWAYS_SUCCESS, context::Context};
+use ckb_tool::ckb_crypto::secp::Privkey;
+use ckb_tool::ckb_hash::blake2b_256;
 use ckb_tool::{ckb_error::assert_error_eq, ckb_script::ScriptError};
-use ckb_tool::ckb_types::{bytes::Bytes, packed::*, prelude::*};
-use ckb_tool::ckb_types::core::{TransactionBuilder};
+use ckb_tool::ckb_types::{bytes::Bytes, packed::*, prelude::*, H256};
+use ckb_tool::ckb_types::core::{HeaderBuilder, TransactionBuilder};
+use token_buy_lock_types::{encode_versioned, encode_with_version, token_buy_lock as order_types, ORDER_VERSION, ORDER_VERSION_V1, ORDER_VERSION_V2};
 
 // Constants
 const MAX_CYCLES: u64 = 100_000_000;
 
 // Error Codes
+const ERROR_ENCODING: i8 = 4;
 const ERROR_AMOUNT: i8 = 5;
 const ERROR_ARGS_LEN: i8 = 6;
 const ERROR_DATA_LEN: i8 = 7;
+const ERROR_REMAINDER: i8 = 8;
+const ERROR_ALLOWLIST: i8 = 9;
+const ERROR_ORACLE: i8 = 10;
+const ERROR_PAYOUT_CELLS: i8 = 11;
+const ERROR_OVERFLOW: i8 = 12;
+const ERROR_SIGNATURE: i8 = 13;
+
+// Payment Binding
+const BINDING_MARKER: &[u8] = b"TBLB";
+
+// Order Flags
+const FLAGS_MARKER: &[u8] = b"TBLF";
+const FLAG_PARTIAL_FILL: u16 = 1 << 0;
+const FLAG_PRICE: u16 = 1 << 1;
+const FLAG_BOUND_PAYMENT: u16 = 1 << 2;
+const FLAG_PAYOUT_LOCK: u16 = 1 << 3;
+const FLAG_FEE: u16 = 1 << 4;
+const FLAG_AUCTION: u16 = 1 << 5;
+const FLAG_ALLOWLIST: u16 = 1 << 6;
+const FLAG_ORACLE: u16 = 1 << 7;
+const FLAG_BUNDLE: u16 = 1 << 8;
+const FLAG_ALTERNATIVES: u16 = 1 << 9;
+
+// Auction Metrics
+const AUCTION_METRIC_BLOCK_NUMBER: u8 = 0;
+const AUCTION_METRIC_TIMESTAMP: u8 = 1;
 
 #[test]
 fn test_buy_exact()
@@ -476,7 +506,7 @@ fn test_buy_invalid_data()
 }
 
 #[test]
-fn test_buy_multiple_buy_cells_same_owner()
+fn test_buy_legacy_trailing_byte()
 {
 	// Create Context
 	let mut context = Context::default();
@@ -513,9 +543,9 @@ fn test_buy_multiple_buy_cells_same_owner()
 	let mut data = vec!();
 	data.extend(buyer_lock_script_hash.to_vec());
 	data.extend(100u128.to_le_bytes().to_vec());
+	data.push(1u8); // Ignored without the flags marker, the same as the original script.
 	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build(), Bytes::from(data));
 	let input = CellInput::new_builder().previous_output(input_out_point).build();
-	inputs.push(input.clone()); // Add two Token Buy Lock cells.
 	inputs.push(input);
 	let data = 9_000u128.to_le_bytes().to_vec();
 	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build(), Bytes::from(data));
@@ -531,9 +561,9 @@ fn test_buy_multiple_buy_cells_same_owner()
 
 	// Prepare Output Data
 	let mut outputs_data: Vec<Bytes> = vec![];
-	let data = 200u128.to_le_bytes().to_vec();
+	let data = 100u128.to_le_bytes().to_vec();
 	outputs_data.push(Bytes::from(data));
-	let data = 8_800u128.to_le_bytes().to_vec();
+	let data = 8_900u128.to_le_bytes().to_vec();
 	outputs_data.push(Bytes::from(data));
 
 	// Build Transaction
@@ -553,7 +583,7 @@ fn test_buy_multiple_buy_cells_same_owner()
 }
 
 #[test]
-fn test_buy_multiple_buy_cells_different_owners()
+fn test_buy_legacy_trailing_bytes_without_marker()
 {
 	// Create Context
 	let mut context = Context::default();
@@ -571,11 +601,9 @@ fn test_buy_multiple_buy_cells_different_owners()
 	// Prepare Identities
 	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
 	let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
-	let buyer_lock_script2 = context.build_script(&out_point_always_success, Bytes::from(vec![2u8; 1])).expect("script");
-	let buyer_lock_script2_hash: [u8; 32] = buyer_lock_script2.calc_script_hash().unpack();
-	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
 	// let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
-	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![4u8; 1])).expect("script");
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
 	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
 
 	// Prepare SUDT Type Script
@@ -592,14 +620,86 @@ fn test_buy_multiple_buy_cells_different_owners()
 	let mut data = vec!();
 	data.extend(buyer_lock_script_hash.to_vec());
 	data.extend(100u128.to_le_bytes().to_vec());
+	data.extend((1u16 << 15).to_le_bytes().to_vec()); // Not flags without the flags marker, so the unknown bit is ignored.
 	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build(), Bytes::from(data));
 	let input = CellInput::new_builder().previous_output(input_out_point).build();
 	inputs.push(input);
+	let data = 9_000u128.to_le_bytes().to_vec();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	let data = 100u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 8_900u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.cell_dep(token_buy_lock_dep)
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Run
+	let _cycles = context.verify_tx(&tx, MAX_CYCLES).expect("pass verification");
+	// println!("consume cycles: {}", cycles);
+}
+
+#[test]
+fn test_buy_multiple_buy_cells_same_owner()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_buy_lock = context.deploy_cell(Loader::default().load_binary("token-buy-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_buy_lock_dep = CellDep::new_builder().out_point(out_point_token_buy_lock.clone()).build();
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	// let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Token Buy Lock Script
+	let script_args: Bytes = sudt_type_script_hash.to_vec().into();
+	let token_buy_lock_script = context.build_script(&out_point_token_buy_lock, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
 	let mut data = vec!();
-	data.extend(buyer_lock_script2_hash.to_vec());
+	data.extend(buyer_lock_script_hash.to_vec());
 	data.extend(100u128.to_le_bytes().to_vec());
 	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build(), Bytes::from(data));
 	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input.clone()); // Add two Token Buy Lock cells.
 	inputs.push(input);
 	let data = 9_000u128.to_le_bytes().to_vec();
 	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build(), Bytes::from(data));
@@ -610,16 +710,12 @@ fn test_buy_multiple_buy_cells_different_owners()
 	let mut outputs = vec![];
 	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
 	outputs.push(output);
-	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script2.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
-	outputs.push(output);
 	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
 	outputs.push(output);
 
 	// Prepare Output Data
 	let mut outputs_data: Vec<Bytes> = vec![];
-	let data = 100u128.to_le_bytes().to_vec();
-	outputs_data.push(Bytes::from(data));
-	let data = 100u128.to_le_bytes().to_vec();
+	let data = 200u128.to_le_bytes().to_vec();
 	outputs_data.push(Bytes::from(data));
 	let data = 8_800u128.to_le_bytes().to_vec();
 	outputs_data.push(Bytes::from(data));
@@ -641,7 +737,7 @@ fn test_buy_multiple_buy_cells_different_owners()
 }
 
 #[test]
-fn test_buy_multiple_buy_cells_different_owners_extra()
+fn test_buy_multiple_script_groups_same_owner()
 {
 	// Create Context
 	let mut context = Context::default();
@@ -659,11 +755,11 @@ fn test_buy_multiple_buy_cells_different_owners_extra()
 	// Prepare Identities
 	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
 	let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
-	let buyer_lock_script2 = context.build_script(&out_point_always_success, Bytes::from(vec![2u8; 1])).expect("script");
-	let buyer_lock_script2_hash: [u8; 32] = buyer_lock_script2.calc_script_hash().unpack();
-	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
 	// let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
-	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![4u8; 1])).expect("script");
+	let recovery_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![2u8; 1])).expect("script");
+	let recovery_lock_script_hash: [u8; 32] = recovery_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
 	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
 
 	// Prepare SUDT Type Script
@@ -674,19 +770,18 @@ fn test_buy_multiple_buy_cells_different_owners_extra()
 	// Prepare Token Buy Lock Script
 	let script_args: Bytes = sudt_type_script_hash.to_vec().into();
 	let token_buy_lock_script = context.build_script(&out_point_token_buy_lock, script_args).expect("script");
+	let script_args: Bytes = [sudt_type_script_hash, recovery_lock_script_hash].concat().into();
+	let recovery_token_buy_lock_script = context.build_script(&out_point_token_buy_lock, script_args).expect("script");
 
 	// Prepare Input Cells
 	let mut inputs = vec![];
 	let mut data = vec!();
 	data.extend(buyer_lock_script_hash.to_vec());
 	data.extend(100u128.to_le_bytes().to_vec());
-	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build(), Bytes::from(data));
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build(), Bytes::from(data.clone()));
 	let input = CellInput::new_builder().previous_output(input_out_point).build();
 	inputs.push(input);
-	let mut data = vec!();
-	data.extend(buyer_lock_script2_hash.to_vec());
-	data.extend(100u128.to_le_bytes().to_vec());
-	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build(), Bytes::from(data));
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(recovery_token_buy_lock_script.clone()).build(), Bytes::from(data)); // The same order in a different script group.
 	let input = CellInput::new_builder().previous_output(input_out_point).build();
 	inputs.push(input);
 	let data = 9_000u128.to_le_bytes().to_vec();
@@ -698,18 +793,14 @@ fn test_buy_multiple_buy_cells_different_owners_extra()
 	let mut outputs = vec![];
 	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
 	outputs.push(output);
-	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script2.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
-	outputs.push(output);
 	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
 	outputs.push(output);
 
 	// Prepare Output Data
 	let mut outputs_data: Vec<Bytes> = vec![];
-	let data = 101u128.to_le_bytes().to_vec();
-	outputs_data.push(Bytes::from(data));
-	let data = 101u128.to_le_bytes().to_vec();
+	let data = 200u128.to_le_bytes().to_vec();
 	outputs_data.push(Bytes::from(data));
-	let data = 8_798u128.to_le_bytes().to_vec();
+	let data = 8_800u128.to_le_bytes().to_vec();
 	outputs_data.push(Bytes::from(data));
 
 	// Build Transaction
@@ -729,7 +820,7 @@ fn test_buy_multiple_buy_cells_different_owners_extra()
 }
 
 #[test]
-fn test_buy_multiple_buy_cells_different_owners_short()
+fn test_buy_multiple_script_groups_same_owner_short()
 {
 	// Create Context
 	let mut context = Context::default();
@@ -747,11 +838,11 @@ fn test_buy_multiple_buy_cells_different_owners_short()
 	// Prepare Identities
 	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
 	let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
-	let buyer_lock_script2 = context.build_script(&out_point_always_success, Bytes::from(vec![2u8; 1])).expect("script");
-	let buyer_lock_script2_hash: [u8; 32] = buyer_lock_script2.calc_script_hash().unpack();
-	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
 	// let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
-	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![4u8; 1])).expect("script");
+	let recovery_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![2u8; 1])).expect("script");
+	let recovery_lock_script_hash: [u8; 32] = recovery_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
 	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
 
 	// Prepare SUDT Type Script
@@ -762,19 +853,18 @@ fn test_buy_multiple_buy_cells_different_owners_short()
 	// Prepare Token Buy Lock Script
 	let script_args: Bytes = sudt_type_script_hash.to_vec().into();
 	let token_buy_lock_script = context.build_script(&out_point_token_buy_lock, script_args).expect("script");
+	let script_args: Bytes = [sudt_type_script_hash, recovery_lock_script_hash].concat().into();
+	let recovery_token_buy_lock_script = context.build_script(&out_point_token_buy_lock, script_args).expect("script");
 
 	// Prepare Input Cells
 	let mut inputs = vec![];
 	let mut data = vec!();
 	data.extend(buyer_lock_script_hash.to_vec());
 	data.extend(100u128.to_le_bytes().to_vec());
-	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build(), Bytes::from(data));
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build(), Bytes::from(data.clone()));
 	let input = CellInput::new_builder().previous_output(input_out_point).build();
 	inputs.push(input);
-	let mut data = vec!();
-	data.extend(buyer_lock_script2_hash.to_vec());
-	data.extend(100u128.to_le_bytes().to_vec());
-	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build(), Bytes::from(data));
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(recovery_token_buy_lock_script.clone()).build(), Bytes::from(data)); // The same order in a different script group.
 	let input = CellInput::new_builder().previous_output(input_out_point).build();
 	inputs.push(input);
 	let data = 9_000u128.to_le_bytes().to_vec();
@@ -786,18 +876,14 @@ fn test_buy_multiple_buy_cells_different_owners_short()
 	let mut outputs = vec![];
 	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
 	outputs.push(output);
-	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script2.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
-	outputs.push(output);
 	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
 	outputs.push(output);
 
 	// Prepare Output Data
 	let mut outputs_data: Vec<Bytes> = vec![];
-	let data = 101u128.to_le_bytes().to_vec();
-	outputs_data.push(Bytes::from(data));
-	let data = 99u128.to_le_bytes().to_vec();
+	let data = 100u128.to_le_bytes().to_vec(); // One payment for both orders.
 	outputs_data.push(Bytes::from(data));
-	let data = 8_800u128.to_le_bytes().to_vec();
+	let data = 8_900u128.to_le_bytes().to_vec();
 	outputs_data.push(Bytes::from(data));
 
 	// Build Transaction
@@ -817,7 +903,7 @@ fn test_buy_multiple_buy_cells_different_owners_short()
 }
 
 #[test]
-fn test_buy_withdrawal_by_owner()
+fn test_buy_multiple_buy_cells_different_owners()
 {
 	// Create Context
 	let mut context = Context::default();
@@ -835,9 +921,11 @@ fn test_buy_withdrawal_by_owner()
 	// Prepare Identities
 	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
 	let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
-	// let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	let buyer_lock_script2 = context.build_script(&out_point_always_success, Bytes::from(vec![2u8; 1])).expect("script");
+	let buyer_lock_script2_hash: [u8; 32] = buyer_lock_script2.calc_script_hash().unpack();
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
 	// let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
-	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![4u8; 1])).expect("script");
 	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
 
 	// Prepare SUDT Type Script
@@ -857,18 +945,34 @@ fn test_buy_withdrawal_by_owner()
 	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build(), Bytes::from(data));
 	let input = CellInput::new_builder().previous_output(input_out_point).build();
 	inputs.push(input);
-	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).build(), Bytes::new());
+	let mut data = vec!();
+	data.extend(buyer_lock_script2_hash.to_vec());
+	data.extend(100u128.to_le_bytes().to_vec());
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let data = 9_000u128.to_le_bytes().to_vec();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build(), Bytes::from(data));
 	let input = CellInput::new_builder().previous_output(input_out_point).build();
 	inputs.push(input);
 	
 	// Prepare Output Cells
 	let mut outputs = vec![];
-	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).build();
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script2.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
 	outputs.push(output);
 
 	// Prepare Output Data
 	let mut outputs_data: Vec<Bytes> = vec![];
-	outputs_data.push(Bytes::new());
+	let data = 100u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 100u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 8_800u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
 
 	// Build Transaction
 	let tx = TransactionBuilder::default()
@@ -887,7 +991,7 @@ fn test_buy_withdrawal_by_owner()
 }
 
 #[test]
-fn test_buy_withdrawal_by_multiple_owners()
+fn test_buy_multiple_buy_cells_different_owners_extra()
 {
 	// Create Context
 	let mut context = Context::default();
@@ -905,11 +1009,11 @@ fn test_buy_withdrawal_by_multiple_owners()
 	// Prepare Identities
 	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
 	let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
-	let buyer_lock_script2 = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	let buyer_lock_script2 = context.build_script(&out_point_always_success, Bytes::from(vec![2u8; 1])).expect("script");
 	let buyer_lock_script2_hash: [u8; 32] = buyer_lock_script2.calc_script_hash().unpack();
-	// let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![2u8; 1])).expect("script");
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
 	// let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
-	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![4u8; 1])).expect("script");
 	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
 
 	// Prepare SUDT Type Script
@@ -935,21 +1039,28 @@ fn test_buy_withdrawal_by_multiple_owners()
 	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build(), Bytes::from(data));
 	let input = CellInput::new_builder().previous_output(input_out_point).build();
 	inputs.push(input);
-	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).build(), Bytes::new());
-	let input = CellInput::new_builder().previous_output(input_out_point).build();
-	inputs.push(input);
-	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script2.clone()).build(), Bytes::new());
+	let data = 9_000u128.to_le_bytes().to_vec();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build(), Bytes::from(data));
 	let input = CellInput::new_builder().previous_output(input_out_point).build();
 	inputs.push(input);
 	
 	// Prepare Output Cells
 	let mut outputs = vec![];
-	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).build();
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script2.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
 	outputs.push(output);
 
 	// Prepare Output Data
 	let mut outputs_data: Vec<Bytes> = vec![];
-	outputs_data.push(Bytes::new());
+	let data = 101u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 101u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 8_798u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
 
 	// Build Transaction
 	let tx = TransactionBuilder::default()
@@ -968,7 +1079,7 @@ fn test_buy_withdrawal_by_multiple_owners()
 }
 
 #[test]
-fn test_buy_withdrawal_by_multiple_owners_invalid()
+fn test_buy_multiple_buy_cells_different_owners_short()
 {
 	// Create Context
 	let mut context = Context::default();
@@ -986,11 +1097,11 @@ fn test_buy_withdrawal_by_multiple_owners_invalid()
 	// Prepare Identities
 	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
 	let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
-	let buyer_lock_script2 = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	let buyer_lock_script2 = context.build_script(&out_point_always_success, Bytes::from(vec![2u8; 1])).expect("script");
 	let buyer_lock_script2_hash: [u8; 32] = buyer_lock_script2.calc_script_hash().unpack();
-	// let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![2u8; 1])).expect("script");
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
 	// let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
-	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![4u8; 1])).expect("script");
 	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
 
 	// Prepare SUDT Type Script
@@ -1016,21 +1127,28 @@ fn test_buy_withdrawal_by_multiple_owners_invalid()
 	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build(), Bytes::from(data));
 	let input = CellInput::new_builder().previous_output(input_out_point).build();
 	inputs.push(input);
-	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).build(), Bytes::new());
+	let data = 9_000u128.to_le_bytes().to_vec();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build(), Bytes::from(data));
 	let input = CellInput::new_builder().previous_output(input_out_point).build();
 	inputs.push(input);
-	// let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script2.clone()).build(), Bytes::new());
-	// let input = CellInput::new_builder().previous_output(input_out_point).build();
-	// inputs.push(input);
 	
 	// Prepare Output Cells
 	let mut outputs = vec![];
-	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).build();
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script2.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
 	outputs.push(output);
 
 	// Prepare Output Data
 	let mut outputs_data: Vec<Bytes> = vec![];
-	outputs_data.push(Bytes::new());
+	let data = 101u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 99u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 8_800u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
 
 	// Build Transaction
 	let tx = TransactionBuilder::default()
@@ -1049,7 +1167,7 @@ fn test_buy_withdrawal_by_multiple_owners_invalid()
 }
 
 #[test]
-fn test_buy_withdrawal_by_not_owner()
+fn test_buy_withdrawal_by_owner()
 {
 	// Create Context
 	let mut context = Context::default();
@@ -1067,7 +1185,7 @@ fn test_buy_withdrawal_by_not_owner()
 	// Prepare Identities
 	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
 	let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
-	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	// let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
 	// let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
 	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
 	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
@@ -1089,13 +1207,13 @@ fn test_buy_withdrawal_by_not_owner()
 	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build(), Bytes::from(data));
 	let input = CellInput::new_builder().previous_output(input_out_point).build();
 	inputs.push(input);
-	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).build(), Bytes::new());
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).build(), Bytes::new());
 	let input = CellInput::new_builder().previous_output(input_out_point).build();
 	inputs.push(input);
 	
 	// Prepare Output Cells
 	let mut outputs = vec![];
-	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).build();
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).build();
 	outputs.push(output);
 
 	// Prepare Output Data
@@ -1114,8 +1232,9267 @@ fn test_buy_withdrawal_by_not_owner()
 	let tx = context.complete_tx(tx);
 
 	// Run
-	let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
-	assert_error_eq!(err, ScriptError::ValidationFailure(ERROR_AMOUNT).input_lock_script(0));
-	// let _cycles = context.verify_tx(&tx, MAX_CYCLES).expect("pass verification");
-	// // println!("consume cycles: {}", cycles);
+	let _cycles = context.verify_tx(&tx, MAX_CYCLES).expect("pass verification");
+	// println!("consume cycles: {}", cycles);
+}
+
+#[test]
+fn test_buy_withdrawal_by_multiple_owners()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_buy_lock = context.deploy_cell(Loader::default().load_binary("token-buy-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_buy_lock_dep = CellDep::new_builder().out_point(out_point_token_buy_lock.clone()).build();
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let buyer_lock_script2 = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	let buyer_lock_script2_hash: [u8; 32] = buyer_lock_script2.calc_script_hash().unpack();
+	// let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![2u8; 1])).expect("script");
+	// let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Token Buy Lock Script
+	let script_args: Bytes = sudt_type_script_hash.to_vec().into();
+	let token_buy_lock_script = context.build_script(&out_point_token_buy_lock, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let mut data = vec!();
+	data.extend(buyer_lock_script_hash.to_vec());
+	data.extend(100u128.to_le_bytes().to_vec());
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let mut data = vec!();
+	data.extend(buyer_lock_script2_hash.to_vec());
+	data.extend(100u128.to_le_bytes().to_vec());
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).build(), Bytes::new());
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script2.clone()).build(), Bytes::new());
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	outputs_data.push(Bytes::new());
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.cell_dep(token_buy_lock_dep)
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Run
+	let _cycles = context.verify_tx(&tx, MAX_CYCLES).expect("pass verification");
+	// println!("consume cycles: {}", cycles);
+}
+
+#[test]
+fn test_buy_withdrawal_by_multiple_owners_invalid()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_buy_lock = context.deploy_cell(Loader::default().load_binary("token-buy-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_buy_lock_dep = CellDep::new_builder().out_point(out_point_token_buy_lock.clone()).build();
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let buyer_lock_script2 = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	let buyer_lock_script2_hash: [u8; 32] = buyer_lock_script2.calc_script_hash().unpack();
+	// let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![2u8; 1])).expect("script");
+	// let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Token Buy Lock Script
+	let script_args: Bytes = sudt_type_script_hash.to_vec().into();
+	let token_buy_lock_script = context.build_script(&out_point_token_buy_lock, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let mut data = vec!();
+	data.extend(buyer_lock_script_hash.to_vec());
+	data.extend(100u128.to_le_bytes().to_vec());
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let mut data = vec!();
+	data.extend(buyer_lock_script2_hash.to_vec());
+	data.extend(100u128.to_le_bytes().to_vec());
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).build(), Bytes::new());
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	// let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script2.clone()).build(), Bytes::new());
+	// let input = CellInput::new_builder().previous_output(input_out_point).build();
+	// inputs.push(input);
+	
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	outputs_data.push(Bytes::new());
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.cell_dep(token_buy_lock_dep)
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Run
+	let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+	assert_error_eq!(err, ScriptError::ValidationFailure(ERROR_AMOUNT).input_lock_script(0));
+}
+
+#[test]
+fn test_buy_withdrawal_by_not_owner()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_buy_lock = context.deploy_cell(Loader::default().load_binary("token-buy-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_buy_lock_dep = CellDep::new_builder().out_point(out_point_token_buy_lock.clone()).build();
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	// let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Token Buy Lock Script
+	let script_args: Bytes = sudt_type_script_hash.to_vec().into();
+	let token_buy_lock_script = context.build_script(&out_point_token_buy_lock, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let mut data = vec!();
+	data.extend(buyer_lock_script_hash.to_vec());
+	data.extend(100u128.to_le_bytes().to_vec());
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).build(), Bytes::new());
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	outputs_data.push(Bytes::new());
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.cell_dep(token_buy_lock_dep)
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Run
+	let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+	assert_error_eq!(err, ScriptError::ValidationFailure(ERROR_AMOUNT).input_lock_script(0));
+	// let _cycles = context.verify_tx(&tx, MAX_CYCLES).expect("pass verification");
+	// // println!("consume cycles: {}", cycles);
+}
+
+#[test]
+fn test_buy_partial_fill()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_buy_lock = context.deploy_cell(Loader::default().load_binary("token-buy-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_buy_lock_dep = CellDep::new_builder().out_point(out_point_token_buy_lock.clone()).build();
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	// let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Token Buy Lock Script
+	let script_args: Bytes = sudt_type_script_hash.to_vec().into();
+	let token_buy_lock_script = context.build_script(&out_point_token_buy_lock, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let mut data = vec!();
+	data.extend(buyer_lock_script_hash.to_vec());
+	data.extend(100u128.to_le_bytes().to_vec());
+	data.extend(FLAGS_MARKER.to_vec());
+	data.extend(FLAG_PARTIAL_FILL.to_le_bytes().to_vec());
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let data = 9_000u128.to_le_bytes().to_vec();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(60_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	let mut data = vec!();
+	data.extend(buyer_lock_script_hash.to_vec());
+	data.extend(60u128.to_le_bytes().to_vec());
+	data.extend(FLAGS_MARKER.to_vec());
+	data.extend(FLAG_PARTIAL_FILL.to_le_bytes().to_vec());
+	outputs_data.push(Bytes::from(data));
+	let data = 40u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 8_960u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.cell_dep(token_buy_lock_dep)
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Run
+	let _cycles = context.verify_tx(&tx, MAX_CYCLES).expect("pass verification");
+	// println!("consume cycles: {}", cycles);
+}
+
+#[test]
+fn test_buy_partial_fill_short()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_buy_lock = context.deploy_cell(Loader::default().load_binary("token-buy-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_buy_lock_dep = CellDep::new_builder().out_point(out_point_token_buy_lock.clone()).build();
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	// let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Token Buy Lock Script
+	let script_args: Bytes = sudt_type_script_hash.to_vec().into();
+	let token_buy_lock_script = context.build_script(&out_point_token_buy_lock, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let mut data = vec!();
+	data.extend(buyer_lock_script_hash.to_vec());
+	data.extend(100u128.to_le_bytes().to_vec());
+	data.extend(FLAGS_MARKER.to_vec());
+	data.extend(FLAG_PARTIAL_FILL.to_le_bytes().to_vec());
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let data = 9_000u128.to_le_bytes().to_vec();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(60_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	let mut data = vec!();
+	data.extend(buyer_lock_script_hash.to_vec());
+	data.extend(60u128.to_le_bytes().to_vec());
+	data.extend(FLAGS_MARKER.to_vec());
+	data.extend(FLAG_PARTIAL_FILL.to_le_bytes().to_vec());
+	outputs_data.push(Bytes::from(data));
+	let data = 39u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 8_961u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.cell_dep(token_buy_lock_dep)
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Run
+	let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+	assert_error_eq!(err, ScriptError::ValidationFailure(ERROR_AMOUNT).input_lock_script(0));
+}
+
+#[test]
+fn test_buy_partial_fill_invalid_remainder()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_buy_lock = context.deploy_cell(Loader::default().load_binary("token-buy-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_buy_lock_dep = CellDep::new_builder().out_point(out_point_token_buy_lock.clone()).build();
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	// let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Token Buy Lock Script
+	let script_args: Bytes = sudt_type_script_hash.to_vec().into();
+	let token_buy_lock_script = context.build_script(&out_point_token_buy_lock, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let mut data = vec!();
+	data.extend(buyer_lock_script_hash.to_vec());
+	data.extend(100u128.to_le_bytes().to_vec());
+	data.extend(FLAGS_MARKER.to_vec());
+	data.extend(FLAG_PARTIAL_FILL.to_le_bytes().to_vec());
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let data = 9_000u128.to_le_bytes().to_vec();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(60_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	let mut data = vec!();
+	data.extend(buyer_lock_script_hash.to_vec());
+	data.extend(70u128.to_le_bytes().to_vec());
+	data.extend(FLAGS_MARKER.to_vec());
+	data.extend(FLAG_PARTIAL_FILL.to_le_bytes().to_vec());
+	outputs_data.push(Bytes::from(data));
+	let data = 30u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 8_970u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.cell_dep(token_buy_lock_dep)
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Run
+	let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+	assert_error_eq!(err, ScriptError::ValidationFailure(ERROR_REMAINDER).input_lock_script(0));
+}
+
+#[test]
+fn test_buy_partial_fill_not_enabled()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_buy_lock = context.deploy_cell(Loader::default().load_binary("token-buy-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_buy_lock_dep = CellDep::new_builder().out_point(out_point_token_buy_lock.clone()).build();
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	// let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Token Buy Lock Script
+	let script_args: Bytes = sudt_type_script_hash.to_vec().into();
+	let token_buy_lock_script = context.build_script(&out_point_token_buy_lock, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let mut data = vec!();
+	data.extend(buyer_lock_script_hash.to_vec());
+	data.extend(100u128.to_le_bytes().to_vec());
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let data = 9_000u128.to_le_bytes().to_vec();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(60_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	let mut data = vec!();
+	data.extend(buyer_lock_script_hash.to_vec());
+	data.extend(60u128.to_le_bytes().to_vec());
+	outputs_data.push(Bytes::from(data));
+	let data = 40u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 8_960u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.cell_dep(token_buy_lock_dep)
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Run
+	let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+	assert_error_eq!(err, ScriptError::ValidationFailure(ERROR_AMOUNT).input_lock_script(0));
+}
+
+#[test]
+fn test_buy_price()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_buy_lock = context.deploy_cell(Loader::default().load_binary("token-buy-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_buy_lock_dep = CellDep::new_builder().out_point(out_point_token_buy_lock.clone()).build();
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	// let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Token Buy Lock Script
+	let script_args: Bytes = sudt_type_script_hash.to_vec().into();
+	let token_buy_lock_script = context.build_script(&out_point_token_buy_lock, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let mut data = vec!();
+	data.extend(buyer_lock_script_hash.to_vec());
+	data.extend(1u128.to_le_bytes().to_vec());
+	data.extend(FLAGS_MARKER.to_vec());
+	data.extend(FLAG_PRICE.to_le_bytes().to_vec());
+	data.extend(1_000_000_000u128.to_le_bytes().to_vec());
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let data = 9_000u128.to_le_bytes().to_vec();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	let data = 100u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 8_900u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.cell_dep(token_buy_lock_dep)
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Run
+	let _cycles = context.verify_tx(&tx, MAX_CYCLES).expect("pass verification");
+	// println!("consume cycles: {}", cycles);
+}
+
+#[test]
+fn test_buy_price_short()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_buy_lock = context.deploy_cell(Loader::default().load_binary("token-buy-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_buy_lock_dep = CellDep::new_builder().out_point(out_point_token_buy_lock.clone()).build();
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	// let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Token Buy Lock Script
+	let script_args: Bytes = sudt_type_script_hash.to_vec().into();
+	let token_buy_lock_script = context.build_script(&out_point_token_buy_lock, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let mut data = vec!();
+	data.extend(buyer_lock_script_hash.to_vec());
+	data.extend(1u128.to_le_bytes().to_vec());
+	data.extend(FLAGS_MARKER.to_vec());
+	data.extend(FLAG_PRICE.to_le_bytes().to_vec());
+	data.extend(1_000_000_000u128.to_le_bytes().to_vec());
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let data = 9_000u128.to_le_bytes().to_vec();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	let data = 99u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 8_901u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.cell_dep(token_buy_lock_dep)
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Run
+	let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+	assert_error_eq!(err, ScriptError::ValidationFailure(ERROR_AMOUNT).input_lock_script(0));
+}
+
+#[test]
+fn test_buy_price_partial_fill()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_buy_lock = context.deploy_cell(Loader::default().load_binary("token-buy-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_buy_lock_dep = CellDep::new_builder().out_point(out_point_token_buy_lock.clone()).build();
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	// let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Token Buy Lock Script
+	let script_args: Bytes = sudt_type_script_hash.to_vec().into();
+	let token_buy_lock_script = context.build_script(&out_point_token_buy_lock, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let mut data = vec!();
+	data.extend(buyer_lock_script_hash.to_vec());
+	data.extend(1u128.to_le_bytes().to_vec());
+	data.extend(FLAGS_MARKER.to_vec());
+	data.extend((FLAG_PRICE | FLAG_PARTIAL_FILL).to_le_bytes().to_vec());
+	data.extend(1_000_000_000u128.to_le_bytes().to_vec());
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let data = 9_000u128.to_le_bytes().to_vec();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(60_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	let mut data = vec!();
+	data.extend(buyer_lock_script_hash.to_vec());
+	data.extend(1u128.to_le_bytes().to_vec());
+	data.extend(FLAGS_MARKER.to_vec());
+	data.extend((FLAG_PRICE | FLAG_PARTIAL_FILL).to_le_bytes().to_vec());
+	data.extend(1_000_000_000u128.to_le_bytes().to_vec());
+	outputs_data.push(Bytes::from(data));
+	let data = 40u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 8_960u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.cell_dep(token_buy_lock_dep)
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Run
+	let _cycles = context.verify_tx(&tx, MAX_CYCLES).expect("pass verification");
+	// println!("consume cycles: {}", cycles);
+}
+
+#[test]
+fn test_buy_price_partial_fill_rounding()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_buy_lock = context.deploy_cell(Loader::default().load_binary("token-buy-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_buy_lock_dep = CellDep::new_builder().out_point(out_point_token_buy_lock.clone()).build();
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	// let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Token Buy Lock Script
+	let script_args: Bytes = sudt_type_script_hash.to_vec().into();
+	let token_buy_lock_script = context.build_script(&out_point_token_buy_lock, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let mut data = vec!();
+	data.extend(buyer_lock_script_hash.to_vec());
+	data.extend(1u128.to_le_bytes().to_vec());
+	data.extend(FLAGS_MARKER.to_vec());
+	data.extend((FLAG_PRICE | FLAG_PARTIAL_FILL).to_le_bytes().to_vec());
+	data.extend(1_000_000_000u128.to_le_bytes().to_vec());
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let data = 9_000u128.to_le_bytes().to_vec();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(59_999_999_999_u64.pack()).lock(token_buy_lock_script.clone()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	let mut data = vec!();
+	data.extend(buyer_lock_script_hash.to_vec());
+	data.extend(1u128.to_le_bytes().to_vec());
+	data.extend(FLAGS_MARKER.to_vec());
+	data.extend((FLAG_PRICE | FLAG_PARTIAL_FILL).to_le_bytes().to_vec());
+	data.extend(1_000_000_000u128.to_le_bytes().to_vec());
+	outputs_data.push(Bytes::from(data));
+	let data = 40u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 8_960u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.cell_dep(token_buy_lock_dep)
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Run
+	let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+	assert_error_eq!(err, ScriptError::ValidationFailure(ERROR_AMOUNT).input_lock_script(0));
+}
+
+#[test]
+fn test_buy_price_partial_fill_changed_price()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_buy_lock = context.deploy_cell(Loader::default().load_binary("token-buy-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_buy_lock_dep = CellDep::new_builder().out_point(out_point_token_buy_lock.clone()).build();
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	// let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Token Buy Lock Script
+	let script_args: Bytes = sudt_type_script_hash.to_vec().into();
+	let token_buy_lock_script = context.build_script(&out_point_token_buy_lock, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let mut data = vec!();
+	data.extend(buyer_lock_script_hash.to_vec());
+	data.extend(1u128.to_le_bytes().to_vec());
+	data.extend(FLAGS_MARKER.to_vec());
+	data.extend((FLAG_PRICE | FLAG_PARTIAL_FILL).to_le_bytes().to_vec());
+	data.extend(1_000_000_000u128.to_le_bytes().to_vec());
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let data = 9_000u128.to_le_bytes().to_vec();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(60_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	let mut data = vec!();
+	data.extend(buyer_lock_script_hash.to_vec());
+	data.extend(1u128.to_le_bytes().to_vec());
+	data.extend(FLAGS_MARKER.to_vec());
+	data.extend((FLAG_PRICE | FLAG_PARTIAL_FILL).to_le_bytes().to_vec());
+	data.extend(2_000_000_000u128.to_le_bytes().to_vec());
+	outputs_data.push(Bytes::from(data));
+	let data = 40u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 8_960u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.cell_dep(token_buy_lock_dep)
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Run
+	let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+	assert_error_eq!(err, ScriptError::ValidationFailure(ERROR_REMAINDER).input_lock_script(0));
+}
+
+#[test]
+fn test_buy_bound_payment()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_buy_lock = context.deploy_cell(Loader::default().load_binary("token-buy-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_buy_lock_dep = CellDep::new_builder().out_point(out_point_token_buy_lock.clone()).build();
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	// let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Token Buy Lock Script
+	let script_args: Bytes = sudt_type_script_hash.to_vec().into();
+	let token_buy_lock_script = context.build_script(&out_point_token_buy_lock, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let mut data = vec!();
+	data.extend(buyer_lock_script_hash.to_vec());
+	data.extend(100u128.to_le_bytes().to_vec());
+	data.extend(FLAGS_MARKER.to_vec());
+	data.extend(FLAG_BOUND_PAYMENT.to_le_bytes().to_vec());
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build(), Bytes::from(data));
+	let order_out_point = input_out_point.clone();
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let data = 9_000u128.to_le_bytes().to_vec();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	let mut data = vec!();
+	data.extend(100u128.to_le_bytes().to_vec());
+	data.extend(BINDING_MARKER.to_vec());
+	data.extend(order_out_point.as_slice().to_vec());
+	outputs_data.push(Bytes::from(data));
+	let data = 8_900u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.cell_dep(token_buy_lock_dep)
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Run
+	let _cycles = context.verify_tx(&tx, MAX_CYCLES).expect("pass verification");
+	// println!("consume cycles: {}", cycles);
+}
+
+#[test]
+fn test_buy_bound_payment_unbound()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_buy_lock = context.deploy_cell(Loader::default().load_binary("token-buy-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_buy_lock_dep = CellDep::new_builder().out_point(out_point_token_buy_lock.clone()).build();
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	// let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Token Buy Lock Script
+	let script_args: Bytes = sudt_type_script_hash.to_vec().into();
+	let token_buy_lock_script = context.build_script(&out_point_token_buy_lock, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let mut data = vec!();
+	data.extend(buyer_lock_script_hash.to_vec());
+	data.extend(100u128.to_le_bytes().to_vec());
+	data.extend(FLAGS_MARKER.to_vec());
+	data.extend(FLAG_BOUND_PAYMENT.to_le_bytes().to_vec());
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let data = 9_000u128.to_le_bytes().to_vec();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	let data = 100u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 8_900u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.cell_dep(token_buy_lock_dep)
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Run
+	let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+	assert_error_eq!(err, ScriptError::ValidationFailure(ERROR_AMOUNT).input_lock_script(0));
+}
+
+#[test]
+fn test_buy_unbound_payment_with_trailing_data()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_buy_lock = context.deploy_cell(Loader::default().load_binary("token-buy-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_buy_lock_dep = CellDep::new_builder().out_point(out_point_token_buy_lock.clone()).build();
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	// let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Token Buy Lock Script
+	let script_args: Bytes = sudt_type_script_hash.to_vec().into();
+	let token_buy_lock_script = context.build_script(&out_point_token_buy_lock, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let mut data = vec!();
+	data.extend(buyer_lock_script_hash.to_vec());
+	data.extend(100u128.to_le_bytes().to_vec());
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let data = 9_000u128.to_le_bytes().to_vec();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	let mut data = vec!();
+	data.extend(100u128.to_le_bytes().to_vec());
+	data.extend(vec![7u8; 36]); // Long enough for an out point, but not a binding without the binding marker.
+	outputs_data.push(Bytes::from(data));
+	let data = 8_900u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.cell_dep(token_buy_lock_dep)
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Run
+	let _cycles = context.verify_tx(&tx, MAX_CYCLES).expect("pass verification");
+	// println!("consume cycles: {}", cycles);
+}
+
+#[test]
+fn test_buy_bound_payment_reused()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_buy_lock = context.deploy_cell(Loader::default().load_binary("token-buy-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_buy_lock_dep = CellDep::new_builder().out_point(out_point_token_buy_lock.clone()).build();
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	// let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Token Buy Lock Script
+	let script_args: Bytes = sudt_type_script_hash.to_vec().into();
+	let token_buy_lock_script = context.build_script(&out_point_token_buy_lock, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let mut data = vec!();
+	data.extend(buyer_lock_script_hash.to_vec());
+	data.extend(100u128.to_le_bytes().to_vec());
+	data.extend(FLAGS_MARKER.to_vec());
+	data.extend(FLAG_BOUND_PAYMENT.to_le_bytes().to_vec());
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build(), Bytes::from(data));
+	let order_out_point = input_out_point.clone();
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let mut data = vec!();
+	data.extend(buyer_lock_script_hash.to_vec());
+	data.extend(100u128.to_le_bytes().to_vec());
+	data.extend(FLAGS_MARKER.to_vec());
+	data.extend(FLAG_BOUND_PAYMENT.to_le_bytes().to_vec());
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let data = 9_000u128.to_le_bytes().to_vec();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	let mut data = vec!();
+	data.extend(200u128.to_le_bytes().to_vec());
+	data.extend(BINDING_MARKER.to_vec());
+	data.extend(order_out_point.as_slice().to_vec());
+	outputs_data.push(Bytes::from(data));
+	let data = 8_800u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.cell_dep(token_buy_lock_dep)
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Run
+	let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+	assert_error_eq!(err, ScriptError::ValidationFailure(ERROR_AMOUNT).input_lock_script(0));
+}
+
+#[test]
+fn test_buy_bound_payment_to_other_order()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_buy_lock = context.deploy_cell(Loader::default().load_binary("token-buy-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_buy_lock_dep = CellDep::new_builder().out_point(out_point_token_buy_lock.clone()).build();
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	// let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Token Buy Lock Script
+	let script_args: Bytes = sudt_type_script_hash.to_vec().into();
+	let token_buy_lock_script = context.build_script(&out_point_token_buy_lock, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let mut data = vec!();
+	data.extend(buyer_lock_script_hash.to_vec());
+	data.extend(100u128.to_le_bytes().to_vec());
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let data = 9_000u128.to_le_bytes().to_vec();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let other_out_point = input_out_point.clone();
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	let mut data = vec!();
+	data.extend(100u128.to_le_bytes().to_vec());
+	data.extend(BINDING_MARKER.to_vec());
+	data.extend(other_out_point.as_slice().to_vec());
+	outputs_data.push(Bytes::from(data));
+	let data = 8_900u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.cell_dep(token_buy_lock_dep)
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Run
+	let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+	assert_error_eq!(err, ScriptError::ValidationFailure(ERROR_AMOUNT).input_lock_script(0));
+}
+
+#[test]
+fn test_buy_recovery_invalid_data()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_buy_lock = context.deploy_cell(Loader::default().load_binary("token-buy-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_buy_lock_dep = CellDep::new_builder().out_point(out_point_token_buy_lock.clone()).build();
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	// let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	// let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
+	let recovery_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![2u8; 1])).expect("script");
+	let recovery_lock_script_hash: [u8; 32] = recovery_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Token Buy Lock Script
+	let script_args: Bytes = [sudt_type_script_hash, recovery_lock_script_hash].concat().into();
+	let token_buy_lock_script = context.build_script(&out_point_token_buy_lock, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let mut data = vec!();
+	data.extend(buyer_lock_script_hash.to_vec());
+	data.extend(100u64.to_le_bytes().to_vec()); // Incorrect data length (u64 vs u128).
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(recovery_lock_script.clone()).build(), Bytes::new());
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(recovery_lock_script.clone()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	outputs_data.push(Bytes::new());
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.cell_dep(token_buy_lock_dep)
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Run
+	let _cycles = context.verify_tx(&tx, MAX_CYCLES).expect("pass verification");
+	// println!("consume cycles: {}", cycles);
+}
+
+#[test]
+fn test_buy_recovery_invalid_data_not_recovery()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_buy_lock = context.deploy_cell(Loader::default().load_binary("token-buy-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_buy_lock_dep = CellDep::new_builder().out_point(out_point_token_buy_lock.clone()).build();
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	// let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
+	let recovery_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![2u8; 1])).expect("script");
+	let recovery_lock_script_hash: [u8; 32] = recovery_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Token Buy Lock Script
+	let script_args: Bytes = [sudt_type_script_hash, recovery_lock_script_hash].concat().into();
+	let token_buy_lock_script = context.build_script(&out_point_token_buy_lock, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let mut data = vec!();
+	data.extend(buyer_lock_script_hash.to_vec());
+	data.extend(100u64.to_le_bytes().to_vec()); // Incorrect data length (u64 vs u128).
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).build(), Bytes::new());
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	outputs_data.push(Bytes::new());
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.cell_dep(token_buy_lock_dep)
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Run
+	let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+	assert_error_eq!(err, ScriptError::ValidationFailure(ERROR_DATA_LEN).input_lock_script(0));
+}
+
+#[test]
+fn test_buy_recovery_valid_data()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_buy_lock = context.deploy_cell(Loader::default().load_binary("token-buy-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_buy_lock_dep = CellDep::new_builder().out_point(out_point_token_buy_lock.clone()).build();
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	// let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	// let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
+	let recovery_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![2u8; 1])).expect("script");
+	let recovery_lock_script_hash: [u8; 32] = recovery_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Token Buy Lock Script
+	let script_args: Bytes = [sudt_type_script_hash, recovery_lock_script_hash].concat().into();
+	let token_buy_lock_script = context.build_script(&out_point_token_buy_lock, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let mut data = vec!();
+	data.extend(buyer_lock_script_hash.to_vec());
+	data.extend(100u128.to_le_bytes().to_vec());
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(recovery_lock_script.clone()).build(), Bytes::new());
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(recovery_lock_script.clone()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	outputs_data.push(Bytes::new());
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.cell_dep(token_buy_lock_dep)
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Run
+	let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+	assert_error_eq!(err, ScriptError::ValidationFailure(ERROR_AMOUNT).input_lock_script(0));
+}
+
+#[test]
+fn test_buy_withdrawal_by_owner_with_fill()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_buy_lock = context.deploy_cell(Loader::default().load_binary("token-buy-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_buy_lock_dep = CellDep::new_builder().out_point(out_point_token_buy_lock.clone()).build();
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	// let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
+	let buyer_lock_script2 = context.build_script(&out_point_always_success, Bytes::from(vec![2u8; 1])).expect("script");
+	let buyer_lock_script2_hash: [u8; 32] = buyer_lock_script2.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Token Buy Lock Script
+	let script_args: Bytes = sudt_type_script_hash.to_vec().into();
+	let token_buy_lock_script = context.build_script(&out_point_token_buy_lock, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let mut data = vec!();
+	data.extend(buyer_lock_script_hash.to_vec());
+	data.extend(100u128.to_le_bytes().to_vec());
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let mut data = vec!();
+	data.extend(buyer_lock_script2_hash.to_vec());
+	data.extend(100u128.to_le_bytes().to_vec());
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).build(), Bytes::new());
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let data = 9_000u128.to_le_bytes().to_vec();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script2.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	outputs_data.push(Bytes::new());
+	let data = 100u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 8_900u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.cell_dep(token_buy_lock_dep)
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Run
+	let _cycles = context.verify_tx(&tx, MAX_CYCLES).expect("pass verification");
+	// println!("consume cycles: {}", cycles);
+}
+
+#[test]
+fn test_buy_withdrawal_by_owner_with_fill_short()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_buy_lock = context.deploy_cell(Loader::default().load_binary("token-buy-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_buy_lock_dep = CellDep::new_builder().out_point(out_point_token_buy_lock.clone()).build();
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	// let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
+	let buyer_lock_script2 = context.build_script(&out_point_always_success, Bytes::from(vec![2u8; 1])).expect("script");
+	let buyer_lock_script2_hash: [u8; 32] = buyer_lock_script2.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Token Buy Lock Script
+	let script_args: Bytes = sudt_type_script_hash.to_vec().into();
+	let token_buy_lock_script = context.build_script(&out_point_token_buy_lock, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let mut data = vec!();
+	data.extend(buyer_lock_script_hash.to_vec());
+	data.extend(100u128.to_le_bytes().to_vec());
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let mut data = vec!();
+	data.extend(buyer_lock_script2_hash.to_vec());
+	data.extend(100u128.to_le_bytes().to_vec());
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).build(), Bytes::new());
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let data = 9_000u128.to_le_bytes().to_vec();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script2.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	outputs_data.push(Bytes::new());
+	let data = 99u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 8_901u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.cell_dep(token_buy_lock_dep)
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Run
+	let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+	assert_error_eq!(err, ScriptError::ValidationFailure(ERROR_AMOUNT).input_lock_script(0));
+}
+
+#[test]
+fn test_buy_payout_lock()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_buy_lock = context.deploy_cell(Loader::default().load_binary("token-buy-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_buy_lock_dep = CellDep::new_builder().out_point(out_point_token_buy_lock.clone()).build();
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	// let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
+	let payout_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![2u8; 1])).expect("script");
+	let payout_lock_script_hash: [u8; 32] = payout_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Token Buy Lock Script
+	let script_args: Bytes = sudt_type_script_hash.to_vec().into();
+	let token_buy_lock_script = context.build_script(&out_point_token_buy_lock, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let mut data = vec!();
+	data.extend(buyer_lock_script_hash.to_vec());
+	data.extend(100u128.to_le_bytes().to_vec());
+	data.extend(FLAGS_MARKER.to_vec());
+	data.extend(FLAG_PAYOUT_LOCK.to_le_bytes().to_vec());
+	data.extend(payout_lock_script_hash.to_vec());
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let data = 9_000u128.to_le_bytes().to_vec();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(payout_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	let data = 100u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 8_900u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.cell_dep(token_buy_lock_dep)
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Run
+	let _cycles = context.verify_tx(&tx, MAX_CYCLES).expect("pass verification");
+	// println!("consume cycles: {}", cycles);
+}
+
+#[test]
+fn test_buy_payout_lock_paid_to_owner()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_buy_lock = context.deploy_cell(Loader::default().load_binary("token-buy-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_buy_lock_dep = CellDep::new_builder().out_point(out_point_token_buy_lock.clone()).build();
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	// let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
+	let payout_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![2u8; 1])).expect("script");
+	let payout_lock_script_hash: [u8; 32] = payout_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Token Buy Lock Script
+	let script_args: Bytes = sudt_type_script_hash.to_vec().into();
+	let token_buy_lock_script = context.build_script(&out_point_token_buy_lock, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let mut data = vec!();
+	data.extend(buyer_lock_script_hash.to_vec());
+	data.extend(100u128.to_le_bytes().to_vec());
+	data.extend(FLAGS_MARKER.to_vec());
+	data.extend(FLAG_PAYOUT_LOCK.to_le_bytes().to_vec());
+	data.extend(payout_lock_script_hash.to_vec());
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let data = 9_000u128.to_le_bytes().to_vec();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	let data = 100u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 8_900u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.cell_dep(token_buy_lock_dep)
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Run
+	let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+	assert_error_eq!(err, ScriptError::ValidationFailure(ERROR_AMOUNT).input_lock_script(0));
+}
+
+#[test]
+fn test_buy_payout_lock_withdrawal_by_owner()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_buy_lock = context.deploy_cell(Loader::default().load_binary("token-buy-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_buy_lock_dep = CellDep::new_builder().out_point(out_point_token_buy_lock.clone()).build();
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	// let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	// let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
+	let payout_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![2u8; 1])).expect("script");
+	let payout_lock_script_hash: [u8; 32] = payout_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Token Buy Lock Script
+	let script_args: Bytes = sudt_type_script_hash.to_vec().into();
+	let token_buy_lock_script = context.build_script(&out_point_token_buy_lock, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let mut data = vec!();
+	data.extend(buyer_lock_script_hash.to_vec());
+	data.extend(100u128.to_le_bytes().to_vec());
+	data.extend(FLAGS_MARKER.to_vec());
+	data.extend(FLAG_PAYOUT_LOCK.to_le_bytes().to_vec());
+	data.extend(payout_lock_script_hash.to_vec());
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).build(), Bytes::new());
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	outputs_data.push(Bytes::new());
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.cell_dep(token_buy_lock_dep)
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Run
+	let _cycles = context.verify_tx(&tx, MAX_CYCLES).expect("pass verification");
+	// println!("consume cycles: {}", cycles);
+}
+
+#[test]
+fn test_buy_payout_lock_withdrawal_by_payout_lock()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_buy_lock = context.deploy_cell(Loader::default().load_binary("token-buy-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_buy_lock_dep = CellDep::new_builder().out_point(out_point_token_buy_lock.clone()).build();
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	// let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	// let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
+	let payout_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![2u8; 1])).expect("script");
+	let payout_lock_script_hash: [u8; 32] = payout_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Token Buy Lock Script
+	let script_args: Bytes = sudt_type_script_hash.to_vec().into();
+	let token_buy_lock_script = context.build_script(&out_point_token_buy_lock, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let mut data = vec!();
+	data.extend(buyer_lock_script_hash.to_vec());
+	data.extend(100u128.to_le_bytes().to_vec());
+	data.extend(FLAGS_MARKER.to_vec());
+	data.extend(FLAG_PAYOUT_LOCK.to_le_bytes().to_vec());
+	data.extend(payout_lock_script_hash.to_vec());
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(payout_lock_script.clone()).build(), Bytes::new());
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(payout_lock_script.clone()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	outputs_data.push(Bytes::new());
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.cell_dep(token_buy_lock_dep)
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Run
+	let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+	assert_error_eq!(err, ScriptError::ValidationFailure(ERROR_AMOUNT).input_lock_script(0));
+}
+
+#[test]
+fn test_buy_acp_payout()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_buy_lock = context.deploy_cell(Loader::default().load_binary("token-buy-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_buy_lock_dep = CellDep::new_builder().out_point(out_point_token_buy_lock.clone()).build();
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	// let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
+	let acp_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![2u8; 1])).expect("script");
+	let acp_lock_script_hash: [u8; 32] = acp_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Token Buy Lock Script
+	let script_args: Bytes = sudt_type_script_hash.to_vec().into();
+	let token_buy_lock_script = context.build_script(&out_point_token_buy_lock, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let mut data = vec!();
+	data.extend(buyer_lock_script_hash.to_vec());
+	data.extend(100u128.to_le_bytes().to_vec());
+	data.extend(FLAGS_MARKER.to_vec());
+	data.extend(FLAG_PAYOUT_LOCK.to_le_bytes().to_vec());
+	data.extend(acp_lock_script_hash.to_vec());
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let data = 500u128.to_le_bytes().to_vec();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(acp_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let data = 9_000u128.to_le_bytes().to_vec();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(acp_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	let data = 600u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 8_900u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.cell_dep(token_buy_lock_dep)
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Run
+	let _cycles = context.verify_tx(&tx, MAX_CYCLES).expect("pass verification");
+	// println!("consume cycles: {}", cycles);
+}
+
+#[test]
+fn test_buy_acp_payout_unchanged()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_buy_lock = context.deploy_cell(Loader::default().load_binary("token-buy-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_buy_lock_dep = CellDep::new_builder().out_point(out_point_token_buy_lock.clone()).build();
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	// let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
+	let acp_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![2u8; 1])).expect("script");
+	let acp_lock_script_hash: [u8; 32] = acp_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Token Buy Lock Script
+	let script_args: Bytes = sudt_type_script_hash.to_vec().into();
+	let token_buy_lock_script = context.build_script(&out_point_token_buy_lock, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let mut data = vec!();
+	data.extend(buyer_lock_script_hash.to_vec());
+	data.extend(100u128.to_le_bytes().to_vec());
+	data.extend(FLAGS_MARKER.to_vec());
+	data.extend(FLAG_PAYOUT_LOCK.to_le_bytes().to_vec());
+	data.extend(acp_lock_script_hash.to_vec());
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let data = 500u128.to_le_bytes().to_vec();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(acp_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let data = 9_000u128.to_le_bytes().to_vec();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(acp_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	let data = 500u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 9_000u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.cell_dep(token_buy_lock_dep)
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Run
+	let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+	assert_error_eq!(err, ScriptError::ValidationFailure(ERROR_AMOUNT).input_lock_script(0));
+}
+
+#[test]
+fn test_buy_acp_payout_short()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_buy_lock = context.deploy_cell(Loader::default().load_binary("token-buy-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_buy_lock_dep = CellDep::new_builder().out_point(out_point_token_buy_lock.clone()).build();
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	// let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
+	let acp_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![2u8; 1])).expect("script");
+	let acp_lock_script_hash: [u8; 32] = acp_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Token Buy Lock Script
+	let script_args: Bytes = sudt_type_script_hash.to_vec().into();
+	let token_buy_lock_script = context.build_script(&out_point_token_buy_lock, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let mut data = vec!();
+	data.extend(buyer_lock_script_hash.to_vec());
+	data.extend(100u128.to_le_bytes().to_vec());
+	data.extend(FLAGS_MARKER.to_vec());
+	data.extend(FLAG_PAYOUT_LOCK.to_le_bytes().to_vec());
+	data.extend(acp_lock_script_hash.to_vec());
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let data = 500u128.to_le_bytes().to_vec();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(acp_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let data = 9_000u128.to_le_bytes().to_vec();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(acp_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	let data = 599u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 8_901u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.cell_dep(token_buy_lock_dep)
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Run
+	let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+	assert_error_eq!(err, ScriptError::ValidationFailure(ERROR_AMOUNT).input_lock_script(0));
+}
+
+#[test]
+fn test_buy_acp_payout_owner_cell()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_buy_lock = context.deploy_cell(Loader::default().load_binary("token-buy-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_buy_lock_dep = CellDep::new_builder().out_point(out_point_token_buy_lock.clone()).build();
+
+	// Prepare Identities
+	// let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	// let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	// let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
+	let acp_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![2u8; 1])).expect("script");
+	let acp_lock_script_hash: [u8; 32] = acp_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Token Buy Lock Script
+	let script_args: Bytes = sudt_type_script_hash.to_vec().into();
+	let token_buy_lock_script = context.build_script(&out_point_token_buy_lock, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let mut data = vec!();
+	data.extend(acp_lock_script_hash.to_vec());
+	data.extend(100u128.to_le_bytes().to_vec());
+	data.extend(FLAGS_MARKER.to_vec());
+	data.extend(FLAG_PAYOUT_LOCK.to_le_bytes().to_vec());
+	data.extend(acp_lock_script_hash.to_vec());
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let data = 500u128.to_le_bytes().to_vec();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(acp_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let data = 9_000u128.to_le_bytes().to_vec();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(acp_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	let data = 599u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 8_901u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.cell_dep(token_buy_lock_dep)
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Run
+	let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+	assert_error_eq!(err, ScriptError::ValidationFailure(ERROR_AMOUNT).input_lock_script(0));
+}
+
+#[test]
+fn test_buy_acp_payout_owner_typeless_cell()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_buy_lock = context.deploy_cell(Loader::default().load_binary("token-buy-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_buy_lock_dep = CellDep::new_builder().out_point(out_point_token_buy_lock.clone()).build();
+
+	// Prepare Identities
+	// let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	// let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	// let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
+	let acp_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![2u8; 1])).expect("script");
+	let acp_lock_script_hash: [u8; 32] = acp_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Token Buy Lock Script
+	let script_args: Bytes = sudt_type_script_hash.to_vec().into();
+	let token_buy_lock_script = context.build_script(&out_point_token_buy_lock, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let mut data = vec!();
+	data.extend(acp_lock_script_hash.to_vec());
+	data.extend(100u128.to_le_bytes().to_vec());
+	data.extend(FLAGS_MARKER.to_vec());
+	data.extend(FLAG_PAYOUT_LOCK.to_le_bytes().to_vec());
+	data.extend(acp_lock_script_hash.to_vec());
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(acp_lock_script.clone()).build(), Bytes::new());
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let data = 9_000u128.to_le_bytes().to_vec();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(200_000_000_000_u64.pack()).lock(acp_lock_script.clone()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	outputs_data.push(Bytes::new());
+	let data = 9_000u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.cell_dep(token_buy_lock_dep)
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Run
+	let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+	assert_error_eq!(err, ScriptError::ValidationFailure(ERROR_AMOUNT).input_lock_script(0));
+}
+
+#[test]
+fn test_buy_withdrawal_by_owner_sudt_cell()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_buy_lock = context.deploy_cell(Loader::default().load_binary("token-buy-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_buy_lock_dep = CellDep::new_builder().out_point(out_point_token_buy_lock.clone()).build();
+
+	// Prepare Identities
+	// let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	// let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	// let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
+	let owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![2u8; 1])).expect("script");
+	let owner_lock_script_hash: [u8; 32] = owner_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Token Buy Lock Script
+	let script_args: Bytes = sudt_type_script_hash.to_vec().into();
+	let token_buy_lock_script = context.build_script(&out_point_token_buy_lock, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let mut data = vec!();
+	data.extend(owner_lock_script_hash.to_vec());
+	data.extend(100u128.to_le_bytes().to_vec());
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let data = 500u128.to_le_bytes().to_vec();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(owner_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let data = 9_000u128.to_le_bytes().to_vec();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(200_000_000_000_u64.pack()).lock(owner_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	let data = 500u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 9_000u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.cell_dep(token_buy_lock_dep)
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Run
+	let _cycles = context.verify_tx(&tx, MAX_CYCLES).expect("pass verification");
+	// println!("consume cycles: {}", cycles);
+}
+
+#[test]
+fn test_buy_acp_payout_new_cell()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_buy_lock = context.deploy_cell(Loader::default().load_binary("token-buy-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_buy_lock_dep = CellDep::new_builder().out_point(out_point_token_buy_lock.clone()).build();
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	// let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
+	let acp_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![2u8; 1])).expect("script");
+	let acp_lock_script_hash: [u8; 32] = acp_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Token Buy Lock Script
+	let script_args: Bytes = sudt_type_script_hash.to_vec().into();
+	let token_buy_lock_script = context.build_script(&out_point_token_buy_lock, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let mut data = vec!();
+	data.extend(buyer_lock_script_hash.to_vec());
+	data.extend(100u128.to_le_bytes().to_vec());
+	data.extend(FLAGS_MARKER.to_vec());
+	data.extend(FLAG_PAYOUT_LOCK.to_le_bytes().to_vec());
+	data.extend(acp_lock_script_hash.to_vec());
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let data = 500u128.to_le_bytes().to_vec();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(acp_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let data = 9_000u128.to_le_bytes().to_vec();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(acp_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(acp_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	let data = 500u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 100u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 8_900u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.cell_dep(token_buy_lock_dep)
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Run
+	let _cycles = context.verify_tx(&tx, MAX_CYCLES).expect("pass verification");
+	// println!("consume cycles: {}", cycles);
+}
+
+#[test]
+fn test_buy_fee()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_buy_lock = context.deploy_cell(Loader::default().load_binary("token-buy-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_buy_lock_dep = CellDep::new_builder().out_point(out_point_token_buy_lock.clone()).build();
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	// let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
+	let fee_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![2u8; 1])).expect("script");
+	let fee_lock_script_hash: [u8; 32] = fee_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Token Buy Lock Script
+	let script_args: Bytes = sudt_type_script_hash.to_vec().into();
+	let token_buy_lock_script = context.build_script(&out_point_token_buy_lock, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let mut data = vec!();
+	data.extend(buyer_lock_script_hash.to_vec());
+	data.extend(100u128.to_le_bytes().to_vec());
+	data.extend(FLAGS_MARKER.to_vec());
+	data.extend(FLAG_FEE.to_le_bytes().to_vec());
+	data.extend(fee_lock_script_hash.to_vec());
+	data.extend(250u16.to_le_bytes().to_vec());
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let data = 9_000u128.to_le_bytes().to_vec();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(fee_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	let data = 98u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 2u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 8_900u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.cell_dep(token_buy_lock_dep)
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Run
+	let _cycles = context.verify_tx(&tx, MAX_CYCLES).expect("pass verification");
+	// println!("consume cycles: {}", cycles);
+}
+
+#[test]
+fn test_buy_fee_rounding()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_buy_lock = context.deploy_cell(Loader::default().load_binary("token-buy-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_buy_lock_dep = CellDep::new_builder().out_point(out_point_token_buy_lock.clone()).build();
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	// let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
+	let fee_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![2u8; 1])).expect("script");
+	let fee_lock_script_hash: [u8; 32] = fee_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Token Buy Lock Script
+	let script_args: Bytes = sudt_type_script_hash.to_vec().into();
+	let token_buy_lock_script = context.build_script(&out_point_token_buy_lock, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let mut data = vec!();
+	data.extend(buyer_lock_script_hash.to_vec());
+	data.extend(100u128.to_le_bytes().to_vec());
+	data.extend(FLAGS_MARKER.to_vec());
+	data.extend(FLAG_FEE.to_le_bytes().to_vec());
+	data.extend(fee_lock_script_hash.to_vec());
+	data.extend(150u16.to_le_bytes().to_vec());
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let data = 9_000u128.to_le_bytes().to_vec();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(fee_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	let data = 99u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 1u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 8_900u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.cell_dep(token_buy_lock_dep)
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Run
+	let _cycles = context.verify_tx(&tx, MAX_CYCLES).expect("pass verification");
+	// println!("consume cycles: {}", cycles);
+}
+
+#[test]
+fn test_buy_fee_missing()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_buy_lock = context.deploy_cell(Loader::default().load_binary("token-buy-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_buy_lock_dep = CellDep::new_builder().out_point(out_point_token_buy_lock.clone()).build();
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	// let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
+	let fee_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![2u8; 1])).expect("script");
+	let fee_lock_script_hash: [u8; 32] = fee_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Token Buy Lock Script
+	let script_args: Bytes = sudt_type_script_hash.to_vec().into();
+	let token_buy_lock_script = context.build_script(&out_point_token_buy_lock, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let mut data = vec!();
+	data.extend(buyer_lock_script_hash.to_vec());
+	data.extend(100u128.to_le_bytes().to_vec());
+	data.extend(FLAGS_MARKER.to_vec());
+	data.extend(FLAG_FEE.to_le_bytes().to_vec());
+	data.extend(fee_lock_script_hash.to_vec());
+	data.extend(250u16.to_le_bytes().to_vec());
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let data = 9_000u128.to_le_bytes().to_vec();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	let data = 100u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 8_900u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.cell_dep(token_buy_lock_dep)
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Run
+	let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+	assert_error_eq!(err, ScriptError::ValidationFailure(ERROR_AMOUNT).input_lock_script(0));
+}
+
+#[test]
+fn test_buy_fee_owner_short()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_buy_lock = context.deploy_cell(Loader::default().load_binary("token-buy-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_buy_lock_dep = CellDep::new_builder().out_point(out_point_token_buy_lock.clone()).build();
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	// let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
+	let fee_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![2u8; 1])).expect("script");
+	let fee_lock_script_hash: [u8; 32] = fee_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Token Buy Lock Script
+	let script_args: Bytes = sudt_type_script_hash.to_vec().into();
+	let token_buy_lock_script = context.build_script(&out_point_token_buy_lock, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let mut data = vec!();
+	data.extend(buyer_lock_script_hash.to_vec());
+	data.extend(100u128.to_le_bytes().to_vec());
+	data.extend(FLAGS_MARKER.to_vec());
+	data.extend(FLAG_FEE.to_le_bytes().to_vec());
+	data.extend(fee_lock_script_hash.to_vec());
+	data.extend(250u16.to_le_bytes().to_vec());
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let data = 9_000u128.to_le_bytes().to_vec();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(fee_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	let data = 97u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 3u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 8_900u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.cell_dep(token_buy_lock_dep)
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Run
+	let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+	assert_error_eq!(err, ScriptError::ValidationFailure(ERROR_AMOUNT).input_lock_script(0));
+}
+
+#[test]
+fn test_buy_fee_invalid_rate()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_buy_lock = context.deploy_cell(Loader::default().load_binary("token-buy-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_buy_lock_dep = CellDep::new_builder().out_point(out_point_token_buy_lock.clone()).build();
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	// let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
+	let fee_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![2u8; 1])).expect("script");
+	let fee_lock_script_hash: [u8; 32] = fee_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Token Buy Lock Script
+	let script_args: Bytes = sudt_type_script_hash.to_vec().into();
+	let token_buy_lock_script = context.build_script(&out_point_token_buy_lock, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let mut data = vec!();
+	data.extend(buyer_lock_script_hash.to_vec());
+	data.extend(100u128.to_le_bytes().to_vec());
+	data.extend(FLAGS_MARKER.to_vec());
+	data.extend(FLAG_FEE.to_le_bytes().to_vec());
+	data.extend(fee_lock_script_hash.to_vec());
+	data.extend(10_001u16.to_le_bytes().to_vec());
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let data = 9_000u128.to_le_bytes().to_vec();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	let data = 100u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 8_900u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.cell_dep(token_buy_lock_dep)
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Run
+	let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+	assert_error_eq!(err, ScriptError::ValidationFailure(ERROR_ENCODING).input_lock_script(0));
+}
+
+#[test]
+fn test_buy_fee_short()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_buy_lock = context.deploy_cell(Loader::default().load_binary("token-buy-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_buy_lock_dep = CellDep::new_builder().out_point(out_point_token_buy_lock.clone()).build();
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	// let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
+	let fee_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![2u8; 1])).expect("script");
+	let fee_lock_script_hash: [u8; 32] = fee_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Token Buy Lock Script
+	let script_args: Bytes = sudt_type_script_hash.to_vec().into();
+	let token_buy_lock_script = context.build_script(&out_point_token_buy_lock, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let mut data = vec!();
+	data.extend(buyer_lock_script_hash.to_vec());
+	data.extend(100u128.to_le_bytes().to_vec());
+	data.extend(FLAGS_MARKER.to_vec());
+	data.extend(FLAG_FEE.to_le_bytes().to_vec());
+	data.extend(fee_lock_script_hash.to_vec());
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let data = 9_000u128.to_le_bytes().to_vec();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(fee_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	let data = 98u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 2u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 8_900u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.cell_dep(token_buy_lock_dep)
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Run
+	let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+	assert_error_eq!(err, ScriptError::ValidationFailure(ERROR_DATA_LEN).input_lock_script(0));
+}
+
+#[test]
+fn test_buy_auction()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_buy_lock = context.deploy_cell(Loader::default().load_binary("token-buy-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_buy_lock_dep = CellDep::new_builder().out_point(out_point_token_buy_lock.clone()).build();
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	// let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Token Buy Lock Script
+	let script_args: Bytes = sudt_type_script_hash.to_vec().into();
+	let token_buy_lock_script = context.build_script(&out_point_token_buy_lock, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let mut data = vec!();
+	data.extend(buyer_lock_script_hash.to_vec());
+	data.extend(1_000u128.to_le_bytes().to_vec());
+	data.extend(FLAGS_MARKER.to_vec());
+	data.extend(FLAG_AUCTION.to_le_bytes().to_vec());
+	data.extend(500u128.to_le_bytes().to_vec());
+	data.extend(vec![AUCTION_METRIC_BLOCK_NUMBER]);
+	data.extend(100u64.to_le_bytes().to_vec());
+	data.extend(200u64.to_le_bytes().to_vec());
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let data = 9_000u128.to_le_bytes().to_vec();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	let data = 750u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 8_250u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+
+	// Prepare Header Deps
+	let header = HeaderBuilder::default().number(150u64.pack()).build();
+	context.insert_header(header.clone());
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.cell_dep(token_buy_lock_dep)
+		.header_dep(header.hash())
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Run
+	let _cycles = context.verify_tx(&tx, MAX_CYCLES).expect("pass verification");
+	// println!("consume cycles: {}", cycles);
+}
+
+#[test]
+fn test_buy_auction_short()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_buy_lock = context.deploy_cell(Loader::default().load_binary("token-buy-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_buy_lock_dep = CellDep::new_builder().out_point(out_point_token_buy_lock.clone()).build();
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	// let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Token Buy Lock Script
+	let script_args: Bytes = sudt_type_script_hash.to_vec().into();
+	let token_buy_lock_script = context.build_script(&out_point_token_buy_lock, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let mut data = vec!();
+	data.extend(buyer_lock_script_hash.to_vec());
+	data.extend(1_000u128.to_le_bytes().to_vec());
+	data.extend(FLAGS_MARKER.to_vec());
+	data.extend(FLAG_AUCTION.to_le_bytes().to_vec());
+	data.extend(500u128.to_le_bytes().to_vec());
+	data.extend(vec![AUCTION_METRIC_BLOCK_NUMBER]);
+	data.extend(100u64.to_le_bytes().to_vec());
+	data.extend(200u64.to_le_bytes().to_vec());
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let data = 9_000u128.to_le_bytes().to_vec();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	let data = 749u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 8_251u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+
+	// Prepare Header Deps
+	let header = HeaderBuilder::default().number(150u64.pack()).build();
+	context.insert_header(header.clone());
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.cell_dep(token_buy_lock_dep)
+		.header_dep(header.hash())
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Run
+	let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+	assert_error_eq!(err, ScriptError::ValidationFailure(ERROR_AMOUNT).input_lock_script(0));
+}
+
+#[test]
+fn test_buy_auction_rounding()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_buy_lock = context.deploy_cell(Loader::default().load_binary("token-buy-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_buy_lock_dep = CellDep::new_builder().out_point(out_point_token_buy_lock.clone()).build();
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	// let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Token Buy Lock Script
+	let script_args: Bytes = sudt_type_script_hash.to_vec().into();
+	let token_buy_lock_script = context.build_script(&out_point_token_buy_lock, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let mut data = vec!();
+	data.extend(buyer_lock_script_hash.to_vec());
+	data.extend(1_000u128.to_le_bytes().to_vec());
+	data.extend(FLAGS_MARKER.to_vec());
+	data.extend(FLAG_AUCTION.to_le_bytes().to_vec());
+	data.extend(500u128.to_le_bytes().to_vec());
+	data.extend(vec![AUCTION_METRIC_BLOCK_NUMBER]);
+	data.extend(100u64.to_le_bytes().to_vec());
+	data.extend(103u64.to_le_bytes().to_vec());
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let data = 9_000u128.to_le_bytes().to_vec();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	let data = 667u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 8_333u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+
+	// Prepare Header Deps
+	let header = HeaderBuilder::default().number(101u64.pack()).build();
+	context.insert_header(header.clone());
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.cell_dep(token_buy_lock_dep)
+		.header_dep(header.hash())
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Run
+	let _cycles = context.verify_tx(&tx, MAX_CYCLES).expect("pass verification");
+	// println!("consume cycles: {}", cycles);
+}
+
+#[test]
+fn test_buy_auction_rounding_short()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_buy_lock = context.deploy_cell(Loader::default().load_binary("token-buy-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_buy_lock_dep = CellDep::new_builder().out_point(out_point_token_buy_lock.clone()).build();
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	// let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Token Buy Lock Script
+	let script_args: Bytes = sudt_type_script_hash.to_vec().into();
+	let token_buy_lock_script = context.build_script(&out_point_token_buy_lock, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let mut data = vec!();
+	data.extend(buyer_lock_script_hash.to_vec());
+	data.extend(1_000u128.to_le_bytes().to_vec());
+	data.extend(FLAGS_MARKER.to_vec());
+	data.extend(FLAG_AUCTION.to_le_bytes().to_vec());
+	data.extend(500u128.to_le_bytes().to_vec());
+	data.extend(vec![AUCTION_METRIC_BLOCK_NUMBER]);
+	data.extend(100u64.to_le_bytes().to_vec());
+	data.extend(103u64.to_le_bytes().to_vec());
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let data = 9_000u128.to_le_bytes().to_vec();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	let data = 666u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 8_334u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+
+	// Prepare Header Deps
+	let header = HeaderBuilder::default().number(101u64.pack()).build();
+	context.insert_header(header.clone());
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.cell_dep(token_buy_lock_dep)
+		.header_dep(header.hash())
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Run
+	let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+	assert_error_eq!(err, ScriptError::ValidationFailure(ERROR_AMOUNT).input_lock_script(0));
+}
+
+#[test]
+fn test_buy_auction_no_header()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_buy_lock = context.deploy_cell(Loader::default().load_binary("token-buy-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_buy_lock_dep = CellDep::new_builder().out_point(out_point_token_buy_lock.clone()).build();
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	// let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Token Buy Lock Script
+	let script_args: Bytes = sudt_type_script_hash.to_vec().into();
+	let token_buy_lock_script = context.build_script(&out_point_token_buy_lock, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let mut data = vec!();
+	data.extend(buyer_lock_script_hash.to_vec());
+	data.extend(1_000u128.to_le_bytes().to_vec());
+	data.extend(FLAGS_MARKER.to_vec());
+	data.extend(FLAG_AUCTION.to_le_bytes().to_vec());
+	data.extend(500u128.to_le_bytes().to_vec());
+	data.extend(vec![AUCTION_METRIC_BLOCK_NUMBER]);
+	data.extend(100u64.to_le_bytes().to_vec());
+	data.extend(200u64.to_le_bytes().to_vec());
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let data = 9_000u128.to_le_bytes().to_vec();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	let data = 750u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 8_250u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.cell_dep(token_buy_lock_dep)
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Run
+	let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+	assert_error_eq!(err, ScriptError::ValidationFailure(ERROR_AMOUNT).input_lock_script(0));
+}
+
+#[test]
+fn test_buy_auction_before_start()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_buy_lock = context.deploy_cell(Loader::default().load_binary("token-buy-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_buy_lock_dep = CellDep::new_builder().out_point(out_point_token_buy_lock.clone()).build();
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	// let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Token Buy Lock Script
+	let script_args: Bytes = sudt_type_script_hash.to_vec().into();
+	let token_buy_lock_script = context.build_script(&out_point_token_buy_lock, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let mut data = vec!();
+	data.extend(buyer_lock_script_hash.to_vec());
+	data.extend(1_000u128.to_le_bytes().to_vec());
+	data.extend(FLAGS_MARKER.to_vec());
+	data.extend(FLAG_AUCTION.to_le_bytes().to_vec());
+	data.extend(500u128.to_le_bytes().to_vec());
+	data.extend(vec![AUCTION_METRIC_BLOCK_NUMBER]);
+	data.extend(100u64.to_le_bytes().to_vec());
+	data.extend(200u64.to_le_bytes().to_vec());
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let data = 9_000u128.to_le_bytes().to_vec();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	let data = 1_000u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 8_000u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+
+	// Prepare Header Deps
+	let header = HeaderBuilder::default().number(50u64.pack()).build();
+	context.insert_header(header.clone());
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.cell_dep(token_buy_lock_dep)
+		.header_dep(header.hash())
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Run
+	let _cycles = context.verify_tx(&tx, MAX_CYCLES).expect("pass verification");
+	// println!("consume cycles: {}", cycles);
+}
+
+#[test]
+fn test_buy_auction_ended()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_buy_lock = context.deploy_cell(Loader::default().load_binary("token-buy-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_buy_lock_dep = CellDep::new_builder().out_point(out_point_token_buy_lock.clone()).build();
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	// let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Token Buy Lock Script
+	let script_args: Bytes = sudt_type_script_hash.to_vec().into();
+	let token_buy_lock_script = context.build_script(&out_point_token_buy_lock, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let mut data = vec!();
+	data.extend(buyer_lock_script_hash.to_vec());
+	data.extend(1_000u128.to_le_bytes().to_vec());
+	data.extend(FLAGS_MARKER.to_vec());
+	data.extend(FLAG_AUCTION.to_le_bytes().to_vec());
+	data.extend(500u128.to_le_bytes().to_vec());
+	data.extend(vec![AUCTION_METRIC_BLOCK_NUMBER]);
+	data.extend(100u64.to_le_bytes().to_vec());
+	data.extend(200u64.to_le_bytes().to_vec());
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let data = 9_000u128.to_le_bytes().to_vec();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	let data = 500u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 8_500u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+
+	// Prepare Header Deps
+	let header = HeaderBuilder::default().number(300u64.pack()).build();
+	context.insert_header(header.clone());
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.cell_dep(token_buy_lock_dep)
+		.header_dep(header.hash())
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Run
+	let _cycles = context.verify_tx(&tx, MAX_CYCLES).expect("pass verification");
+	// println!("consume cycles: {}", cycles);
+}
+
+#[test]
+fn test_buy_auction_latest_header()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_buy_lock = context.deploy_cell(Loader::default().load_binary("token-buy-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_buy_lock_dep = CellDep::new_builder().out_point(out_point_token_buy_lock.clone()).build();
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	// let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Token Buy Lock Script
+	let script_args: Bytes = sudt_type_script_hash.to_vec().into();
+	let token_buy_lock_script = context.build_script(&out_point_token_buy_lock, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let mut data = vec!();
+	data.extend(buyer_lock_script_hash.to_vec());
+	data.extend(1_000u128.to_le_bytes().to_vec());
+	data.extend(FLAGS_MARKER.to_vec());
+	data.extend(FLAG_AUCTION.to_le_bytes().to_vec());
+	data.extend(500u128.to_le_bytes().to_vec());
+	data.extend(vec![AUCTION_METRIC_BLOCK_NUMBER]);
+	data.extend(100u64.to_le_bytes().to_vec());
+	data.extend(200u64.to_le_bytes().to_vec());
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let data = 9_000u128.to_le_bytes().to_vec();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	let data = 750u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 8_250u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+
+	// Prepare Header Deps
+	let header1 = HeaderBuilder::default().number(120u64.pack()).build();
+	context.insert_header(header1.clone());
+	let header2 = HeaderBuilder::default().number(150u64.pack()).build();
+	context.insert_header(header2.clone());
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.cell_dep(token_buy_lock_dep)
+		.header_dep(header1.hash())
+		.header_dep(header2.hash())
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Run
+	let _cycles = context.verify_tx(&tx, MAX_CYCLES).expect("pass verification");
+	// println!("consume cycles: {}", cycles);
+}
+
+#[test]
+fn test_buy_auction_timestamp()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_buy_lock = context.deploy_cell(Loader::default().load_binary("token-buy-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_buy_lock_dep = CellDep::new_builder().out_point(out_point_token_buy_lock.clone()).build();
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	// let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Token Buy Lock Script
+	let script_args: Bytes = sudt_type_script_hash.to_vec().into();
+	let token_buy_lock_script = context.build_script(&out_point_token_buy_lock, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let mut data = vec!();
+	data.extend(buyer_lock_script_hash.to_vec());
+	data.extend(1_000u128.to_le_bytes().to_vec());
+	data.extend(FLAGS_MARKER.to_vec());
+	data.extend(FLAG_AUCTION.to_le_bytes().to_vec());
+	data.extend(500u128.to_le_bytes().to_vec());
+	data.extend(vec![AUCTION_METRIC_TIMESTAMP]);
+	data.extend(1_000_000u64.to_le_bytes().to_vec());
+	data.extend(2_000_000u64.to_le_bytes().to_vec());
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let data = 9_000u128.to_le_bytes().to_vec();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	let data = 750u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 8_250u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+
+	// Prepare Header Deps
+	let header = HeaderBuilder::default().timestamp(1_500_000u64.pack()).build();
+	context.insert_header(header.clone());
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.cell_dep(token_buy_lock_dep)
+		.header_dep(header.hash())
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Run
+	let _cycles = context.verify_tx(&tx, MAX_CYCLES).expect("pass verification");
+	// println!("consume cycles: {}", cycles);
+}
+
+#[test]
+fn test_buy_auction_rising()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_buy_lock = context.deploy_cell(Loader::default().load_binary("token-buy-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_buy_lock_dep = CellDep::new_builder().out_point(out_point_token_buy_lock.clone()).build();
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	// let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Token Buy Lock Script
+	let script_args: Bytes = sudt_type_script_hash.to_vec().into();
+	let token_buy_lock_script = context.build_script(&out_point_token_buy_lock, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let mut data = vec!();
+	data.extend(buyer_lock_script_hash.to_vec());
+	data.extend(1_000u128.to_le_bytes().to_vec());
+	data.extend(FLAGS_MARKER.to_vec());
+	data.extend(FLAG_AUCTION.to_le_bytes().to_vec());
+	data.extend(1_500u128.to_le_bytes().to_vec());
+	data.extend(vec![AUCTION_METRIC_BLOCK_NUMBER]);
+	data.extend(100u64.to_le_bytes().to_vec());
+	data.extend(200u64.to_le_bytes().to_vec());
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let data = 9_000u128.to_le_bytes().to_vec();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	let data = 1_000u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 8_000u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+
+	// Prepare Header Deps
+	let header = HeaderBuilder::default().number(150u64.pack()).build();
+	context.insert_header(header.clone());
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.cell_dep(token_buy_lock_dep)
+		.header_dep(header.hash())
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Run
+	let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+	assert_error_eq!(err, ScriptError::ValidationFailure(ERROR_ENCODING).input_lock_script(0));
+}
+
+#[test]
+fn test_buy_auction_partial_fill()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_buy_lock = context.deploy_cell(Loader::default().load_binary("token-buy-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_buy_lock_dep = CellDep::new_builder().out_point(out_point_token_buy_lock.clone()).build();
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	// let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Token Buy Lock Script
+	let script_args: Bytes = sudt_type_script_hash.to_vec().into();
+	let token_buy_lock_script = context.build_script(&out_point_token_buy_lock, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let mut data = vec!();
+	data.extend(buyer_lock_script_hash.to_vec());
+	data.extend(1_000u128.to_le_bytes().to_vec());
+	data.extend(FLAGS_MARKER.to_vec());
+	data.extend(FLAG_AUCTION | FLAG_PARTIAL_FILL.to_le_bytes().to_vec());
+	data.extend(500u128.to_le_bytes().to_vec());
+	data.extend(vec![AUCTION_METRIC_BLOCK_NUMBER]);
+	data.extend(100u64.to_le_bytes().to_vec());
+	data.extend(200u64.to_le_bytes().to_vec());
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let data = 9_000u128.to_le_bytes().to_vec();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	let data = 750u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 8_250u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+
+	// Prepare Header Deps
+	let header = HeaderBuilder::default().number(150u64.pack()).build();
+	context.insert_header(header.clone());
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.cell_dep(token_buy_lock_dep)
+		.header_dep(header.hash())
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Run
+	let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+	assert_error_eq!(err, ScriptError::ValidationFailure(ERROR_ENCODING).input_lock_script(0));
+}
+
+#[test]
+fn test_buy_allowlist()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_buy_lock = context.deploy_cell(Loader::default().load_binary("token-buy-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_buy_lock_dep = CellDep::new_builder().out_point(out_point_token_buy_lock.clone()).build();
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
+	let other_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![2u8; 1])).expect("script");
+	let other_lock_script_hash: [u8; 32] = other_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Allowlist
+	let mut allowlist = vec![seller_lock_script_hash, other_lock_script_hash];
+	allowlist.sort();
+	let allowlist_root = blake2b_256([allowlist[0], allowlist[1]].concat());
+
+	// Prepare Token Buy Lock Script
+	let script_args: Bytes = sudt_type_script_hash.to_vec().into();
+	let token_buy_lock_script = context.build_script(&out_point_token_buy_lock, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let mut data = vec!();
+	data.extend(buyer_lock_script_hash.to_vec());
+	data.extend(100u128.to_le_bytes().to_vec());
+	data.extend(FLAGS_MARKER.to_vec());
+	data.extend(FLAG_ALLOWLIST.to_le_bytes().to_vec());
+	data.extend(allowlist_root.to_vec());
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let data = 9_000u128.to_le_bytes().to_vec();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	let data = 100u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 8_900u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+
+	// Prepare Witnesses
+	let mut proof = vec!();
+	proof.extend(seller_lock_script_hash.to_vec());
+	proof.extend(other_lock_script_hash.to_vec());
+	let witness = WitnessArgs::new_builder().lock(Some(Bytes::from(proof)).pack()).build();
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.cell_dep(token_buy_lock_dep)
+		.witness(witness.as_bytes().pack())
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Run
+	let _cycles = context.verify_tx(&tx, MAX_CYCLES).expect("pass verification");
+	// println!("consume cycles: {}", cycles);
+}
+
+#[test]
+fn test_buy_allowlist_single()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_buy_lock = context.deploy_cell(Loader::default().load_binary("token-buy-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_buy_lock_dep = CellDep::new_builder().out_point(out_point_token_buy_lock.clone()).build();
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
+	// let other_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![2u8; 1])).expect("script");
+	// let other_lock_script_hash: [u8; 32] = other_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Allowlist
+	let allowlist_root = seller_lock_script_hash;
+
+	// Prepare Token Buy Lock Script
+	let script_args: Bytes = sudt_type_script_hash.to_vec().into();
+	let token_buy_lock_script = context.build_script(&out_point_token_buy_lock, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let mut data = vec!();
+	data.extend(buyer_lock_script_hash.to_vec());
+	data.extend(100u128.to_le_bytes().to_vec());
+	data.extend(FLAGS_MARKER.to_vec());
+	data.extend(FLAG_ALLOWLIST.to_le_bytes().to_vec());
+	data.extend(allowlist_root.to_vec());
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let data = 9_000u128.to_le_bytes().to_vec();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	let data = 100u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 8_900u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+
+	// Prepare Witnesses
+	let mut proof = vec!();
+	proof.extend(seller_lock_script_hash.to_vec());
+	let witness = WitnessArgs::new_builder().lock(Some(Bytes::from(proof)).pack()).build();
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.cell_dep(token_buy_lock_dep)
+		.witness(witness.as_bytes().pack())
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Run
+	let _cycles = context.verify_tx(&tx, MAX_CYCLES).expect("pass verification");
+	// println!("consume cycles: {}", cycles);
+}
+
+#[test]
+fn test_buy_allowlist_not_in_inputs()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_buy_lock = context.deploy_cell(Loader::default().load_binary("token-buy-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_buy_lock_dep = CellDep::new_builder().out_point(out_point_token_buy_lock.clone()).build();
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
+	let other_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![2u8; 1])).expect("script");
+	let other_lock_script_hash: [u8; 32] = other_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Allowlist
+	let mut allowlist = vec![seller_lock_script_hash, other_lock_script_hash];
+	allowlist.sort();
+	let allowlist_root = blake2b_256([allowlist[0], allowlist[1]].concat());
+
+	// Prepare Token Buy Lock Script
+	let script_args: Bytes = sudt_type_script_hash.to_vec().into();
+	let token_buy_lock_script = context.build_script(&out_point_token_buy_lock, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let mut data = vec!();
+	data.extend(buyer_lock_script_hash.to_vec());
+	data.extend(100u128.to_le_bytes().to_vec());
+	data.extend(FLAGS_MARKER.to_vec());
+	data.extend(FLAG_ALLOWLIST.to_le_bytes().to_vec());
+	data.extend(allowlist_root.to_vec());
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let data = 9_000u128.to_le_bytes().to_vec();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	let data = 100u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 8_900u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+
+	// Prepare Witnesses
+	let mut proof = vec!();
+	proof.extend(other_lock_script_hash.to_vec());
+	proof.extend(seller_lock_script_hash.to_vec());
+	let witness = WitnessArgs::new_builder().lock(Some(Bytes::from(proof)).pack()).build();
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.cell_dep(token_buy_lock_dep)
+		.witness(witness.as_bytes().pack())
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Run
+	let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+	assert_error_eq!(err, ScriptError::ValidationFailure(ERROR_ALLOWLIST).input_lock_script(0));
+}
+
+#[test]
+fn test_buy_allowlist_invalid_proof()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_buy_lock = context.deploy_cell(Loader::default().load_binary("token-buy-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_buy_lock_dep = CellDep::new_builder().out_point(out_point_token_buy_lock.clone()).build();
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
+	let other_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![2u8; 1])).expect("script");
+	let other_lock_script_hash: [u8; 32] = other_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Allowlist
+	let mut allowlist = vec![seller_lock_script_hash, other_lock_script_hash];
+	allowlist.sort();
+	let allowlist_root = blake2b_256([allowlist[0], allowlist[1]].concat());
+
+	// Prepare Token Buy Lock Script
+	let script_args: Bytes = sudt_type_script_hash.to_vec().into();
+	let token_buy_lock_script = context.build_script(&out_point_token_buy_lock, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let mut data = vec!();
+	data.extend(buyer_lock_script_hash.to_vec());
+	data.extend(100u128.to_le_bytes().to_vec());
+	data.extend(FLAGS_MARKER.to_vec());
+	data.extend(FLAG_ALLOWLIST.to_le_bytes().to_vec());
+	data.extend(allowlist_root.to_vec());
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let data = 9_000u128.to_le_bytes().to_vec();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	let data = 100u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 8_900u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+
+	// Prepare Witnesses
+	let mut proof = vec!();
+	proof.extend(seller_lock_script_hash.to_vec());
+	proof.extend(seller_lock_script_hash.to_vec());
+	let witness = WitnessArgs::new_builder().lock(Some(Bytes::from(proof)).pack()).build();
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.cell_dep(token_buy_lock_dep)
+		.witness(witness.as_bytes().pack())
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Run
+	let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+	assert_error_eq!(err, ScriptError::ValidationFailure(ERROR_ALLOWLIST).input_lock_script(0));
+}
+
+#[test]
+fn test_buy_allowlist_missing_witness()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_buy_lock = context.deploy_cell(Loader::default().load_binary("token-buy-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_buy_lock_dep = CellDep::new_builder().out_point(out_point_token_buy_lock.clone()).build();
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
+	let other_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![2u8; 1])).expect("script");
+	let other_lock_script_hash: [u8; 32] = other_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Allowlist
+	let mut allowlist = vec![seller_lock_script_hash, other_lock_script_hash];
+	allowlist.sort();
+	let allowlist_root = blake2b_256([allowlist[0], allowlist[1]].concat());
+
+	// Prepare Token Buy Lock Script
+	let script_args: Bytes = sudt_type_script_hash.to_vec().into();
+	let token_buy_lock_script = context.build_script(&out_point_token_buy_lock, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let mut data = vec!();
+	data.extend(buyer_lock_script_hash.to_vec());
+	data.extend(100u128.to_le_bytes().to_vec());
+	data.extend(FLAGS_MARKER.to_vec());
+	data.extend(FLAG_ALLOWLIST.to_le_bytes().to_vec());
+	data.extend(allowlist_root.to_vec());
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let data = 9_000u128.to_le_bytes().to_vec();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	let data = 100u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 8_900u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.cell_dep(token_buy_lock_dep)
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Run
+	let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+	assert_error_eq!(err, ScriptError::ValidationFailure(ERROR_ALLOWLIST).input_lock_script(0));
+}
+
+#[test]
+fn test_buy_allowlist_invalid_proof_length()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_buy_lock = context.deploy_cell(Loader::default().load_binary("token-buy-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_buy_lock_dep = CellDep::new_builder().out_point(out_point_token_buy_lock.clone()).build();
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
+	let other_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![2u8; 1])).expect("script");
+	let other_lock_script_hash: [u8; 32] = other_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Allowlist
+	let mut allowlist = vec![seller_lock_script_hash, other_lock_script_hash];
+	allowlist.sort();
+	let allowlist_root = blake2b_256([allowlist[0], allowlist[1]].concat());
+
+	// Prepare Token Buy Lock Script
+	let script_args: Bytes = sudt_type_script_hash.to_vec().into();
+	let token_buy_lock_script = context.build_script(&out_point_token_buy_lock, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let mut data = vec!();
+	data.extend(buyer_lock_script_hash.to_vec());
+	data.extend(100u128.to_le_bytes().to_vec());
+	data.extend(FLAGS_MARKER.to_vec());
+	data.extend(FLAG_ALLOWLIST.to_le_bytes().to_vec());
+	data.extend(allowlist_root.to_vec());
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let data = 9_000u128.to_le_bytes().to_vec();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	let data = 100u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 8_900u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+
+	// Prepare Witnesses
+	let mut proof = vec!();
+	proof.extend(seller_lock_script_hash.to_vec());
+	proof.extend([0u8; 16].to_vec());
+	let witness = WitnessArgs::new_builder().lock(Some(Bytes::from(proof)).pack()).build();
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.cell_dep(token_buy_lock_dep)
+		.witness(witness.as_bytes().pack())
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Run
+	let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+	assert_error_eq!(err, ScriptError::ValidationFailure(ERROR_ENCODING).input_lock_script(0));
+}
+
+#[test]
+fn test_buy_oracle()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_buy_lock = context.deploy_cell(Loader::default().load_binary("token-buy-lock"));
+	let out_point_mock_oracle = context.deploy_cell(Loader::default().load_binary("mock-oracle"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_buy_lock_dep = CellDep::new_builder().out_point(out_point_token_buy_lock.clone()).build();
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	// let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Mock Oracle Type Script
+	let oracle_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![4u8; 1])).expect("script");
+	let oracle_lock_script_hash: [u8; 32] = oracle_lock_script.calc_script_hash().unpack();
+	let script_args: Bytes = oracle_lock_script_hash.to_vec().into();
+	let oracle_type_script = context.build_script(&out_point_mock_oracle, script_args).expect("script");
+	let oracle_type_script_hash: [u8; 32] = oracle_type_script.calc_script_hash().unpack();
+
+	// Prepare Token Buy Lock Script
+	let script_args: Bytes = sudt_type_script_hash.to_vec().into();
+	let token_buy_lock_script = context.build_script(&out_point_token_buy_lock, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let mut data = vec!();
+	data.extend(buyer_lock_script_hash.to_vec());
+	data.extend(0u128.to_le_bytes().to_vec());
+	data.extend(FLAGS_MARKER.to_vec());
+	data.extend(FLAG_ORACLE.to_le_bytes().to_vec());
+	data.extend(oracle_type_script_hash.to_vec());
+	data.extend(0u16.to_le_bytes().to_vec());
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let data = 9_000u128.to_le_bytes().to_vec();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	let data = 100u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 8_900u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+
+	// Prepare Oracle Cell Deps
+	let mut data = vec!();
+	data.extend(1u128.to_le_bytes().to_vec());
+	data.extend(1_000_000_000u128.to_le_bytes().to_vec());
+	let oracle_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(oracle_lock_script.clone()).type_(Some(oracle_type_script.clone()).pack()).build(), Bytes::from(data));
+	let oracle_dep = CellDep::new_builder().out_point(oracle_out_point).build();
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.cell_dep(token_buy_lock_dep)
+		.cell_dep(oracle_dep)
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Run
+	let _cycles = context.verify_tx(&tx, MAX_CYCLES).expect("pass verification");
+	// println!("consume cycles: {}", cycles);
+}
+
+#[test]
+fn test_buy_oracle_short()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_buy_lock = context.deploy_cell(Loader::default().load_binary("token-buy-lock"));
+	let out_point_mock_oracle = context.deploy_cell(Loader::default().load_binary("mock-oracle"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_buy_lock_dep = CellDep::new_builder().out_point(out_point_token_buy_lock.clone()).build();
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	// let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Mock Oracle Type Script
+	let oracle_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![4u8; 1])).expect("script");
+	let oracle_lock_script_hash: [u8; 32] = oracle_lock_script.calc_script_hash().unpack();
+	let script_args: Bytes = oracle_lock_script_hash.to_vec().into();
+	let oracle_type_script = context.build_script(&out_point_mock_oracle, script_args).expect("script");
+	let oracle_type_script_hash: [u8; 32] = oracle_type_script.calc_script_hash().unpack();
+
+	// Prepare Token Buy Lock Script
+	let script_args: Bytes = sudt_type_script_hash.to_vec().into();
+	let token_buy_lock_script = context.build_script(&out_point_token_buy_lock, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let mut data = vec!();
+	data.extend(buyer_lock_script_hash.to_vec());
+	data.extend(0u128.to_le_bytes().to_vec());
+	data.extend(FLAGS_MARKER.to_vec());
+	data.extend(FLAG_ORACLE.to_le_bytes().to_vec());
+	data.extend(oracle_type_script_hash.to_vec());
+	data.extend(0u16.to_le_bytes().to_vec());
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let data = 9_000u128.to_le_bytes().to_vec();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	let data = 99u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 8_901u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+
+	// Prepare Oracle Cell Deps
+	let mut data = vec!();
+	data.extend(1u128.to_le_bytes().to_vec());
+	data.extend(1_000_000_000u128.to_le_bytes().to_vec());
+	let oracle_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(oracle_lock_script.clone()).type_(Some(oracle_type_script.clone()).pack()).build(), Bytes::from(data));
+	let oracle_dep = CellDep::new_builder().out_point(oracle_out_point).build();
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.cell_dep(token_buy_lock_dep)
+		.cell_dep(oracle_dep)
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Run
+	let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+	assert_error_eq!(err, ScriptError::ValidationFailure(ERROR_AMOUNT).input_lock_script(0));
+}
+
+#[test]
+fn test_buy_oracle_spread()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_buy_lock = context.deploy_cell(Loader::default().load_binary("token-buy-lock"));
+	let out_point_mock_oracle = context.deploy_cell(Loader::default().load_binary("mock-oracle"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_buy_lock_dep = CellDep::new_builder().out_point(out_point_token_buy_lock.clone()).build();
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	// let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Mock Oracle Type Script
+	let oracle_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![4u8; 1])).expect("script");
+	let oracle_lock_script_hash: [u8; 32] = oracle_lock_script.calc_script_hash().unpack();
+	let script_args: Bytes = oracle_lock_script_hash.to_vec().into();
+	let oracle_type_script = context.build_script(&out_point_mock_oracle, script_args).expect("script");
+	let oracle_type_script_hash: [u8; 32] = oracle_type_script.calc_script_hash().unpack();
+
+	// Prepare Token Buy Lock Script
+	let script_args: Bytes = sudt_type_script_hash.to_vec().into();
+	let token_buy_lock_script = context.build_script(&out_point_token_buy_lock, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let mut data = vec!();
+	data.extend(buyer_lock_script_hash.to_vec());
+	data.extend(0u128.to_le_bytes().to_vec());
+	data.extend(FLAGS_MARKER.to_vec());
+	data.extend(FLAG_ORACLE.to_le_bytes().to_vec());
+	data.extend(oracle_type_script_hash.to_vec());
+	data.extend(250u16.to_le_bytes().to_vec());
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let data = 9_000u128.to_le_bytes().to_vec();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	let data = 103u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 8_897u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+
+	// Prepare Oracle Cell Deps
+	let mut data = vec!();
+	data.extend(1u128.to_le_bytes().to_vec());
+	data.extend(1_000_000_000u128.to_le_bytes().to_vec());
+	let oracle_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(oracle_lock_script.clone()).type_(Some(oracle_type_script.clone()).pack()).build(), Bytes::from(data));
+	let oracle_dep = CellDep::new_builder().out_point(oracle_out_point).build();
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.cell_dep(token_buy_lock_dep)
+		.cell_dep(oracle_dep)
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Run
+	let _cycles = context.verify_tx(&tx, MAX_CYCLES).expect("pass verification");
+	// println!("consume cycles: {}", cycles);
+}
+
+#[test]
+fn test_buy_oracle_spread_short()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_buy_lock = context.deploy_cell(Loader::default().load_binary("token-buy-lock"));
+	let out_point_mock_oracle = context.deploy_cell(Loader::default().load_binary("mock-oracle"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_buy_lock_dep = CellDep::new_builder().out_point(out_point_token_buy_lock.clone()).build();
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	// let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Mock Oracle Type Script
+	let oracle_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![4u8; 1])).expect("script");
+	let oracle_lock_script_hash: [u8; 32] = oracle_lock_script.calc_script_hash().unpack();
+	let script_args: Bytes = oracle_lock_script_hash.to_vec().into();
+	let oracle_type_script = context.build_script(&out_point_mock_oracle, script_args).expect("script");
+	let oracle_type_script_hash: [u8; 32] = oracle_type_script.calc_script_hash().unpack();
+
+	// Prepare Token Buy Lock Script
+	let script_args: Bytes = sudt_type_script_hash.to_vec().into();
+	let token_buy_lock_script = context.build_script(&out_point_token_buy_lock, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let mut data = vec!();
+	data.extend(buyer_lock_script_hash.to_vec());
+	data.extend(0u128.to_le_bytes().to_vec());
+	data.extend(FLAGS_MARKER.to_vec());
+	data.extend(FLAG_ORACLE.to_le_bytes().to_vec());
+	data.extend(oracle_type_script_hash.to_vec());
+	data.extend(250u16.to_le_bytes().to_vec());
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let data = 9_000u128.to_le_bytes().to_vec();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	let data = 102u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 8_898u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+
+	// Prepare Oracle Cell Deps
+	let mut data = vec!();
+	data.extend(1u128.to_le_bytes().to_vec());
+	data.extend(1_000_000_000u128.to_le_bytes().to_vec());
+	let oracle_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(oracle_lock_script.clone()).type_(Some(oracle_type_script.clone()).pack()).build(), Bytes::from(data));
+	let oracle_dep = CellDep::new_builder().out_point(oracle_out_point).build();
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.cell_dep(token_buy_lock_dep)
+		.cell_dep(oracle_dep)
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Run
+	let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+	assert_error_eq!(err, ScriptError::ValidationFailure(ERROR_AMOUNT).input_lock_script(0));
+}
+
+#[test]
+fn test_buy_oracle_missing()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_buy_lock = context.deploy_cell(Loader::default().load_binary("token-buy-lock"));
+	let out_point_mock_oracle = context.deploy_cell(Loader::default().load_binary("mock-oracle"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_buy_lock_dep = CellDep::new_builder().out_point(out_point_token_buy_lock.clone()).build();
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	// let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Mock Oracle Type Script
+	let oracle_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![4u8; 1])).expect("script");
+	let oracle_lock_script_hash: [u8; 32] = oracle_lock_script.calc_script_hash().unpack();
+	let script_args: Bytes = oracle_lock_script_hash.to_vec().into();
+	let oracle_type_script = context.build_script(&out_point_mock_oracle, script_args).expect("script");
+	let oracle_type_script_hash: [u8; 32] = oracle_type_script.calc_script_hash().unpack();
+
+	// Prepare Token Buy Lock Script
+	let script_args: Bytes = sudt_type_script_hash.to_vec().into();
+	let token_buy_lock_script = context.build_script(&out_point_token_buy_lock, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let mut data = vec!();
+	data.extend(buyer_lock_script_hash.to_vec());
+	data.extend(0u128.to_le_bytes().to_vec());
+	data.extend(FLAGS_MARKER.to_vec());
+	data.extend(FLAG_ORACLE.to_le_bytes().to_vec());
+	data.extend(oracle_type_script_hash.to_vec());
+	data.extend(0u16.to_le_bytes().to_vec());
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let data = 9_000u128.to_le_bytes().to_vec();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	let data = 100u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 8_900u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.cell_dep(token_buy_lock_dep)
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Run
+	let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+	assert_error_eq!(err, ScriptError::ValidationFailure(ERROR_ORACLE).input_lock_script(0));
+}
+
+#[test]
+fn test_buy_oracle_multiple()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_buy_lock = context.deploy_cell(Loader::default().load_binary("token-buy-lock"));
+	let out_point_mock_oracle = context.deploy_cell(Loader::default().load_binary("mock-oracle"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_buy_lock_dep = CellDep::new_builder().out_point(out_point_token_buy_lock.clone()).build();
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	// let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Mock Oracle Type Script
+	let oracle_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![4u8; 1])).expect("script");
+	let oracle_lock_script_hash: [u8; 32] = oracle_lock_script.calc_script_hash().unpack();
+	let script_args: Bytes = oracle_lock_script_hash.to_vec().into();
+	let oracle_type_script = context.build_script(&out_point_mock_oracle, script_args).expect("script");
+	let oracle_type_script_hash: [u8; 32] = oracle_type_script.calc_script_hash().unpack();
+
+	// Prepare Token Buy Lock Script
+	let script_args: Bytes = sudt_type_script_hash.to_vec().into();
+	let token_buy_lock_script = context.build_script(&out_point_token_buy_lock, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let mut data = vec!();
+	data.extend(buyer_lock_script_hash.to_vec());
+	data.extend(0u128.to_le_bytes().to_vec());
+	data.extend(FLAGS_MARKER.to_vec());
+	data.extend(FLAG_ORACLE.to_le_bytes().to_vec());
+	data.extend(oracle_type_script_hash.to_vec());
+	data.extend(0u16.to_le_bytes().to_vec());
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let data = 9_000u128.to_le_bytes().to_vec();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	let data = 100u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 8_900u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+
+	// Prepare Oracle Cell Deps
+	let mut data = vec!();
+	data.extend(1u128.to_le_bytes().to_vec());
+	data.extend(1_000_000_000u128.to_le_bytes().to_vec());
+	let oracle_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(oracle_lock_script.clone()).type_(Some(oracle_type_script.clone()).pack()).build(), Bytes::from(data));
+	let oracle_dep = CellDep::new_builder().out_point(oracle_out_point).build();
+	let mut data = vec!();
+	data.extend(1u128.to_le_bytes().to_vec());
+	data.extend(2_000_000_000u128.to_le_bytes().to_vec());
+	let oracle_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(oracle_lock_script.clone()).type_(Some(oracle_type_script.clone()).pack()).build(), Bytes::from(data));
+	let oracle_dep2 = CellDep::new_builder().out_point(oracle_out_point).build();
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.cell_dep(token_buy_lock_dep)
+		.cell_dep(oracle_dep)
+		.cell_dep(oracle_dep2)
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Run
+	let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+	assert_error_eq!(err, ScriptError::ValidationFailure(ERROR_ORACLE).input_lock_script(0));
+}
+
+#[test]
+fn test_buy_oracle_zero_denominator()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_buy_lock = context.deploy_cell(Loader::default().load_binary("token-buy-lock"));
+	let out_point_mock_oracle = context.deploy_cell(Loader::default().load_binary("mock-oracle"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_buy_lock_dep = CellDep::new_builder().out_point(out_point_token_buy_lock.clone()).build();
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	// let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Mock Oracle Type Script
+	let oracle_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![4u8; 1])).expect("script");
+	let oracle_lock_script_hash: [u8; 32] = oracle_lock_script.calc_script_hash().unpack();
+	let script_args: Bytes = oracle_lock_script_hash.to_vec().into();
+	let oracle_type_script = context.build_script(&out_point_mock_oracle, script_args).expect("script");
+	let oracle_type_script_hash: [u8; 32] = oracle_type_script.calc_script_hash().unpack();
+
+	// Prepare Token Buy Lock Script
+	let script_args: Bytes = sudt_type_script_hash.to_vec().into();
+	let token_buy_lock_script = context.build_script(&out_point_token_buy_lock, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let mut data = vec!();
+	data.extend(buyer_lock_script_hash.to_vec());
+	data.extend(0u128.to_le_bytes().to_vec());
+	data.extend(FLAGS_MARKER.to_vec());
+	data.extend(FLAG_ORACLE.to_le_bytes().to_vec());
+	data.extend(oracle_type_script_hash.to_vec());
+	data.extend(0u16.to_le_bytes().to_vec());
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let data = 9_000u128.to_le_bytes().to_vec();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	let data = 100u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 8_900u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+
+	// Prepare Oracle Cell Deps
+	let mut data = vec!();
+	data.extend(1u128.to_le_bytes().to_vec());
+	data.extend(0u128.to_le_bytes().to_vec());
+	let oracle_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(oracle_lock_script.clone()).type_(Some(oracle_type_script.clone()).pack()).build(), Bytes::from(data));
+	let oracle_dep = CellDep::new_builder().out_point(oracle_out_point).build();
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.cell_dep(token_buy_lock_dep)
+		.cell_dep(oracle_dep)
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Run
+	let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+	assert_error_eq!(err, ScriptError::ValidationFailure(ERROR_ORACLE).input_lock_script(0));
+}
+
+#[test]
+fn test_buy_oracle_with_price()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_buy_lock = context.deploy_cell(Loader::default().load_binary("token-buy-lock"));
+	let out_point_mock_oracle = context.deploy_cell(Loader::default().load_binary("mock-oracle"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_buy_lock_dep = CellDep::new_builder().out_point(out_point_token_buy_lock.clone()).build();
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	// let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Mock Oracle Type Script
+	let oracle_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![4u8; 1])).expect("script");
+	let oracle_lock_script_hash: [u8; 32] = oracle_lock_script.calc_script_hash().unpack();
+	let script_args: Bytes = oracle_lock_script_hash.to_vec().into();
+	let oracle_type_script = context.build_script(&out_point_mock_oracle, script_args).expect("script");
+	let oracle_type_script_hash: [u8; 32] = oracle_type_script.calc_script_hash().unpack();
+
+	// Prepare Token Buy Lock Script
+	let script_args: Bytes = sudt_type_script_hash.to_vec().into();
+	let token_buy_lock_script = context.build_script(&out_point_token_buy_lock, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let mut data = vec!();
+	data.extend(buyer_lock_script_hash.to_vec());
+	data.extend(0u128.to_le_bytes().to_vec());
+	data.extend(FLAGS_MARKER.to_vec());
+	data.extend(FLAG_ORACLE | FLAG_PRICE.to_le_bytes().to_vec());
+	data.extend(oracle_type_script_hash.to_vec());
+	data.extend(0u16.to_le_bytes().to_vec());
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let data = 9_000u128.to_le_bytes().to_vec();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	let data = 100u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 8_900u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+
+	// Prepare Oracle Cell Deps
+	let mut data = vec!();
+	data.extend(1u128.to_le_bytes().to_vec());
+	data.extend(1_000_000_000u128.to_le_bytes().to_vec());
+	let oracle_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(oracle_lock_script.clone()).type_(Some(oracle_type_script.clone()).pack()).build(), Bytes::from(data));
+	let oracle_dep = CellDep::new_builder().out_point(oracle_out_point).build();
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.cell_dep(token_buy_lock_dep)
+		.cell_dep(oracle_dep)
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Run
+	let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+	assert_error_eq!(err, ScriptError::ValidationFailure(ERROR_ENCODING).input_lock_script(0));
+}
+
+#[test]
+fn test_buy_bundle()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_buy_lock = context.deploy_cell(Loader::default().load_binary("token-buy-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_buy_lock_dep = CellDep::new_builder().out_point(out_point_token_buy_lock.clone()).build();
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	// let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Second SUDT Type Script
+	let sudt_owner_lock_script2 = context.build_script(&out_point_always_success, Bytes::from(vec![4u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash2: [u8; 32] = sudt_owner_lock_script2.calc_script_hash().unpack();
+	let script_args: Bytes = sudt_owner_lock_script_hash2.to_vec().into();
+	let sudt_type_script2 = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash2: [u8; 32] = sudt_type_script2.calc_script_hash().unpack();
+
+	// Prepare Token Buy Lock Script
+	let script_args: Bytes = sudt_type_script_hash.to_vec().into();
+	let token_buy_lock_script = context.build_script(&out_point_token_buy_lock, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let mut data = vec!();
+	data.extend(buyer_lock_script_hash.to_vec());
+	data.extend(100u128.to_le_bytes().to_vec());
+	data.extend(FLAGS_MARKER.to_vec());
+	data.extend(FLAG_BUNDLE.to_le_bytes().to_vec());
+	data.extend(vec![1u8]);
+	data.extend(sudt_type_script_hash2.to_vec());
+	data.extend(50u128.to_le_bytes().to_vec());
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let data = 9_000u128.to_le_bytes().to_vec();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let data = 1_000u128.to_le_bytes().to_vec();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script2.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(sudt_type_script2.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script2.clone()).pack()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	let data = 100u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 50u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 8_900u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 950u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.cell_dep(token_buy_lock_dep)
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Run
+	let _cycles = context.verify_tx(&tx, MAX_CYCLES).expect("pass verification");
+	// println!("consume cycles: {}", cycles);
+}
+
+#[test]
+fn test_buy_bundle_missing_token()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_buy_lock = context.deploy_cell(Loader::default().load_binary("token-buy-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_buy_lock_dep = CellDep::new_builder().out_point(out_point_token_buy_lock.clone()).build();
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	// let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Second SUDT Type Script
+	let sudt_owner_lock_script2 = context.build_script(&out_point_always_success, Bytes::from(vec![4u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash2: [u8; 32] = sudt_owner_lock_script2.calc_script_hash().unpack();
+	let script_args: Bytes = sudt_owner_lock_script_hash2.to_vec().into();
+	let sudt_type_script2 = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash2: [u8; 32] = sudt_type_script2.calc_script_hash().unpack();
+
+	// Prepare Token Buy Lock Script
+	let script_args: Bytes = sudt_type_script_hash.to_vec().into();
+	let token_buy_lock_script = context.build_script(&out_point_token_buy_lock, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let mut data = vec!();
+	data.extend(buyer_lock_script_hash.to_vec());
+	data.extend(100u128.to_le_bytes().to_vec());
+	data.extend(FLAGS_MARKER.to_vec());
+	data.extend(FLAG_BUNDLE.to_le_bytes().to_vec());
+	data.extend(vec![1u8]);
+	data.extend(sudt_type_script_hash2.to_vec());
+	data.extend(50u128.to_le_bytes().to_vec());
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let data = 9_000u128.to_le_bytes().to_vec();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let data = 1_000u128.to_le_bytes().to_vec();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script2.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script2.clone()).pack()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	let data = 100u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 8_900u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 1_000u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.cell_dep(token_buy_lock_dep)
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Run
+	let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+	assert_error_eq!(err, ScriptError::ValidationFailure(ERROR_AMOUNT).input_lock_script(0));
+}
+
+#[test]
+fn test_buy_bundle_short()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_buy_lock = context.deploy_cell(Loader::default().load_binary("token-buy-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_buy_lock_dep = CellDep::new_builder().out_point(out_point_token_buy_lock.clone()).build();
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	// let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Second SUDT Type Script
+	let sudt_owner_lock_script2 = context.build_script(&out_point_always_success, Bytes::from(vec![4u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash2: [u8; 32] = sudt_owner_lock_script2.calc_script_hash().unpack();
+	let script_args: Bytes = sudt_owner_lock_script_hash2.to_vec().into();
+	let sudt_type_script2 = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash2: [u8; 32] = sudt_type_script2.calc_script_hash().unpack();
+
+	// Prepare Token Buy Lock Script
+	let script_args: Bytes = sudt_type_script_hash.to_vec().into();
+	let token_buy_lock_script = context.build_script(&out_point_token_buy_lock, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let mut data = vec!();
+	data.extend(buyer_lock_script_hash.to_vec());
+	data.extend(100u128.to_le_bytes().to_vec());
+	data.extend(FLAGS_MARKER.to_vec());
+	data.extend(FLAG_BUNDLE.to_le_bytes().to_vec());
+	data.extend(vec![1u8]);
+	data.extend(sudt_type_script_hash2.to_vec());
+	data.extend(50u128.to_le_bytes().to_vec());
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let data = 9_000u128.to_le_bytes().to_vec();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let data = 1_000u128.to_le_bytes().to_vec();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script2.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(sudt_type_script2.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script2.clone()).pack()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	let data = 100u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 49u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 8_900u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 951u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.cell_dep(token_buy_lock_dep)
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Run
+	let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+	assert_error_eq!(err, ScriptError::ValidationFailure(ERROR_AMOUNT).input_lock_script(0));
+}
+
+#[test]
+fn test_buy_bundle_only()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_buy_lock = context.deploy_cell(Loader::default().load_binary("token-buy-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_buy_lock_dep = CellDep::new_builder().out_point(out_point_token_buy_lock.clone()).build();
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	// let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Second SUDT Type Script
+	let sudt_owner_lock_script2 = context.build_script(&out_point_always_success, Bytes::from(vec![4u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash2: [u8; 32] = sudt_owner_lock_script2.calc_script_hash().unpack();
+	let script_args: Bytes = sudt_owner_lock_script_hash2.to_vec().into();
+	let sudt_type_script2 = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash2: [u8; 32] = sudt_type_script2.calc_script_hash().unpack();
+
+	// Prepare Token Buy Lock Script
+	let script_args: Bytes = sudt_type_script_hash.to_vec().into();
+	let token_buy_lock_script = context.build_script(&out_point_token_buy_lock, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let mut data = vec!();
+	data.extend(buyer_lock_script_hash.to_vec());
+	data.extend(0u128.to_le_bytes().to_vec());
+	data.extend(FLAGS_MARKER.to_vec());
+	data.extend(FLAG_BUNDLE.to_le_bytes().to_vec());
+	data.extend(vec![1u8]);
+	data.extend(sudt_type_script_hash2.to_vec());
+	data.extend(50u128.to_le_bytes().to_vec());
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let data = 1_000u128.to_le_bytes().to_vec();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script2.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(sudt_type_script2.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script2.clone()).pack()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	let data = 50u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 950u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.cell_dep(token_buy_lock_dep)
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Run
+	let _cycles = context.verify_tx(&tx, MAX_CYCLES).expect("pass verification");
+	// println!("consume cycles: {}", cycles);
+}
+
+#[test]
+fn test_buy_bundle_data_short()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_buy_lock = context.deploy_cell(Loader::default().load_binary("token-buy-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_buy_lock_dep = CellDep::new_builder().out_point(out_point_token_buy_lock.clone()).build();
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	// let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Second SUDT Type Script
+	let sudt_owner_lock_script2 = context.build_script(&out_point_always_success, Bytes::from(vec![4u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash2: [u8; 32] = sudt_owner_lock_script2.calc_script_hash().unpack();
+	let script_args: Bytes = sudt_owner_lock_script_hash2.to_vec().into();
+	let sudt_type_script2 = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash2: [u8; 32] = sudt_type_script2.calc_script_hash().unpack();
+
+	// Prepare Token Buy Lock Script
+	let script_args: Bytes = sudt_type_script_hash.to_vec().into();
+	let token_buy_lock_script = context.build_script(&out_point_token_buy_lock, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let mut data = vec!();
+	data.extend(buyer_lock_script_hash.to_vec());
+	data.extend(100u128.to_le_bytes().to_vec());
+	data.extend(FLAGS_MARKER.to_vec());
+	data.extend(FLAG_BUNDLE.to_le_bytes().to_vec());
+	data.extend(vec![2u8]);
+	data.extend(sudt_type_script_hash2.to_vec());
+	data.extend(50u128.to_le_bytes().to_vec());
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let data = 9_000u128.to_le_bytes().to_vec();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let data = 1_000u128.to_le_bytes().to_vec();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script2.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(sudt_type_script2.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script2.clone()).pack()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	let data = 100u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 50u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 8_900u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 950u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.cell_dep(token_buy_lock_dep)
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Run
+	let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+	assert_error_eq!(err, ScriptError::ValidationFailure(ERROR_DATA_LEN).input_lock_script(0));
+}
+
+#[test]
+fn test_buy_bundle_empty()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_buy_lock = context.deploy_cell(Loader::default().load_binary("token-buy-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_buy_lock_dep = CellDep::new_builder().out_point(out_point_token_buy_lock.clone()).build();
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	// let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Second SUDT Type Script
+	let sudt_owner_lock_script2 = context.build_script(&out_point_always_success, Bytes::from(vec![4u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash2: [u8; 32] = sudt_owner_lock_script2.calc_script_hash().unpack();
+	let script_args: Bytes = sudt_owner_lock_script_hash2.to_vec().into();
+	let sudt_type_script2 = context.build_script(&out_point_sudt, script_args).expect("script");
+	// let sudt_type_script_hash2: [u8; 32] = sudt_type_script2.calc_script_hash().unpack();
+
+	// Prepare Token Buy Lock Script
+	let script_args: Bytes = sudt_type_script_hash.to_vec().into();
+	let token_buy_lock_script = context.build_script(&out_point_token_buy_lock, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let mut data = vec!();
+	data.extend(buyer_lock_script_hash.to_vec());
+	data.extend(100u128.to_le_bytes().to_vec());
+	data.extend(FLAGS_MARKER.to_vec());
+	data.extend(FLAG_BUNDLE.to_le_bytes().to_vec());
+	data.extend(vec![0u8]);
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let data = 9_000u128.to_le_bytes().to_vec();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let data = 1_000u128.to_le_bytes().to_vec();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script2.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(sudt_type_script2.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script2.clone()).pack()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	let data = 100u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 50u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 8_900u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 950u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.cell_dep(token_buy_lock_dep)
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Run
+	let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+	assert_error_eq!(err, ScriptError::ValidationFailure(ERROR_ENCODING).input_lock_script(0));
+}
+
+#[test]
+fn test_buy_bundle_partial_fill()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_buy_lock = context.deploy_cell(Loader::default().load_binary("token-buy-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_buy_lock_dep = CellDep::new_builder().out_point(out_point_token_buy_lock.clone()).build();
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	// let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Second SUDT Type Script
+	let sudt_owner_lock_script2 = context.build_script(&out_point_always_success, Bytes::from(vec![4u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash2: [u8; 32] = sudt_owner_lock_script2.calc_script_hash().unpack();
+	let script_args: Bytes = sudt_owner_lock_script_hash2.to_vec().into();
+	let sudt_type_script2 = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash2: [u8; 32] = sudt_type_script2.calc_script_hash().unpack();
+
+	// Prepare Token Buy Lock Script
+	let script_args: Bytes = sudt_type_script_hash.to_vec().into();
+	let token_buy_lock_script = context.build_script(&out_point_token_buy_lock, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let mut data = vec!();
+	data.extend(buyer_lock_script_hash.to_vec());
+	data.extend(100u128.to_le_bytes().to_vec());
+	data.extend(FLAGS_MARKER.to_vec());
+	data.extend(FLAG_BUNDLE | FLAG_PARTIAL_FILL.to_le_bytes().to_vec());
+	data.extend(vec![1u8]);
+	data.extend(sudt_type_script_hash2.to_vec());
+	data.extend(50u128.to_le_bytes().to_vec());
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let data = 9_000u128.to_le_bytes().to_vec();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let data = 1_000u128.to_le_bytes().to_vec();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script2.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(sudt_type_script2.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script2.clone()).pack()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	let data = 100u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 50u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 8_900u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 950u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.cell_dep(token_buy_lock_dep)
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Run
+	let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+	assert_error_eq!(err, ScriptError::ValidationFailure(ERROR_ENCODING).input_lock_script(0));
+}
+
+#[test]
+fn test_buy_alternatives_primary()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_buy_lock = context.deploy_cell(Loader::default().load_binary("token-buy-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_buy_lock_dep = CellDep::new_builder().out_point(out_point_token_buy_lock.clone()).build();
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	// let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Second SUDT Type Script
+	let sudt_owner_lock_script2 = context.build_script(&out_point_always_success, Bytes::from(vec![4u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash2: [u8; 32] = sudt_owner_lock_script2.calc_script_hash().unpack();
+	let script_args: Bytes = sudt_owner_lock_script_hash2.to_vec().into();
+	let sudt_type_script2 = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash2: [u8; 32] = sudt_type_script2.calc_script_hash().unpack();
+
+	// Prepare Token Buy Lock Script
+	let script_args: Bytes = sudt_type_script_hash.to_vec().into();
+	let token_buy_lock_script = context.build_script(&out_point_token_buy_lock, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let mut data = vec!();
+	data.extend(buyer_lock_script_hash.to_vec());
+	data.extend(100u128.to_le_bytes().to_vec());
+	data.extend(FLAGS_MARKER.to_vec());
+	data.extend(FLAG_ALTERNATIVES.to_le_bytes().to_vec());
+	data.extend(vec![1u8]);
+	data.extend(sudt_type_script_hash2.to_vec());
+	data.extend(100u128.to_le_bytes().to_vec());
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let data = 9_000u128.to_le_bytes().to_vec();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let data = 1_000u128.to_le_bytes().to_vec();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script2.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script2.clone()).pack()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	let data = 100u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 8_900u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 1_000u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.cell_dep(token_buy_lock_dep)
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Run
+	let _cycles = context.verify_tx(&tx, MAX_CYCLES).expect("pass verification");
+	// println!("consume cycles: {}", cycles);
+}
+
+#[test]
+fn test_buy_alternatives_alternative()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_buy_lock = context.deploy_cell(Loader::default().load_binary("token-buy-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_buy_lock_dep = CellDep::new_builder().out_point(out_point_token_buy_lock.clone()).build();
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	// let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Second SUDT Type Script
+	let sudt_owner_lock_script2 = context.build_script(&out_point_always_success, Bytes::from(vec![4u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash2: [u8; 32] = sudt_owner_lock_script2.calc_script_hash().unpack();
+	let script_args: Bytes = sudt_owner_lock_script_hash2.to_vec().into();
+	let sudt_type_script2 = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash2: [u8; 32] = sudt_type_script2.calc_script_hash().unpack();
+
+	// Prepare Token Buy Lock Script
+	let script_args: Bytes = sudt_type_script_hash.to_vec().into();
+	let token_buy_lock_script = context.build_script(&out_point_token_buy_lock, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let mut data = vec!();
+	data.extend(buyer_lock_script_hash.to_vec());
+	data.extend(100u128.to_le_bytes().to_vec());
+	data.extend(FLAGS_MARKER.to_vec());
+	data.extend(FLAG_ALTERNATIVES.to_le_bytes().to_vec());
+	data.extend(vec![1u8]);
+	data.extend(sudt_type_script_hash2.to_vec());
+	data.extend(100u128.to_le_bytes().to_vec());
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let data = 9_000u128.to_le_bytes().to_vec();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let data = 1_000u128.to_le_bytes().to_vec();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script2.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(sudt_type_script2.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script2.clone()).pack()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	let data = 100u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 9_000u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 900u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.cell_dep(token_buy_lock_dep)
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Run
+	let _cycles = context.verify_tx(&tx, MAX_CYCLES).expect("pass verification");
+	// println!("consume cycles: {}", cycles);
+}
+
+#[test]
+fn test_buy_alternatives_short()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_buy_lock = context.deploy_cell(Loader::default().load_binary("token-buy-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_buy_lock_dep = CellDep::new_builder().out_point(out_point_token_buy_lock.clone()).build();
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	// let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Second SUDT Type Script
+	let sudt_owner_lock_script2 = context.build_script(&out_point_always_success, Bytes::from(vec![4u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash2: [u8; 32] = sudt_owner_lock_script2.calc_script_hash().unpack();
+	let script_args: Bytes = sudt_owner_lock_script_hash2.to_vec().into();
+	let sudt_type_script2 = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash2: [u8; 32] = sudt_type_script2.calc_script_hash().unpack();
+
+	// Prepare Token Buy Lock Script
+	let script_args: Bytes = sudt_type_script_hash.to_vec().into();
+	let token_buy_lock_script = context.build_script(&out_point_token_buy_lock, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let mut data = vec!();
+	data.extend(buyer_lock_script_hash.to_vec());
+	data.extend(100u128.to_le_bytes().to_vec());
+	data.extend(FLAGS_MARKER.to_vec());
+	data.extend(FLAG_ALTERNATIVES.to_le_bytes().to_vec());
+	data.extend(vec![1u8]);
+	data.extend(sudt_type_script_hash2.to_vec());
+	data.extend(100u128.to_le_bytes().to_vec());
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let data = 9_000u128.to_le_bytes().to_vec();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let data = 1_000u128.to_le_bytes().to_vec();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script2.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(sudt_type_script2.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script2.clone()).pack()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	let data = 99u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 9_000u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 901u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.cell_dep(token_buy_lock_dep)
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Run
+	let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+	assert_error_eq!(err, ScriptError::ValidationFailure(ERROR_AMOUNT).input_lock_script(0));
+}
+
+#[test]
+fn test_buy_alternatives_split()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_buy_lock = context.deploy_cell(Loader::default().load_binary("token-buy-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_buy_lock_dep = CellDep::new_builder().out_point(out_point_token_buy_lock.clone()).build();
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	// let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Second SUDT Type Script
+	let sudt_owner_lock_script2 = context.build_script(&out_point_always_success, Bytes::from(vec![4u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash2: [u8; 32] = sudt_owner_lock_script2.calc_script_hash().unpack();
+	let script_args: Bytes = sudt_owner_lock_script_hash2.to_vec().into();
+	let sudt_type_script2 = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash2: [u8; 32] = sudt_type_script2.calc_script_hash().unpack();
+
+	// Prepare Token Buy Lock Script
+	let script_args: Bytes = sudt_type_script_hash.to_vec().into();
+	let token_buy_lock_script = context.build_script(&out_point_token_buy_lock, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let mut data = vec!();
+	data.extend(buyer_lock_script_hash.to_vec());
+	data.extend(100u128.to_le_bytes().to_vec());
+	data.extend(FLAGS_MARKER.to_vec());
+	data.extend(FLAG_ALTERNATIVES.to_le_bytes().to_vec());
+	data.extend(vec![1u8]);
+	data.extend(sudt_type_script_hash2.to_vec());
+	data.extend(100u128.to_le_bytes().to_vec());
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let data = 9_000u128.to_le_bytes().to_vec();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let data = 1_000u128.to_le_bytes().to_vec();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script2.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(sudt_type_script2.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script2.clone()).pack()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	let data = 50u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 50u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 8_950u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 950u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.cell_dep(token_buy_lock_dep)
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Run
+	let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+	assert_error_eq!(err, ScriptError::ValidationFailure(ERROR_AMOUNT).input_lock_script(0));
+}
+
+#[test]
+fn test_buy_alternatives_multiple_orders()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_buy_lock = context.deploy_cell(Loader::default().load_binary("token-buy-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_buy_lock_dep = CellDep::new_builder().out_point(out_point_token_buy_lock.clone()).build();
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	// let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Second SUDT Type Script
+	let sudt_owner_lock_script2 = context.build_script(&out_point_always_success, Bytes::from(vec![4u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash2: [u8; 32] = sudt_owner_lock_script2.calc_script_hash().unpack();
+	let script_args: Bytes = sudt_owner_lock_script_hash2.to_vec().into();
+	let sudt_type_script2 = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash2: [u8; 32] = sudt_type_script2.calc_script_hash().unpack();
+
+	// Prepare Token Buy Lock Script
+	let script_args: Bytes = sudt_type_script_hash.to_vec().into();
+	let token_buy_lock_script = context.build_script(&out_point_token_buy_lock, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let mut data = vec!();
+	data.extend(buyer_lock_script_hash.to_vec());
+	data.extend(100u128.to_le_bytes().to_vec());
+	data.extend(FLAGS_MARKER.to_vec());
+	data.extend(FLAG_ALTERNATIVES.to_le_bytes().to_vec());
+	data.extend(vec![1u8]);
+	data.extend(sudt_type_script_hash2.to_vec());
+	data.extend(100u128.to_le_bytes().to_vec());
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let mut data = vec!();
+	data.extend(buyer_lock_script_hash.to_vec());
+	data.extend(100u128.to_le_bytes().to_vec());
+	data.extend(FLAGS_MARKER.to_vec());
+	data.extend(FLAG_ALTERNATIVES.to_le_bytes().to_vec());
+	data.extend(vec![1u8]);
+	data.extend(sudt_type_script_hash2.to_vec());
+	data.extend(100u128.to_le_bytes().to_vec());
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let data = 9_000u128.to_le_bytes().to_vec();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let data = 1_000u128.to_le_bytes().to_vec();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script2.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(sudt_type_script2.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script2.clone()).pack()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	let data = 100u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 100u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 8_900u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 900u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.cell_dep(token_buy_lock_dep)
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Run
+	let _cycles = context.verify_tx(&tx, MAX_CYCLES).expect("pass verification");
+	// println!("consume cycles: {}", cycles);
+}
+
+#[test]
+fn test_buy_alternatives_multiple_orders_short()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_buy_lock = context.deploy_cell(Loader::default().load_binary("token-buy-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_buy_lock_dep = CellDep::new_builder().out_point(out_point_token_buy_lock.clone()).build();
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	// let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Second SUDT Type Script
+	let sudt_owner_lock_script2 = context.build_script(&out_point_always_success, Bytes::from(vec![4u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash2: [u8; 32] = sudt_owner_lock_script2.calc_script_hash().unpack();
+	let script_args: Bytes = sudt_owner_lock_script_hash2.to_vec().into();
+	let sudt_type_script2 = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash2: [u8; 32] = sudt_type_script2.calc_script_hash().unpack();
+
+	// Prepare Token Buy Lock Script
+	let script_args: Bytes = sudt_type_script_hash.to_vec().into();
+	let token_buy_lock_script = context.build_script(&out_point_token_buy_lock, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let mut data = vec!();
+	data.extend(buyer_lock_script_hash.to_vec());
+	data.extend(100u128.to_le_bytes().to_vec());
+	data.extend(FLAGS_MARKER.to_vec());
+	data.extend(FLAG_ALTERNATIVES.to_le_bytes().to_vec());
+	data.extend(vec![1u8]);
+	data.extend(sudt_type_script_hash2.to_vec());
+	data.extend(100u128.to_le_bytes().to_vec());
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let mut data = vec!();
+	data.extend(buyer_lock_script_hash.to_vec());
+	data.extend(100u128.to_le_bytes().to_vec());
+	data.extend(FLAGS_MARKER.to_vec());
+	data.extend(FLAG_ALTERNATIVES.to_le_bytes().to_vec());
+	data.extend(vec![1u8]);
+	data.extend(sudt_type_script_hash2.to_vec());
+	data.extend(100u128.to_le_bytes().to_vec());
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let data = 9_000u128.to_le_bytes().to_vec();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let data = 1_000u128.to_le_bytes().to_vec();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script2.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(sudt_type_script2.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script2.clone()).pack()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	let data = 100u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 9_000u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 900u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.cell_dep(token_buy_lock_dep)
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Run
+	let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+	assert_error_eq!(err, ScriptError::ValidationFailure(ERROR_AMOUNT).input_lock_script(0));
+}
+
+#[test]
+fn test_buy_alternatives_bound_payment()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_buy_lock = context.deploy_cell(Loader::default().load_binary("token-buy-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_buy_lock_dep = CellDep::new_builder().out_point(out_point_token_buy_lock.clone()).build();
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	// let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Second SUDT Type Script
+	let sudt_owner_lock_script2 = context.build_script(&out_point_always_success, Bytes::from(vec![4u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash2: [u8; 32] = sudt_owner_lock_script2.calc_script_hash().unpack();
+	let script_args: Bytes = sudt_owner_lock_script_hash2.to_vec().into();
+	let sudt_type_script2 = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash2: [u8; 32] = sudt_type_script2.calc_script_hash().unpack();
+
+	// Prepare Token Buy Lock Script
+	let script_args: Bytes = sudt_type_script_hash.to_vec().into();
+	let token_buy_lock_script = context.build_script(&out_point_token_buy_lock, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let mut data = vec!();
+	data.extend(buyer_lock_script_hash.to_vec());
+	data.extend(100u128.to_le_bytes().to_vec());
+	data.extend(FLAGS_MARKER.to_vec());
+	data.extend(FLAG_ALTERNATIVES | FLAG_BOUND_PAYMENT.to_le_bytes().to_vec());
+	data.extend(vec![1u8]);
+	data.extend(sudt_type_script_hash2.to_vec());
+	data.extend(100u128.to_le_bytes().to_vec());
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let data = 9_000u128.to_le_bytes().to_vec();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let data = 1_000u128.to_le_bytes().to_vec();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script2.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script2.clone()).pack()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	let data = 100u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 8_900u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 1_000u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.cell_dep(token_buy_lock_dep)
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Run
+	let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+	assert_error_eq!(err, ScriptError::ValidationFailure(ERROR_ENCODING).input_lock_script(0));
+}
+
+#[test]
+fn test_buy_versioned()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_buy_lock = context.deploy_cell(Loader::default().load_binary("token-buy-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_buy_lock_dep = CellDep::new_builder().out_point(out_point_token_buy_lock.clone()).build();
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	// let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Token Buy Lock Args
+	let order_args = order_types::OrderArgs::new_builder().sudt_type_hash(sudt_type_script_hash.into()).build();
+
+	// Prepare Token Buy Lock Script
+	let script_args: Bytes = encode_versioned(&order_args).into();
+	let token_buy_lock_script = context.build_script(&out_point_token_buy_lock, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let order_data = order_types::OrderData::new_builder()
+		.owner_lock_hash(buyer_lock_script_hash.into())
+		.buy_amount(100u128.into())
+		.build();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build(), Bytes::from(encode_versioned(&order_data)));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let data = 9_000u128.to_le_bytes().to_vec();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	let data = 100u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 8_900u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.cell_dep(token_buy_lock_dep)
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Run
+	let _cycles = context.verify_tx(&tx, MAX_CYCLES).expect("pass verification");
+	// println!("consume cycles: {}", cycles);
+}
+
+#[test]
+fn test_buy_versioned_v1()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_buy_lock = context.deploy_cell(Loader::default().load_binary("token-buy-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_buy_lock_dep = CellDep::new_builder().out_point(out_point_token_buy_lock.clone()).build();
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	// let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Token Buy Lock Args
+	let order_args = order_types::OrderArgs::new_builder().sudt_type_hash(sudt_type_script_hash.into()).build();
+
+	// Prepare Token Buy Lock Script
+	let script_args: Bytes = encode_with_version(ORDER_VERSION_V1, &order_args).into();
+	let token_buy_lock_script = context.build_script(&out_point_token_buy_lock, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let order_data = order_types::OrderDataV1::new_builder()
+		.owner_lock_hash(buyer_lock_script_hash.into())
+		.buy_amount(100u128.into())
+		.build();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build(), Bytes::from(encode_with_version(ORDER_VERSION_V1, &order_data)));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let data = 9_000u128.to_le_bytes().to_vec();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	let data = 100u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 8_900u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.cell_dep(token_buy_lock_dep)
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Run
+	let _cycles = context.verify_tx(&tx, MAX_CYCLES).expect("pass verification");
+	// println!("consume cycles: {}", cycles);
+}
+
+#[test]
+fn test_buy_versioned_v1_with_current_data()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_buy_lock = context.deploy_cell(Loader::default().load_binary("token-buy-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_buy_lock_dep = CellDep::new_builder().out_point(out_point_token_buy_lock.clone()).build();
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	// let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Token Buy Lock Args
+	let order_args = order_types::OrderArgs::new_builder().sudt_type_hash(sudt_type_script_hash.into()).build();
+
+	// Prepare Token Buy Lock Script
+	let script_args: Bytes = encode_with_version(ORDER_VERSION_V1, &order_args).into();
+	let token_buy_lock_script = context.build_script(&out_point_token_buy_lock, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let order_data = order_types::OrderData::new_builder()
+		.owner_lock_hash(buyer_lock_script_hash.into())
+		.buy_amount(100u128.into())
+		.build();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build(), Bytes::from(encode_with_version(ORDER_VERSION_V1, &order_data)));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let data = 9_000u128.to_le_bytes().to_vec();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	let data = 100u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 8_900u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.cell_dep(token_buy_lock_dep)
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Run
+	let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+	assert_error_eq!(err, ScriptError::ValidationFailure(ERROR_ENCODING).input_lock_script(0));
+}
+
+#[test]
+fn test_buy_versioned_v2()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_buy_lock = context.deploy_cell(Loader::default().load_binary("token-buy-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_buy_lock_dep = CellDep::new_builder().out_point(out_point_token_buy_lock.clone()).build();
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	// let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Token Buy Lock Args
+	let order_args = order_types::OrderArgs::new_builder().sudt_type_hash(sudt_type_script_hash.into()).build();
+
+	// Prepare Token Buy Lock Script
+	let script_args: Bytes = encode_with_version(ORDER_VERSION_V2, &order_args).into();
+	let token_buy_lock_script = context.build_script(&out_point_token_buy_lock, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let order_data = order_types::OrderDataV2::new_builder()
+		.owner_lock_hash(buyer_lock_script_hash.into())
+		.buy_amount(100u128.into())
+		.max_payout_cells(order_types::Uint32Opt::new_builder().set(Some(1u32.into())).build())
+		.build();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build(), Bytes::from(encode_with_version(ORDER_VERSION_V2, &order_data)));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let data = 9_000u128.to_le_bytes().to_vec();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	let data = 100u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 8_900u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.cell_dep(token_buy_lock_dep)
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Run
+	let _cycles = context.verify_tx(&tx, MAX_CYCLES).expect("pass verification");
+	// println!("consume cycles: {}", cycles);
+}
+
+#[test]
+fn test_buy_versioned_v2_with_current_data()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_buy_lock = context.deploy_cell(Loader::default().load_binary("token-buy-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_buy_lock_dep = CellDep::new_builder().out_point(out_point_token_buy_lock.clone()).build();
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	// let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Token Buy Lock Args
+	let order_args = order_types::OrderArgs::new_builder().sudt_type_hash(sudt_type_script_hash.into()).build();
+
+	// Prepare Token Buy Lock Script
+	let script_args: Bytes = encode_with_version(ORDER_VERSION_V2, &order_args).into();
+	let token_buy_lock_script = context.build_script(&out_point_token_buy_lock, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let order_data = order_types::OrderData::new_builder()
+		.owner_lock_hash(buyer_lock_script_hash.into())
+		.buy_amount(100u128.into())
+		.build();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build(), Bytes::from(encode_with_version(ORDER_VERSION_V2, &order_data)));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let data = 9_000u128.to_le_bytes().to_vec();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	let data = 100u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 8_900u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.cell_dep(token_buy_lock_dep)
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Run
+	let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+	assert_error_eq!(err, ScriptError::ValidationFailure(ERROR_ENCODING).input_lock_script(0));
+}
+
+#[test]
+fn test_buy_versioned_short()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_buy_lock = context.deploy_cell(Loader::default().load_binary("token-buy-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_buy_lock_dep = CellDep::new_builder().out_point(out_point_token_buy_lock.clone()).build();
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	// let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Token Buy Lock Args
+	let order_args = order_types::OrderArgs::new_builder().sudt_type_hash(sudt_type_script_hash.into()).build();
+
+	// Prepare Token Buy Lock Script
+	let script_args: Bytes = encode_versioned(&order_args).into();
+	let token_buy_lock_script = context.build_script(&out_point_token_buy_lock, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let order_data = order_types::OrderData::new_builder()
+		.owner_lock_hash(buyer_lock_script_hash.into())
+		.buy_amount(100u128.into())
+		.build();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build(), Bytes::from(encode_versioned(&order_data)));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let data = 9_000u128.to_le_bytes().to_vec();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	let data = 99u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 8_901u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.cell_dep(token_buy_lock_dep)
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Run
+	let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+	assert_error_eq!(err, ScriptError::ValidationFailure(ERROR_AMOUNT).input_lock_script(0));
+}
+
+#[test]
+fn test_buy_versioned_fee()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_buy_lock = context.deploy_cell(Loader::default().load_binary("token-buy-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_buy_lock_dep = CellDep::new_builder().out_point(out_point_token_buy_lock.clone()).build();
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	// let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
+	let fee_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![2u8; 1])).expect("script");
+	let fee_lock_script_hash: [u8; 32] = fee_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Token Buy Lock Args
+	let order_args = order_types::OrderArgs::new_builder().sudt_type_hash(sudt_type_script_hash.into()).build();
+
+	// Prepare Token Buy Lock Script
+	let script_args: Bytes = encode_versioned(&order_args).into();
+	let token_buy_lock_script = context.build_script(&out_point_token_buy_lock, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let order_data = order_types::OrderData::new_builder()
+		.owner_lock_hash(buyer_lock_script_hash.into())
+		.buy_amount(100u128.into())
+		.fee(order_types::FeeOpt::new_builder().set(Some(order_types::Fee::new_builder().lock_hash(fee_lock_script_hash.into()).rate(250u16.into()).build())).build())
+		.build();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build(), Bytes::from(encode_versioned(&order_data)));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let data = 9_000u128.to_le_bytes().to_vec();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(fee_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	let data = 98u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 2u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 8_900u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.cell_dep(token_buy_lock_dep)
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Run
+	let _cycles = context.verify_tx(&tx, MAX_CYCLES).expect("pass verification");
+	// println!("consume cycles: {}", cycles);
+}
+
+#[test]
+fn test_buy_versioned_fee_missing()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_buy_lock = context.deploy_cell(Loader::default().load_binary("token-buy-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_buy_lock_dep = CellDep::new_builder().out_point(out_point_token_buy_lock.clone()).build();
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	// let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
+	let fee_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![2u8; 1])).expect("script");
+	let fee_lock_script_hash: [u8; 32] = fee_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Token Buy Lock Args
+	let order_args = order_types::OrderArgs::new_builder().sudt_type_hash(sudt_type_script_hash.into()).build();
+
+	// Prepare Token Buy Lock Script
+	let script_args: Bytes = encode_versioned(&order_args).into();
+	let token_buy_lock_script = context.build_script(&out_point_token_buy_lock, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let order_data = order_types::OrderData::new_builder()
+		.owner_lock_hash(buyer_lock_script_hash.into())
+		.buy_amount(100u128.into())
+		.fee(order_types::FeeOpt::new_builder().set(Some(order_types::Fee::new_builder().lock_hash(fee_lock_script_hash.into()).rate(250u16.into()).build())).build())
+		.build();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build(), Bytes::from(encode_versioned(&order_data)));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let data = 9_000u128.to_le_bytes().to_vec();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	let data = 100u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 8_900u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.cell_dep(token_buy_lock_dep)
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Run
+	let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+	assert_error_eq!(err, ScriptError::ValidationFailure(ERROR_AMOUNT).input_lock_script(0));
+}
+
+#[test]
+fn test_buy_versioned_partial_fill()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_buy_lock = context.deploy_cell(Loader::default().load_binary("token-buy-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_buy_lock_dep = CellDep::new_builder().out_point(out_point_token_buy_lock.clone()).build();
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	// let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Token Buy Lock Args
+	let order_args = order_types::OrderArgs::new_builder().sudt_type_hash(sudt_type_script_hash.into()).build();
+
+	// Prepare Token Buy Lock Script
+	let script_args: Bytes = encode_versioned(&order_args).into();
+	let token_buy_lock_script = context.build_script(&out_point_token_buy_lock, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let order_data = order_types::OrderData::new_builder()
+		.owner_lock_hash(buyer_lock_script_hash.into())
+		.buy_amount(100u128.into())
+		.partial_fill(1u8.into())
+		.build();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build(), Bytes::from(encode_versioned(&order_data)));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let data = 9_000u128.to_le_bytes().to_vec();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(50_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	let order_data = order_types::OrderData::new_builder()
+		.owner_lock_hash(buyer_lock_script_hash.into())
+		.buy_amount(50u128.into())
+		.partial_fill(1u8.into())
+		.build();
+	outputs_data.push(Bytes::from(encode_versioned(&order_data)));
+	let data = 50u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 8_950u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.cell_dep(token_buy_lock_dep)
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Run
+	let _cycles = context.verify_tx(&tx, MAX_CYCLES).expect("pass verification");
+	// println!("consume cycles: {}", cycles);
+}
+
+#[test]
+fn test_buy_versioned_partial_fill_short()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_buy_lock = context.deploy_cell(Loader::default().load_binary("token-buy-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_buy_lock_dep = CellDep::new_builder().out_point(out_point_token_buy_lock.clone()).build();
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	// let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Token Buy Lock Args
+	let order_args = order_types::OrderArgs::new_builder().sudt_type_hash(sudt_type_script_hash.into()).build();
+
+	// Prepare Token Buy Lock Script
+	let script_args: Bytes = encode_versioned(&order_args).into();
+	let token_buy_lock_script = context.build_script(&out_point_token_buy_lock, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let order_data = order_types::OrderData::new_builder()
+		.owner_lock_hash(buyer_lock_script_hash.into())
+		.buy_amount(100u128.into())
+		.partial_fill(1u8.into())
+		.build();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build(), Bytes::from(encode_versioned(&order_data)));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let data = 9_000u128.to_le_bytes().to_vec();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(50_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	let order_data = order_types::OrderData::new_builder()
+		.owner_lock_hash(buyer_lock_script_hash.into())
+		.buy_amount(50u128.into())
+		.partial_fill(1u8.into())
+		.build();
+	outputs_data.push(Bytes::from(encode_versioned(&order_data)));
+	let data = 49u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 8_951u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.cell_dep(token_buy_lock_dep)
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Run
+	let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+	assert_error_eq!(err, ScriptError::ValidationFailure(ERROR_AMOUNT).input_lock_script(0));
+}
+
+#[test]
+fn test_buy_versioned_withdrawal_by_owner()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_buy_lock = context.deploy_cell(Loader::default().load_binary("token-buy-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_buy_lock_dep = CellDep::new_builder().out_point(out_point_token_buy_lock.clone()).build();
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	// let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	// let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Token Buy Lock Args
+	let order_args = order_types::OrderArgs::new_builder().sudt_type_hash(sudt_type_script_hash.into()).build();
+
+	// Prepare Token Buy Lock Script
+	let script_args: Bytes = encode_versioned(&order_args).into();
+	let token_buy_lock_script = context.build_script(&out_point_token_buy_lock, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let order_data = order_types::OrderData::new_builder()
+		.owner_lock_hash(buyer_lock_script_hash.into())
+		.buy_amount(100u128.into())
+		.build();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build(), Bytes::from(encode_versioned(&order_data)));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).build(), Bytes::new());
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	outputs_data.push(Bytes::new());
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.cell_dep(token_buy_lock_dep)
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Run
+	let _cycles = context.verify_tx(&tx, MAX_CYCLES).expect("pass verification");
+	// println!("consume cycles: {}", cycles);
+}
+
+#[test]
+fn test_buy_versioned_legacy_data()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_buy_lock = context.deploy_cell(Loader::default().load_binary("token-buy-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_buy_lock_dep = CellDep::new_builder().out_point(out_point_token_buy_lock.clone()).build();
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	// let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Token Buy Lock Args
+	let order_args = order_types::OrderArgs::new_builder().sudt_type_hash(sudt_type_script_hash.into()).build();
+
+	// Prepare Token Buy Lock Script
+	let script_args: Bytes = encode_versioned(&order_args).into();
+	let token_buy_lock_script = context.build_script(&out_point_token_buy_lock, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let mut data = vec!();
+	data.extend(buyer_lock_script_hash.to_vec());
+	data.extend(100u128.to_le_bytes().to_vec());
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let data = 9_000u128.to_le_bytes().to_vec();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	let data = 100u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 8_900u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.cell_dep(token_buy_lock_dep)
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Run
+	let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+	assert_error_eq!(err, ScriptError::ValidationFailure(ERROR_ENCODING).input_lock_script(0));
+}
+
+#[test]
+fn test_buy_versioned_invalid_data()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_buy_lock = context.deploy_cell(Loader::default().load_binary("token-buy-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_buy_lock_dep = CellDep::new_builder().out_point(out_point_token_buy_lock.clone()).build();
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	// let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	// let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Token Buy Lock Args
+	let order_args = order_types::OrderArgs::new_builder().sudt_type_hash(sudt_type_script_hash.into()).build();
+
+	// Prepare Token Buy Lock Script
+	let script_args: Bytes = encode_versioned(&order_args).into();
+	let token_buy_lock_script = context.build_script(&out_point_token_buy_lock, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let mut data = vec!();
+	data.extend(vec![ORDER_VERSION]);
+	data.extend([0u8; 16].to_vec());
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let data = 9_000u128.to_le_bytes().to_vec();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	let data = 100u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 8_900u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.cell_dep(token_buy_lock_dep)
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Run
+	let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+	assert_error_eq!(err, ScriptError::ValidationFailure(ERROR_ENCODING).input_lock_script(0));
+}
+
+#[test]
+fn test_buy_versioned_invalid_bool()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_buy_lock = context.deploy_cell(Loader::default().load_binary("token-buy-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_buy_lock_dep = CellDep::new_builder().out_point(out_point_token_buy_lock.clone()).build();
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	// let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Token Buy Lock Args
+	let order_args = order_types::OrderArgs::new_builder().sudt_type_hash(sudt_type_script_hash.into()).build();
+
+	// Prepare Token Buy Lock Script
+	let script_args: Bytes = encode_versioned(&order_args).into();
+	let token_buy_lock_script = context.build_script(&out_point_token_buy_lock, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let order_data = order_types::OrderData::new_builder()
+		.owner_lock_hash(buyer_lock_script_hash.into())
+		.buy_amount(100u128.into())
+		.partial_fill(2u8.into())
+		.build();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build(), Bytes::from(encode_versioned(&order_data)));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let data = 9_000u128.to_le_bytes().to_vec();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	let data = 100u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 8_900u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.cell_dep(token_buy_lock_dep)
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Run
+	let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+	assert_error_eq!(err, ScriptError::ValidationFailure(ERROR_ENCODING).input_lock_script(0));
+}
+
+#[test]
+fn test_buy_versioned_unknown_version()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_buy_lock = context.deploy_cell(Loader::default().load_binary("token-buy-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_buy_lock_dep = CellDep::new_builder().out_point(out_point_token_buy_lock.clone()).build();
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	// let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Token Buy Lock Args
+	let order_args = order_types::OrderArgs::new_builder().sudt_type_hash(sudt_type_script_hash.into()).build();
+
+	// Prepare Token Buy Lock Script
+	let script_args: Bytes = { let mut args = encode_versioned(&order_args); args[0] = ORDER_VERSION + 1; args.into() };
+	let token_buy_lock_script = context.build_script(&out_point_token_buy_lock, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let order_data = order_types::OrderData::new_builder()
+		.owner_lock_hash(buyer_lock_script_hash.into())
+		.buy_amount(100u128.into())
+		.build();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build(), Bytes::from(encode_versioned(&order_data)));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let data = 9_000u128.to_le_bytes().to_vec();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	let data = 100u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 8_900u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.cell_dep(token_buy_lock_dep)
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Run
+	let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+	assert_error_eq!(err, ScriptError::ValidationFailure(ERROR_ARGS_LEN).input_lock_script(0));
+}
+
+#[test]
+fn test_buy_versioned_min_payout_capacity()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_buy_lock = context.deploy_cell(Loader::default().load_binary("token-buy-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_buy_lock_dep = CellDep::new_builder().out_point(out_point_token_buy_lock.clone()).build();
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	// let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Token Buy Lock Args
+	let order_args = order_types::OrderArgs::new_builder().sudt_type_hash(sudt_type_script_hash.into()).build();
+
+	// Prepare Token Buy Lock Script
+	let script_args: Bytes = encode_versioned(&order_args).into();
+	let token_buy_lock_script = context.build_script(&out_point_token_buy_lock, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let order_data = order_types::OrderData::new_builder()
+		.owner_lock_hash(buyer_lock_script_hash.into())
+		.buy_amount(100u128.into())
+		.min_payout_capacity(order_types::Uint64Opt::new_builder().set(Some(61_000_000_000u64.into())).build())
+		.build();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build(), Bytes::from(encode_versioned(&order_data)));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let data = 9_000u128.to_le_bytes().to_vec();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(61_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	let data = 100u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 8_900u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.cell_dep(token_buy_lock_dep)
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Run
+	let _cycles = context.verify_tx(&tx, MAX_CYCLES).expect("pass verification");
+	// println!("consume cycles: {}", cycles);
+}
+
+#[test]
+fn test_buy_versioned_min_payout_capacity_short()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_buy_lock = context.deploy_cell(Loader::default().load_binary("token-buy-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_buy_lock_dep = CellDep::new_builder().out_point(out_point_token_buy_lock.clone()).build();
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	// let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Token Buy Lock Args
+	let order_args = order_types::OrderArgs::new_builder().sudt_type_hash(sudt_type_script_hash.into()).build();
+
+	// Prepare Token Buy Lock Script
+	let script_args: Bytes = encode_versioned(&order_args).into();
+	let token_buy_lock_script = context.build_script(&out_point_token_buy_lock, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let order_data = order_types::OrderData::new_builder()
+		.owner_lock_hash(buyer_lock_script_hash.into())
+		.buy_amount(100u128.into())
+		.min_payout_capacity(order_types::Uint64Opt::new_builder().set(Some(61_000_000_000u64.into())).build())
+		.build();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build(), Bytes::from(encode_versioned(&order_data)));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let data = 9_000u128.to_le_bytes().to_vec();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(60_999_999_999_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	let data = 100u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 8_900u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.cell_dep(token_buy_lock_dep)
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Run
+	let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+	assert_error_eq!(err, ScriptError::ValidationFailure(ERROR_PAYOUT_CELLS).input_lock_script(0));
+}
+
+#[test]
+fn test_buy_versioned_min_payout_capacity_dust()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_buy_lock = context.deploy_cell(Loader::default().load_binary("token-buy-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_buy_lock_dep = CellDep::new_builder().out_point(out_point_token_buy_lock.clone()).build();
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	// let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Token Buy Lock Args
+	let order_args = order_types::OrderArgs::new_builder().sudt_type_hash(sudt_type_script_hash.into()).build();
+
+	// Prepare Token Buy Lock Script
+	let script_args: Bytes = encode_versioned(&order_args).into();
+	let token_buy_lock_script = context.build_script(&out_point_token_buy_lock, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let order_data = order_types::OrderData::new_builder()
+		.owner_lock_hash(buyer_lock_script_hash.into())
+		.buy_amount(100u128.into())
+		.min_payout_capacity(order_types::Uint64Opt::new_builder().set(Some(61_000_000_000u64.into())).build())
+		.build();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build(), Bytes::from(encode_versioned(&order_data)));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let data = 9_000u128.to_le_bytes().to_vec();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(20_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	let data = 100u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 0u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 8_900u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.cell_dep(token_buy_lock_dep)
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Run
+	let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+	assert_error_eq!(err, ScriptError::ValidationFailure(ERROR_PAYOUT_CELLS).input_lock_script(0));
+}
+
+#[test]
+fn test_buy_versioned_max_payout_cells()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_buy_lock = context.deploy_cell(Loader::default().load_binary("token-buy-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_buy_lock_dep = CellDep::new_builder().out_point(out_point_token_buy_lock.clone()).build();
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	// let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Token Buy Lock Args
+	let order_args = order_types::OrderArgs::new_builder().sudt_type_hash(sudt_type_script_hash.into()).build();
+
+	// Prepare Token Buy Lock Script
+	let script_args: Bytes = encode_versioned(&order_args).into();
+	let token_buy_lock_script = context.build_script(&out_point_token_buy_lock, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let order_data = order_types::OrderData::new_builder()
+		.owner_lock_hash(buyer_lock_script_hash.into())
+		.buy_amount(100u128.into())
+		.max_payout_cells(order_types::Uint32Opt::new_builder().set(Some(2u32.into())).build())
+		.build();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build(), Bytes::from(encode_versioned(&order_data)));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let data = 9_000u128.to_le_bytes().to_vec();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	let data = 50u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 50u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 8_900u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.cell_dep(token_buy_lock_dep)
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Run
+	let _cycles = context.verify_tx(&tx, MAX_CYCLES).expect("pass verification");
+	// println!("consume cycles: {}", cycles);
+}
+
+#[test]
+fn test_buy_versioned_max_payout_cells_exceeded()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_buy_lock = context.deploy_cell(Loader::default().load_binary("token-buy-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_buy_lock_dep = CellDep::new_builder().out_point(out_point_token_buy_lock.clone()).build();
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	// let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Token Buy Lock Args
+	let order_args = order_types::OrderArgs::new_builder().sudt_type_hash(sudt_type_script_hash.into()).build();
+
+	// Prepare Token Buy Lock Script
+	let script_args: Bytes = encode_versioned(&order_args).into();
+	let token_buy_lock_script = context.build_script(&out_point_token_buy_lock, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let order_data = order_types::OrderData::new_builder()
+		.owner_lock_hash(buyer_lock_script_hash.into())
+		.buy_amount(100u128.into())
+		.max_payout_cells(order_types::Uint32Opt::new_builder().set(Some(1u32.into())).build())
+		.build();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build(), Bytes::from(encode_versioned(&order_data)));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let data = 9_000u128.to_le_bytes().to_vec();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	let data = 50u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 50u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 8_900u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.cell_dep(token_buy_lock_dep)
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Run
+	let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+	assert_error_eq!(err, ScriptError::ValidationFailure(ERROR_PAYOUT_CELLS).input_lock_script(0));
+}
+
+#[test]
+fn test_buy_versioned_max_payout_cells_zero()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_buy_lock = context.deploy_cell(Loader::default().load_binary("token-buy-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_buy_lock_dep = CellDep::new_builder().out_point(out_point_token_buy_lock.clone()).build();
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	// let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Token Buy Lock Args
+	let order_args = order_types::OrderArgs::new_builder().sudt_type_hash(sudt_type_script_hash.into()).build();
+
+	// Prepare Token Buy Lock Script
+	let script_args: Bytes = encode_versioned(&order_args).into();
+	let token_buy_lock_script = context.build_script(&out_point_token_buy_lock, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let order_data = order_types::OrderData::new_builder()
+		.owner_lock_hash(buyer_lock_script_hash.into())
+		.buy_amount(100u128.into())
+		.max_payout_cells(order_types::Uint32Opt::new_builder().set(Some(0u32.into())).build())
+		.build();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build(), Bytes::from(encode_versioned(&order_data)));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let data = 9_000u128.to_le_bytes().to_vec();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	let data = 100u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 8_900u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.cell_dep(token_buy_lock_dep)
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Run
+	let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+	assert_error_eq!(err, ScriptError::ValidationFailure(ERROR_ENCODING).input_lock_script(0));
+}
+
+#[test]
+fn test_buy_multiple_requirement_overflow()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_buy_lock = context.deploy_cell(Loader::default().load_binary("token-buy-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_buy_lock_dep = CellDep::new_builder().out_point(out_point_token_buy_lock.clone()).build();
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	// let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Token Buy Lock Script
+	let script_args: Bytes = sudt_type_script_hash.to_vec().into();
+	let token_buy_lock_script = context.build_script(&out_point_token_buy_lock, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let mut data = vec!();
+	data.extend(buyer_lock_script_hash.to_vec());
+	data.extend(u128::MAX.to_le_bytes().to_vec());
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let mut data = vec!();
+	data.extend(buyer_lock_script_hash.to_vec());
+	data.extend(u128::MAX.to_le_bytes().to_vec());
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let data = 9_000u128.to_le_bytes().to_vec();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	let data = 100u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 8_900u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.cell_dep(token_buy_lock_dep)
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Run
+	let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+	assert_error_eq!(err, ScriptError::ValidationFailure(ERROR_OVERFLOW).input_lock_script(0));
+}
+
+#[test]
+fn test_buy_payment_overflow()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_buy_lock = context.deploy_cell(Loader::default().load_binary("token-buy-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_buy_lock_dep = CellDep::new_builder().out_point(out_point_token_buy_lock.clone()).build();
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	// let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Token Buy Lock Script
+	let script_args: Bytes = sudt_type_script_hash.to_vec().into();
+	let token_buy_lock_script = context.build_script(&out_point_token_buy_lock, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let mut data = vec!();
+	data.extend(buyer_lock_script_hash.to_vec());
+	data.extend(100u128.to_le_bytes().to_vec());
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let data = 9_000u128.to_le_bytes().to_vec();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	let data = u128::MAX.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 100u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 8_900u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.cell_dep(token_buy_lock_dep)
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Run
+	let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+	assert_error_eq!(err, ScriptError::ValidationFailure(ERROR_OVERFLOW).input_lock_script(0));
+}
+
+#[test]
+fn test_buy_fee_overflow()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_buy_lock = context.deploy_cell(Loader::default().load_binary("token-buy-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_buy_lock_dep = CellDep::new_builder().out_point(out_point_token_buy_lock.clone()).build();
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	// let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Token Buy Lock Script
+	let script_args: Bytes = sudt_type_script_hash.to_vec().into();
+	let token_buy_lock_script = context.build_script(&out_point_token_buy_lock, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let mut data = vec!();
+	data.extend(buyer_lock_script_hash.to_vec());
+	data.extend(u128::MAX.to_le_bytes().to_vec());
+	data.extend(FLAGS_MARKER.to_vec());
+	data.extend(FLAG_FEE.to_le_bytes().to_vec());
+	data.extend(sudt_owner_lock_script_hash.to_vec());
+	data.extend(100u16.to_le_bytes().to_vec());
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let data = 9_000u128.to_le_bytes().to_vec();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	let data = 100u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 8_900u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.cell_dep(token_buy_lock_dep)
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Run
+	let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+	assert_error_eq!(err, ScriptError::ValidationFailure(ERROR_OVERFLOW).input_lock_script(0));
+}
+
+#[test]
+fn test_buy_versioned_cancel_by_signature()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_buy_lock = context.deploy_cell(Loader::default().load_binary("token-buy-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_buy_lock_dep = CellDep::new_builder().out_point(out_point_token_buy_lock.clone()).build();
+	let (secp256k1_lib_dep, secp256k1_data_dep) = deploy_secp256k1(&mut context);
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	// let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Token Buy Lock Args
+	let order_args = order_types::OrderArgs::new_builder().sudt_type_hash(sudt_type_script_hash.into()).build();
+
+	// Prepare Owner Key
+	let owner_privkey = Privkey::from_slice(&[7u8; 32]);
+	let owner_pubkey = owner_privkey.pubkey().expect("pubkey");
+	let mut owner_pubkey_hash = [0u8; 20];
+	owner_pubkey_hash.copy_from_slice(&blake2b_256(owner_pubkey.serialize())[0..20]);
+
+	// Prepare Token Buy Lock Script
+	let script_args: Bytes = encode_versioned(&order_args).into();
+	let token_buy_lock_script = context.build_script(&out_point_token_buy_lock, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let order_data = order_types::OrderData::new_builder()
+		.owner_lock_hash(buyer_lock_script_hash.into())
+		.buy_amount(100u128.into())
+		.owner_pubkey_hash(order_types::Byte20Opt::new_builder().set(Some(owner_pubkey_hash.into())).build())
+		.build();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build(), Bytes::from(encode_versioned(&order_data)));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	outputs_data.push(Bytes::new());
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.cell_dep(token_buy_lock_dep)
+		.cell_dep(secp256k1_lib_dep)
+		.cell_dep(secp256k1_data_dep)
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Sign Transaction
+	let tx_hash: [u8; 32] = tx.hash().unpack();
+	let signature = owner_privkey.sign_recoverable(&H256::from(tx_hash)).expect("sign");
+	let witness = WitnessArgs::new_builder().lock(Some(Bytes::from(signature.serialize())).pack()).build();
+	let tx = tx.as_advanced_builder().witness(witness.as_bytes().pack()).build();
+
+	// Run
+	let _cycles = context.verify_tx(&tx, MAX_CYCLES).expect("pass verification");
+	// println!("consume cycles: {}", cycles);
+}
+
+#[test]
+fn test_buy_versioned_cancel_by_signature_unsigned_order()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_buy_lock = context.deploy_cell(Loader::default().load_binary("token-buy-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_buy_lock_dep = CellDep::new_builder().out_point(out_point_token_buy_lock.clone()).build();
+	let (secp256k1_lib_dep, secp256k1_data_dep) = deploy_secp256k1(&mut context);
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	// let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Token Buy Lock Args
+	let order_args = order_types::OrderArgs::new_builder().sudt_type_hash(sudt_type_script_hash.into()).build();
+
+	// Prepare Owner Key
+	let owner_privkey = Privkey::from_slice(&[7u8; 32]);
+	let owner_pubkey = owner_privkey.pubkey().expect("pubkey");
+	let mut owner_pubkey_hash = [0u8; 20];
+	owner_pubkey_hash.copy_from_slice(&blake2b_256(owner_pubkey.serialize())[0..20]);
+
+	// Prepare Token Buy Lock Script
+	let script_args: Bytes = encode_versioned(&order_args).into();
+	let token_buy_lock_script = context.build_script(&out_point_token_buy_lock, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let order_data = order_types::OrderData::new_builder()
+		.owner_lock_hash(buyer_lock_script_hash.into())
+		.buy_amount(100u128.into())
+		.owner_pubkey_hash(order_types::Byte20Opt::new_builder().set(Some(owner_pubkey_hash.into())).build())
+		.build();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build(), Bytes::from(encode_versioned(&order_data)));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let order_data = order_types::OrderData::new_builder()
+		.owner_lock_hash(buyer_lock_script_hash.into())
+		.buy_amount(100u128.into())
+		.owner_pubkey_hash(order_types::Byte20Opt::new_builder().set(Some(owner_pubkey_hash.into())).build())
+		.build();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build(), Bytes::from(encode_versioned(&order_data)));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	outputs_data.push(Bytes::new());
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.cell_dep(token_buy_lock_dep)
+		.cell_dep(secp256k1_lib_dep)
+		.cell_dep(secp256k1_data_dep)
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Sign Transaction
+	let tx_hash: [u8; 32] = tx.hash().unpack();
+	let signature = owner_privkey.sign_recoverable(&H256::from(tx_hash)).expect("sign");
+	let witness = WitnessArgs::new_builder().lock(Some(Bytes::from(signature.serialize())).pack()).build();
+	let tx = tx.as_advanced_builder().witness(witness.as_bytes().pack()).build();
+
+	// Run
+	let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+	assert_error_eq!(err, ScriptError::ValidationFailure(ERROR_AMOUNT).input_lock_script(0));
+}
+
+#[test]
+fn test_buy_versioned_cancel_by_signature_wrong_key()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_buy_lock = context.deploy_cell(Loader::default().load_binary("token-buy-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_buy_lock_dep = CellDep::new_builder().out_point(out_point_token_buy_lock.clone()).build();
+	let (secp256k1_lib_dep, secp256k1_data_dep) = deploy_secp256k1(&mut context);
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	// let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Token Buy Lock Args
+	let order_args = order_types::OrderArgs::new_builder().sudt_type_hash(sudt_type_script_hash.into()).build();
+
+	// Prepare Owner Key
+	let owner_privkey = Privkey::from_slice(&[7u8; 32]);
+	let owner_pubkey = owner_privkey.pubkey().expect("pubkey");
+	let mut owner_pubkey_hash = [0u8; 20];
+	owner_pubkey_hash.copy_from_slice(&blake2b_256(owner_pubkey.serialize())[0..20]);
+
+	// Prepare Token Buy Lock Script
+	let script_args: Bytes = encode_versioned(&order_args).into();
+	let token_buy_lock_script = context.build_script(&out_point_token_buy_lock, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let order_data = order_types::OrderData::new_builder()
+		.owner_lock_hash(buyer_lock_script_hash.into())
+		.buy_amount(100u128.into())
+		.owner_pubkey_hash(order_types::Byte20Opt::new_builder().set(Some(owner_pubkey_hash.into())).build())
+		.build();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build(), Bytes::from(encode_versioned(&order_data)));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	outputs_data.push(Bytes::new());
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.cell_dep(token_buy_lock_dep)
+		.cell_dep(secp256k1_lib_dep)
+		.cell_dep(secp256k1_data_dep)
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Sign Transaction
+	let tx_hash: [u8; 32] = tx.hash().unpack();
+	let signature = Privkey::from_slice(&[8u8; 32]).sign_recoverable(&H256::from(tx_hash)).expect("sign");
+	let witness = WitnessArgs::new_builder().lock(Some(Bytes::from(signature.serialize())).pack()).build();
+	let tx = tx.as_advanced_builder().witness(witness.as_bytes().pack()).build();
+
+	// Run
+	let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+	assert_error_eq!(err, ScriptError::ValidationFailure(ERROR_SIGNATURE).input_lock_script(0));
+}
+
+#[test]
+fn test_buy_versioned_cancel_without_signature()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_buy_lock = context.deploy_cell(Loader::default().load_binary("token-buy-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_buy_lock_dep = CellDep::new_builder().out_point(out_point_token_buy_lock.clone()).build();
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	// let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Token Buy Lock Args
+	let order_args = order_types::OrderArgs::new_builder().sudt_type_hash(sudt_type_script_hash.into()).build();
+
+	// Prepare Owner Key
+	let owner_privkey = Privkey::from_slice(&[7u8; 32]);
+	let owner_pubkey = owner_privkey.pubkey().expect("pubkey");
+	let mut owner_pubkey_hash = [0u8; 20];
+	owner_pubkey_hash.copy_from_slice(&blake2b_256(owner_pubkey.serialize())[0..20]);
+
+	// Prepare Token Buy Lock Script
+	let script_args: Bytes = encode_versioned(&order_args).into();
+	let token_buy_lock_script = context.build_script(&out_point_token_buy_lock, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let order_data = order_types::OrderData::new_builder()
+		.owner_lock_hash(buyer_lock_script_hash.into())
+		.buy_amount(100u128.into())
+		.owner_pubkey_hash(order_types::Byte20Opt::new_builder().set(Some(owner_pubkey_hash.into())).build())
+		.build();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build(), Bytes::from(encode_versioned(&order_data)));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	outputs_data.push(Bytes::new());
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.cell_dep(token_buy_lock_dep)
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Run
+	let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+	assert_error_eq!(err, ScriptError::ValidationFailure(ERROR_AMOUNT).input_lock_script(0));
+}
+
+#[test]
+fn test_buy_versioned_cancel_by_signature_no_pubkey_hash()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_buy_lock = context.deploy_cell(Loader::default().load_binary("token-buy-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_buy_lock_dep = CellDep::new_builder().out_point(out_point_token_buy_lock.clone()).build();
+	let (secp256k1_lib_dep, secp256k1_data_dep) = deploy_secp256k1(&mut context);
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	// let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Token Buy Lock Args
+	let order_args = order_types::OrderArgs::new_builder().sudt_type_hash(sudt_type_script_hash.into()).build();
+
+	// Prepare Owner Key
+	let owner_privkey = Privkey::from_slice(&[7u8; 32]);
+	let owner_pubkey = owner_privkey.pubkey().expect("pubkey");
+	let mut owner_pubkey_hash = [0u8; 20];
+	owner_pubkey_hash.copy_from_slice(&blake2b_256(owner_pubkey.serialize())[0..20]);
+
+	// Prepare Token Buy Lock Script
+	let script_args: Bytes = encode_versioned(&order_args).into();
+	let token_buy_lock_script = context.build_script(&out_point_token_buy_lock, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let order_data = order_types::OrderData::new_builder()
+		.owner_lock_hash(buyer_lock_script_hash.into())
+		.buy_amount(100u128.into())
+		.build();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_buy_lock_script.clone()).build(), Bytes::from(encode_versioned(&order_data)));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	outputs_data.push(Bytes::new());
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.cell_dep(token_buy_lock_dep)
+		.cell_dep(secp256k1_lib_dep)
+		.cell_dep(secp256k1_data_dep)
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Sign Transaction
+	let tx_hash: [u8; 32] = tx.hash().unpack();
+	let signature = owner_privkey.sign_recoverable(&H256::from(tx_hash)).expect("sign");
+	let witness = WitnessArgs::new_builder().lock(Some(Bytes::from(signature.serialize())).pack()).build();
+	let tx = tx.as_advanced_builder().witness(witness.as_bytes().pack()).build();
+
+	// Run
+	let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+	assert_error_eq!(err, ScriptError::ValidationFailure(ERROR_AMOUNT).input_lock_script(0));
 }
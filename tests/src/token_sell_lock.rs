@@ -0,0 +1,1244 @@
+use super::*;
+use ckb_testtool::{builtin::ALWAYS_SUCCESS, context::Context};
+use ckb_tool::{ckb_error::assert_error_eq, ckb_script::ScriptError};
+use ckb_tool::ckb_types::{bytes::Bytes, packed::*, prelude::*};
+use ckb_tool::ckb_types::core::{TransactionBuilder};
+
+// Constants
+const MAX_CYCLES: u64 = 100_000_000;
+
+// Error Codes
+const ERROR_AMOUNT: i8 = 5;
+const ERROR_ARGS_LEN: i8 = 6;
+const ERROR_DATA_LEN: i8 = 7;
+const ERROR_TYPE_HASH: i8 = 8;
+const ERROR_OVERFLOW: i8 = 9;
+
+#[test]
+fn test_sell_exact()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_sell_lock = context.deploy_cell(Loader::default().load_binary("token-sell-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_sell_lock_dep = CellDep::new_builder().out_point(out_point_token_sell_lock.clone()).build();
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	// let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Token Sell Lock Script
+	let script_args: Bytes = sudt_type_script_hash.to_vec().into();
+	let token_sell_lock_script = context.build_script(&out_point_token_sell_lock, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let mut data = vec!();
+	data.extend(100u128.to_le_bytes().to_vec());
+	data.extend(seller_lock_script_hash.to_vec());
+	data.extend(50_000_000_000u64.to_le_bytes().to_vec());
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_sell_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).build(), Bytes::new());
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(50_000_000_000_u64.pack()).lock(seller_lock_script.clone()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(50_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	let data = 100u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	outputs_data.push(Bytes::new());
+	outputs_data.push(Bytes::new());
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.cell_dep(token_sell_lock_dep)
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Run
+	let _cycles = context.verify_tx(&tx, MAX_CYCLES).expect("pass verification");
+	// println!("consume cycles: {}", cycles);
+}
+
+#[test]
+fn test_sell_extra()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_sell_lock = context.deploy_cell(Loader::default().load_binary("token-sell-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_sell_lock_dep = CellDep::new_builder().out_point(out_point_token_sell_lock.clone()).build();
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	// let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Token Sell Lock Script
+	let script_args: Bytes = sudt_type_script_hash.to_vec().into();
+	let token_sell_lock_script = context.build_script(&out_point_token_sell_lock, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let mut data = vec!();
+	data.extend(100u128.to_le_bytes().to_vec());
+	data.extend(seller_lock_script_hash.to_vec());
+	data.extend(50_000_000_000u64.to_le_bytes().to_vec());
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_sell_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).build(), Bytes::new());
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(60_000_000_000_u64.pack()).lock(seller_lock_script.clone()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(40_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	let data = 100u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	outputs_data.push(Bytes::new());
+	outputs_data.push(Bytes::new());
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.cell_dep(token_sell_lock_dep)
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Run
+	let _cycles = context.verify_tx(&tx, MAX_CYCLES).expect("pass verification");
+	// println!("consume cycles: {}", cycles);
+}
+
+#[test]
+fn test_sell_short()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_sell_lock = context.deploy_cell(Loader::default().load_binary("token-sell-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_sell_lock_dep = CellDep::new_builder().out_point(out_point_token_sell_lock.clone()).build();
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	// let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Token Sell Lock Script
+	let script_args: Bytes = sudt_type_script_hash.to_vec().into();
+	let token_sell_lock_script = context.build_script(&out_point_token_sell_lock, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let mut data = vec!();
+	data.extend(100u128.to_le_bytes().to_vec());
+	data.extend(seller_lock_script_hash.to_vec());
+	data.extend(50_000_000_000u64.to_le_bytes().to_vec());
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_sell_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).build(), Bytes::new());
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(49_999_999_999_u64.pack()).lock(seller_lock_script.clone()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(50_000_000_001_u64.pack()).lock(buyer_lock_script.clone()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	let data = 100u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	outputs_data.push(Bytes::new());
+	outputs_data.push(Bytes::new());
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.cell_dep(token_sell_lock_dep)
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Run
+	let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+	assert_error_eq!(err, ScriptError::ValidationFailure(ERROR_AMOUNT).input_lock_script(0));
+}
+
+#[test]
+fn test_sell_typed_payment()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_sell_lock = context.deploy_cell(Loader::default().load_binary("token-sell-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_sell_lock_dep = CellDep::new_builder().out_point(out_point_token_sell_lock.clone()).build();
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	// let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Token Sell Lock Script
+	let script_args: Bytes = sudt_type_script_hash.to_vec().into();
+	let token_sell_lock_script = context.build_script(&out_point_token_sell_lock, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let mut data = vec!();
+	data.extend(100u128.to_le_bytes().to_vec());
+	data.extend(seller_lock_script_hash.to_vec());
+	data.extend(50_000_000_000u64.to_le_bytes().to_vec());
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_sell_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).build(), Bytes::new());
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(50_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(50_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	let data = 100u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 0u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	outputs_data.push(Bytes::new());
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.cell_dep(token_sell_lock_dep)
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Run
+	let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+	assert_error_eq!(err, ScriptError::ValidationFailure(ERROR_AMOUNT).input_lock_script(0));
+}
+
+#[test]
+fn test_sell_invalid_args()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_sell_lock = context.deploy_cell(Loader::default().load_binary("token-sell-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_sell_lock_dep = CellDep::new_builder().out_point(out_point_token_sell_lock.clone()).build();
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	// let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	// let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Token Sell Lock Script
+	let script_args: Bytes = [0u8; 16].to_vec().into(); // Invalid args.
+	let token_sell_lock_script = context.build_script(&out_point_token_sell_lock, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let mut data = vec!();
+	data.extend(100u128.to_le_bytes().to_vec());
+	data.extend(seller_lock_script_hash.to_vec());
+	data.extend(50_000_000_000u64.to_le_bytes().to_vec());
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_sell_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).build(), Bytes::new());
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(50_000_000_000_u64.pack()).lock(seller_lock_script.clone()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(50_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	let data = 100u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	outputs_data.push(Bytes::new());
+	outputs_data.push(Bytes::new());
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.cell_dep(token_sell_lock_dep)
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Run
+	let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+	assert_error_eq!(err, ScriptError::ValidationFailure(ERROR_ARGS_LEN).input_lock_script(0));
+}
+
+#[test]
+fn test_sell_invalid_data()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_sell_lock = context.deploy_cell(Loader::default().load_binary("token-sell-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_sell_lock_dep = CellDep::new_builder().out_point(out_point_token_sell_lock.clone()).build();
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	// let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Token Sell Lock Script
+	let script_args: Bytes = sudt_type_script_hash.to_vec().into();
+	let token_sell_lock_script = context.build_script(&out_point_token_sell_lock, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let mut data = vec!();
+	data.extend(100u128.to_le_bytes().to_vec());
+	data.extend(seller_lock_script_hash.to_vec());
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_sell_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).build(), Bytes::new());
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(50_000_000_000_u64.pack()).lock(seller_lock_script.clone()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(50_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	let data = 100u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	outputs_data.push(Bytes::new());
+	outputs_data.push(Bytes::new());
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.cell_dep(token_sell_lock_dep)
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Run
+	let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+	assert_error_eq!(err, ScriptError::ValidationFailure(ERROR_DATA_LEN).input_lock_script(0));
+}
+
+#[test]
+fn test_sell_wrong_token()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_sell_lock = context.deploy_cell(Loader::default().load_binary("token-sell-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_sell_lock_dep = CellDep::new_builder().out_point(out_point_token_sell_lock.clone()).build();
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	// let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Other SUDT Type Script
+	let script_args: Bytes = seller_lock_script_hash.to_vec().into();
+	let other_sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+
+	// Prepare Token Sell Lock Script
+	let script_args: Bytes = sudt_type_script_hash.to_vec().into();
+	let token_sell_lock_script = context.build_script(&out_point_token_sell_lock, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let mut data = vec!();
+	data.extend(100u128.to_le_bytes().to_vec());
+	data.extend(seller_lock_script_hash.to_vec());
+	data.extend(50_000_000_000u64.to_le_bytes().to_vec());
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_sell_lock_script.clone()).type_(Some(other_sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).build(), Bytes::new());
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(other_sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(50_000_000_000_u64.pack()).lock(seller_lock_script.clone()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(50_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	let data = 100u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	outputs_data.push(Bytes::new());
+	outputs_data.push(Bytes::new());
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.cell_dep(token_sell_lock_dep)
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Run
+	let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+	assert_error_eq!(err, ScriptError::ValidationFailure(ERROR_TYPE_HASH).input_lock_script(0));
+}
+
+#[test]
+fn test_sell_multiple_sell_cells_same_owner()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_sell_lock = context.deploy_cell(Loader::default().load_binary("token-sell-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_sell_lock_dep = CellDep::new_builder().out_point(out_point_token_sell_lock.clone()).build();
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	// let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Token Sell Lock Script
+	let script_args: Bytes = sudt_type_script_hash.to_vec().into();
+	let token_sell_lock_script = context.build_script(&out_point_token_sell_lock, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let mut data = vec!();
+	data.extend(100u128.to_le_bytes().to_vec());
+	data.extend(seller_lock_script_hash.to_vec());
+	data.extend(50_000_000_000u64.to_le_bytes().to_vec());
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_sell_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let mut data = vec!();
+	data.extend(100u128.to_le_bytes().to_vec());
+	data.extend(seller_lock_script_hash.to_vec());
+	data.extend(50_000_000_000u64.to_le_bytes().to_vec());
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_sell_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).build(), Bytes::new());
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	let data = 200u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	outputs_data.push(Bytes::new());
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.cell_dep(token_sell_lock_dep)
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Run
+	let _cycles = context.verify_tx(&tx, MAX_CYCLES).expect("pass verification");
+	// println!("consume cycles: {}", cycles);
+}
+
+#[test]
+fn test_sell_multiple_tokens_same_owner()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_sell_lock = context.deploy_cell(Loader::default().load_binary("token-sell-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_sell_lock_dep = CellDep::new_builder().out_point(out_point_token_sell_lock.clone()).build();
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	// let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Second SUDT Type Script
+	let sudt_owner_lock_script2 = context.build_script(&out_point_always_success, Bytes::from(vec![4u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash2: [u8; 32] = sudt_owner_lock_script2.calc_script_hash().unpack();
+	let script_args: Bytes = sudt_owner_lock_script_hash2.to_vec().into();
+	let sudt_type_script2 = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash2: [u8; 32] = sudt_type_script2.calc_script_hash().unpack();
+
+	// Prepare Token Sell Lock Scripts
+	let script_args: Bytes = sudt_type_script_hash.to_vec().into();
+	let token_sell_lock_script = context.build_script(&out_point_token_sell_lock, script_args).expect("script");
+	let script_args: Bytes = sudt_type_script_hash2.to_vec().into();
+	let token_sell_lock_script2 = context.build_script(&out_point_token_sell_lock, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let mut data = vec!();
+	data.extend(100u128.to_le_bytes().to_vec());
+	data.extend(seller_lock_script_hash.to_vec());
+	data.extend(50_000_000_000u64.to_le_bytes().to_vec());
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_sell_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let mut data = vec!();
+	data.extend(100u128.to_le_bytes().to_vec());
+	data.extend(seller_lock_script_hash.to_vec());
+	data.extend(50_000_000_000u64.to_le_bytes().to_vec());
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_sell_lock_script2.clone()).type_(Some(sudt_type_script2.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).build(), Bytes::new());
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(sudt_type_script2.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	let data = 100u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 100u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	outputs_data.push(Bytes::new());
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.cell_dep(token_sell_lock_dep)
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Run
+	let _cycles = context.verify_tx(&tx, MAX_CYCLES).expect("pass verification");
+	// println!("consume cycles: {}", cycles);
+}
+
+#[test]
+fn test_sell_multiple_tokens_same_owner_short()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_sell_lock = context.deploy_cell(Loader::default().load_binary("token-sell-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_sell_lock_dep = CellDep::new_builder().out_point(out_point_token_sell_lock.clone()).build();
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	// let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Second SUDT Type Script
+	let sudt_owner_lock_script2 = context.build_script(&out_point_always_success, Bytes::from(vec![4u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash2: [u8; 32] = sudt_owner_lock_script2.calc_script_hash().unpack();
+	let script_args: Bytes = sudt_owner_lock_script_hash2.to_vec().into();
+	let sudt_type_script2 = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash2: [u8; 32] = sudt_type_script2.calc_script_hash().unpack();
+
+	// Prepare Token Sell Lock Scripts
+	let script_args: Bytes = sudt_type_script_hash.to_vec().into();
+	let token_sell_lock_script = context.build_script(&out_point_token_sell_lock, script_args).expect("script");
+	let script_args: Bytes = sudt_type_script_hash2.to_vec().into();
+	let token_sell_lock_script2 = context.build_script(&out_point_token_sell_lock, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let mut data = vec!();
+	data.extend(100u128.to_le_bytes().to_vec());
+	data.extend(seller_lock_script_hash.to_vec());
+	data.extend(50_000_000_000u64.to_le_bytes().to_vec());
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_sell_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let mut data = vec!();
+	data.extend(100u128.to_le_bytes().to_vec());
+	data.extend(seller_lock_script_hash.to_vec());
+	data.extend(50_000_000_000u64.to_le_bytes().to_vec());
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_sell_lock_script2.clone()).type_(Some(sudt_type_script2.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).build(), Bytes::new());
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(sudt_type_script2.clone()).pack()).build();
+	outputs.push(output);
+	// Enough to pay either order alone, but not both.
+	let output = CellOutput::new_builder().capacity(50_000_000_000_u64.pack()).lock(seller_lock_script.clone()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	let data = 100u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 100u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	outputs_data.push(Bytes::new());
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.cell_dep(token_sell_lock_dep)
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Run
+	let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+	assert_error_eq!(err, ScriptError::ValidationFailure(ERROR_AMOUNT).input_lock_script(0));
+}
+
+#[test]
+fn test_sell_multiple_sell_cells_different_owners()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_sell_lock = context.deploy_cell(Loader::default().load_binary("token-sell-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_sell_lock_dep = CellDep::new_builder().out_point(out_point_token_sell_lock.clone()).build();
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	// let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
+	let seller_lock_script2 = context.build_script(&out_point_always_success, Bytes::from(vec![2u8; 1])).expect("script");
+	let seller_lock_script2_hash: [u8; 32] = seller_lock_script2.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Token Sell Lock Script
+	let script_args: Bytes = sudt_type_script_hash.to_vec().into();
+	let token_sell_lock_script = context.build_script(&out_point_token_sell_lock, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let mut data = vec!();
+	data.extend(100u128.to_le_bytes().to_vec());
+	data.extend(seller_lock_script_hash.to_vec());
+	data.extend(50_000_000_000u64.to_le_bytes().to_vec());
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_sell_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let mut data = vec!();
+	data.extend(100u128.to_le_bytes().to_vec());
+	data.extend(seller_lock_script2_hash.to_vec());
+	data.extend(50_000_000_000u64.to_le_bytes().to_vec());
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_sell_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).build(), Bytes::new());
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(50_000_000_000_u64.pack()).lock(seller_lock_script.clone()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(50_000_000_000_u64.pack()).lock(seller_lock_script2.clone()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	let data = 200u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	outputs_data.push(Bytes::new());
+	outputs_data.push(Bytes::new());
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.cell_dep(token_sell_lock_dep)
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Run
+	let _cycles = context.verify_tx(&tx, MAX_CYCLES).expect("pass verification");
+	// println!("consume cycles: {}", cycles);
+}
+
+#[test]
+fn test_sell_multiple_sell_cells_different_owners_short()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_sell_lock = context.deploy_cell(Loader::default().load_binary("token-sell-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_sell_lock_dep = CellDep::new_builder().out_point(out_point_token_sell_lock.clone()).build();
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	// let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
+	let seller_lock_script2 = context.build_script(&out_point_always_success, Bytes::from(vec![2u8; 1])).expect("script");
+	let seller_lock_script2_hash: [u8; 32] = seller_lock_script2.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Token Sell Lock Script
+	let script_args: Bytes = sudt_type_script_hash.to_vec().into();
+	let token_sell_lock_script = context.build_script(&out_point_token_sell_lock, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let mut data = vec!();
+	data.extend(100u128.to_le_bytes().to_vec());
+	data.extend(seller_lock_script_hash.to_vec());
+	data.extend(50_000_000_000u64.to_le_bytes().to_vec());
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_sell_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let mut data = vec!();
+	data.extend(100u128.to_le_bytes().to_vec());
+	data.extend(seller_lock_script2_hash.to_vec());
+	data.extend(50_000_000_000u64.to_le_bytes().to_vec());
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_sell_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).build(), Bytes::new());
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(50_000_000_000_u64.pack()).lock(seller_lock_script.clone()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(49_999_999_999_u64.pack()).lock(seller_lock_script2.clone()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	let data = 200u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	outputs_data.push(Bytes::new());
+	outputs_data.push(Bytes::new());
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.cell_dep(token_sell_lock_dep)
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Run
+	let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+	assert_error_eq!(err, ScriptError::ValidationFailure(ERROR_AMOUNT).input_lock_script(0));
+}
+
+#[test]
+fn test_sell_withdrawal_by_owner()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_sell_lock = context.deploy_cell(Loader::default().load_binary("token-sell-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_sell_lock_dep = CellDep::new_builder().out_point(out_point_token_sell_lock.clone()).build();
+
+	// Prepare Identities
+	// let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	// let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Token Sell Lock Script
+	let script_args: Bytes = sudt_type_script_hash.to_vec().into();
+	let token_sell_lock_script = context.build_script(&out_point_token_sell_lock, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let mut data = vec!();
+	data.extend(100u128.to_le_bytes().to_vec());
+	data.extend(seller_lock_script_hash.to_vec());
+	data.extend(50_000_000_000u64.to_le_bytes().to_vec());
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_sell_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).build(), Bytes::new());
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	let data = 100u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	outputs_data.push(Bytes::new());
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.cell_dep(token_sell_lock_dep)
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Run
+	let _cycles = context.verify_tx(&tx, MAX_CYCLES).expect("pass verification");
+	// println!("consume cycles: {}", cycles);
+}
+
+#[test]
+fn test_sell_withdrawal_by_not_owner()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_sell_lock = context.deploy_cell(Loader::default().load_binary("token-sell-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_sell_lock_dep = CellDep::new_builder().out_point(out_point_token_sell_lock.clone()).build();
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	// let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Token Sell Lock Script
+	let script_args: Bytes = sudt_type_script_hash.to_vec().into();
+	let token_sell_lock_script = context.build_script(&out_point_token_sell_lock, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let mut data = vec!();
+	data.extend(100u128.to_le_bytes().to_vec());
+	data.extend(seller_lock_script_hash.to_vec());
+	data.extend(50_000_000_000u64.to_le_bytes().to_vec());
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_sell_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).build(), Bytes::new());
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	let data = 100u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	outputs_data.push(Bytes::new());
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.cell_dep(token_sell_lock_dep)
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Run
+	let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+	assert_error_eq!(err, ScriptError::ValidationFailure(ERROR_AMOUNT).input_lock_script(0));
+}
+
+#[test]
+fn test_sell_multiple_requirement_overflow()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_sell_lock = context.deploy_cell(Loader::default().load_binary("token-sell-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_sell_lock_dep = CellDep::new_builder().out_point(out_point_token_sell_lock.clone()).build();
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	// let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Token Sell Lock Script
+	let script_args: Bytes = sudt_type_script_hash.to_vec().into();
+	let token_sell_lock_script = context.build_script(&out_point_token_sell_lock, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let mut data = vec!();
+	data.extend(100u128.to_le_bytes().to_vec());
+	data.extend(seller_lock_script_hash.to_vec());
+	data.extend(u64::MAX.to_le_bytes().to_vec());
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_sell_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let mut data = vec!();
+	data.extend(100u128.to_le_bytes().to_vec());
+	data.extend(seller_lock_script_hash.to_vec());
+	data.extend(u64::MAX.to_le_bytes().to_vec());
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_sell_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).build(), Bytes::new());
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	let data = 200u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	outputs_data.push(Bytes::new());
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.cell_dep(token_sell_lock_dep)
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Run
+	let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+	assert_error_eq!(err, ScriptError::ValidationFailure(ERROR_OVERFLOW).input_lock_script(0));
+}
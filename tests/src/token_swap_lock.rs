@@ -0,0 +1,1271 @@
+use super::*;
+use ckb_testtool::{builtin::ALWAYS_SUCCESS, context::Context};
+use ckb_tool::{ckb_error::assert_error_eq, ckb_script::ScriptError};
+use ckb_tool::ckb_types::{bytes::Bytes, packed::*, prelude::*};
+use ckb_tool::ckb_types::core::{TransactionBuilder};
+
+// Constants
+const MAX_CYCLES: u64 = 100_000_000;
+
+// Error Codes
+const ERROR_AMOUNT: i8 = 5;
+const ERROR_ARGS_LEN: i8 = 6;
+const ERROR_DATA_LEN: i8 = 7;
+const ERROR_TYPE_HASH: i8 = 8;
+const ERROR_OVERFLOW: i8 = 9;
+
+#[test]
+fn test_swap_exact()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_swap_lock = context.deploy_cell(Loader::default().load_binary("token-swap-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_swap_lock_dep = CellDep::new_builder().out_point(out_point_token_swap_lock.clone()).build();
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	// let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+	let wanted_sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![4u8; 1])).expect("script");
+	let wanted_sudt_owner_lock_script_hash: [u8; 32] = wanted_sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare Offered SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	// let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Wanted SUDT Type Script
+	let script_args: Bytes = wanted_sudt_owner_lock_script_hash.to_vec().into();
+	let wanted_sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let wanted_sudt_type_script_hash: [u8; 32] = wanted_sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Token Swap Lock Script
+	let script_args: Bytes = wanted_sudt_type_script_hash.to_vec().into();
+	let token_swap_lock_script = context.build_script(&out_point_token_swap_lock, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let mut data = vec!();
+	data.extend(100u128.to_le_bytes().to_vec());
+	data.extend(seller_lock_script_hash.to_vec());
+	data.extend(50u128.to_le_bytes().to_vec());
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_swap_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let data = 500u128.to_le_bytes().to_vec();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(wanted_sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(wanted_sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(wanted_sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	let data = 100u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 50u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 450u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.cell_dep(token_swap_lock_dep)
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Run
+	let _cycles = context.verify_tx(&tx, MAX_CYCLES).expect("pass verification");
+	// println!("consume cycles: {}", cycles);
+}
+
+#[test]
+fn test_swap_extra()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_swap_lock = context.deploy_cell(Loader::default().load_binary("token-swap-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_swap_lock_dep = CellDep::new_builder().out_point(out_point_token_swap_lock.clone()).build();
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	// let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+	let wanted_sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![4u8; 1])).expect("script");
+	let wanted_sudt_owner_lock_script_hash: [u8; 32] = wanted_sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare Offered SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	// let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Wanted SUDT Type Script
+	let script_args: Bytes = wanted_sudt_owner_lock_script_hash.to_vec().into();
+	let wanted_sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let wanted_sudt_type_script_hash: [u8; 32] = wanted_sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Token Swap Lock Script
+	let script_args: Bytes = wanted_sudt_type_script_hash.to_vec().into();
+	let token_swap_lock_script = context.build_script(&out_point_token_swap_lock, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let mut data = vec!();
+	data.extend(100u128.to_le_bytes().to_vec());
+	data.extend(seller_lock_script_hash.to_vec());
+	data.extend(50u128.to_le_bytes().to_vec());
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_swap_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let data = 500u128.to_le_bytes().to_vec();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(wanted_sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(wanted_sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(wanted_sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	let data = 100u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 60u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 440u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.cell_dep(token_swap_lock_dep)
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Run
+	let _cycles = context.verify_tx(&tx, MAX_CYCLES).expect("pass verification");
+	// println!("consume cycles: {}", cycles);
+}
+
+#[test]
+fn test_swap_short()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_swap_lock = context.deploy_cell(Loader::default().load_binary("token-swap-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_swap_lock_dep = CellDep::new_builder().out_point(out_point_token_swap_lock.clone()).build();
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	// let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+	let wanted_sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![4u8; 1])).expect("script");
+	let wanted_sudt_owner_lock_script_hash: [u8; 32] = wanted_sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare Offered SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	// let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Wanted SUDT Type Script
+	let script_args: Bytes = wanted_sudt_owner_lock_script_hash.to_vec().into();
+	let wanted_sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let wanted_sudt_type_script_hash: [u8; 32] = wanted_sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Token Swap Lock Script
+	let script_args: Bytes = wanted_sudt_type_script_hash.to_vec().into();
+	let token_swap_lock_script = context.build_script(&out_point_token_swap_lock, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let mut data = vec!();
+	data.extend(100u128.to_le_bytes().to_vec());
+	data.extend(seller_lock_script_hash.to_vec());
+	data.extend(50u128.to_le_bytes().to_vec());
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_swap_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let data = 500u128.to_le_bytes().to_vec();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(wanted_sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(wanted_sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(wanted_sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	let data = 100u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 49u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 451u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.cell_dep(token_swap_lock_dep)
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Run
+	let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+	assert_error_eq!(err, ScriptError::ValidationFailure(ERROR_AMOUNT).input_lock_script(0));
+}
+
+#[test]
+fn test_swap_bound_payment()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_swap_lock = context.deploy_cell(Loader::default().load_binary("token-swap-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_swap_lock_dep = CellDep::new_builder().out_point(out_point_token_swap_lock.clone()).build();
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	// let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+	let wanted_sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![4u8; 1])).expect("script");
+	let wanted_sudt_owner_lock_script_hash: [u8; 32] = wanted_sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare Offered SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	// let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Wanted SUDT Type Script
+	let script_args: Bytes = wanted_sudt_owner_lock_script_hash.to_vec().into();
+	let wanted_sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let wanted_sudt_type_script_hash: [u8; 32] = wanted_sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Token Swap Lock Script
+	let script_args: Bytes = wanted_sudt_type_script_hash.to_vec().into();
+	let token_swap_lock_script = context.build_script(&out_point_token_swap_lock, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let mut data = vec!();
+	data.extend(100u128.to_le_bytes().to_vec());
+	data.extend(seller_lock_script_hash.to_vec());
+	data.extend(50u128.to_le_bytes().to_vec());
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_swap_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let data = 500u128.to_le_bytes().to_vec();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(wanted_sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(wanted_sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(wanted_sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	let data = 100u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	// The payment is bound to a Token Buy Lock order, so it cannot also pay the swap.
+	let mut data = 50u128.to_le_bytes().to_vec();
+	data.extend(b"TBLB".to_vec());
+	data.extend([9u8; 36].to_vec());
+	outputs_data.push(Bytes::from(data));
+	let data = 450u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.cell_dep(token_swap_lock_dep)
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Run
+	let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+	assert_error_eq!(err, ScriptError::ValidationFailure(ERROR_AMOUNT).input_lock_script(0));
+}
+
+#[test]
+fn test_swap_wrong_tokens()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_swap_lock = context.deploy_cell(Loader::default().load_binary("token-swap-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_swap_lock_dep = CellDep::new_builder().out_point(out_point_token_swap_lock.clone()).build();
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	// let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+	let wanted_sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![4u8; 1])).expect("script");
+	let wanted_sudt_owner_lock_script_hash: [u8; 32] = wanted_sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare Offered SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	// let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Wanted SUDT Type Script
+	let script_args: Bytes = wanted_sudt_owner_lock_script_hash.to_vec().into();
+	let wanted_sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let wanted_sudt_type_script_hash: [u8; 32] = wanted_sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Other SUDT Type Script
+	let script_args: Bytes = seller_lock_script_hash.to_vec().into();
+	let other_sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+
+	// Prepare Token Swap Lock Script
+	let script_args: Bytes = wanted_sudt_type_script_hash.to_vec().into();
+	let token_swap_lock_script = context.build_script(&out_point_token_swap_lock, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let mut data = vec!();
+	data.extend(100u128.to_le_bytes().to_vec());
+	data.extend(seller_lock_script_hash.to_vec());
+	data.extend(50u128.to_le_bytes().to_vec());
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_swap_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let data = 500u128.to_le_bytes().to_vec();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(other_sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(other_sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(other_sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	let data = 100u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 50u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 450u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.cell_dep(token_swap_lock_dep)
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Run
+	let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+	assert_error_eq!(err, ScriptError::ValidationFailure(ERROR_AMOUNT).input_lock_script(0));
+}
+
+#[test]
+fn test_swap_invalid_args()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_swap_lock = context.deploy_cell(Loader::default().load_binary("token-swap-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_swap_lock_dep = CellDep::new_builder().out_point(out_point_token_swap_lock.clone()).build();
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	// let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+	let wanted_sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![4u8; 1])).expect("script");
+	let wanted_sudt_owner_lock_script_hash: [u8; 32] = wanted_sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare Offered SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	// let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Wanted SUDT Type Script
+	let script_args: Bytes = wanted_sudt_owner_lock_script_hash.to_vec().into();
+	let wanted_sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	// let wanted_sudt_type_script_hash: [u8; 32] = wanted_sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Token Swap Lock Script
+	let script_args: Bytes = [0u8; 16].to_vec().into(); // Invalid args.
+	let token_swap_lock_script = context.build_script(&out_point_token_swap_lock, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let mut data = vec!();
+	data.extend(100u128.to_le_bytes().to_vec());
+	data.extend(seller_lock_script_hash.to_vec());
+	data.extend(50u128.to_le_bytes().to_vec());
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_swap_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let data = 500u128.to_le_bytes().to_vec();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(wanted_sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(wanted_sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(wanted_sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	let data = 100u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 50u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 450u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.cell_dep(token_swap_lock_dep)
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Run
+	let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+	assert_error_eq!(err, ScriptError::ValidationFailure(ERROR_ARGS_LEN).input_lock_script(0));
+}
+
+#[test]
+fn test_swap_invalid_data()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_swap_lock = context.deploy_cell(Loader::default().load_binary("token-swap-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_swap_lock_dep = CellDep::new_builder().out_point(out_point_token_swap_lock.clone()).build();
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	// let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+	let wanted_sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![4u8; 1])).expect("script");
+	let wanted_sudt_owner_lock_script_hash: [u8; 32] = wanted_sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare Offered SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	// let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Wanted SUDT Type Script
+	let script_args: Bytes = wanted_sudt_owner_lock_script_hash.to_vec().into();
+	let wanted_sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let wanted_sudt_type_script_hash: [u8; 32] = wanted_sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Token Swap Lock Script
+	let script_args: Bytes = wanted_sudt_type_script_hash.to_vec().into();
+	let token_swap_lock_script = context.build_script(&out_point_token_swap_lock, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let mut data = vec!();
+	data.extend(100u128.to_le_bytes().to_vec());
+	data.extend(seller_lock_script_hash.to_vec());
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_swap_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let data = 500u128.to_le_bytes().to_vec();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(wanted_sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(wanted_sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(wanted_sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	let data = 100u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 50u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 450u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.cell_dep(token_swap_lock_dep)
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Run
+	let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+	assert_error_eq!(err, ScriptError::ValidationFailure(ERROR_DATA_LEN).input_lock_script(0));
+}
+
+#[test]
+fn test_swap_same_token()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_swap_lock = context.deploy_cell(Loader::default().load_binary("token-swap-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_swap_lock_dep = CellDep::new_builder().out_point(out_point_token_swap_lock.clone()).build();
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	// let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+	let wanted_sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![4u8; 1])).expect("script");
+	let wanted_sudt_owner_lock_script_hash: [u8; 32] = wanted_sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare Offered SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Wanted SUDT Type Script
+	let script_args: Bytes = wanted_sudt_owner_lock_script_hash.to_vec().into();
+	let wanted_sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	// let wanted_sudt_type_script_hash: [u8; 32] = wanted_sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Token Swap Lock Script
+	let script_args: Bytes = sudt_type_script_hash.to_vec().into();
+	let token_swap_lock_script = context.build_script(&out_point_token_swap_lock, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let mut data = vec!();
+	data.extend(100u128.to_le_bytes().to_vec());
+	data.extend(seller_lock_script_hash.to_vec());
+	data.extend(50u128.to_le_bytes().to_vec());
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_swap_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let data = 500u128.to_le_bytes().to_vec();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(wanted_sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(wanted_sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(wanted_sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	let data = 100u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 50u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 450u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.cell_dep(token_swap_lock_dep)
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Run
+	let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+	assert_error_eq!(err, ScriptError::ValidationFailure(ERROR_TYPE_HASH).input_lock_script(0));
+}
+
+#[test]
+fn test_swap_wanted_token_not_sudt()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_swap_lock = context.deploy_cell(Loader::default().load_binary("token-swap-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_swap_lock_dep = CellDep::new_builder().out_point(out_point_token_swap_lock.clone()).build();
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	// let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+	let wanted_sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![4u8; 1])).expect("script");
+	let wanted_sudt_owner_lock_script_hash: [u8; 32] = wanted_sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare Offered SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	// let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Wanted Type Script, which is not an SUDT.
+	let script_args: Bytes = wanted_sudt_owner_lock_script_hash.to_vec().into();
+	let wanted_sudt_type_script = context.build_script(&out_point_always_success, script_args).expect("script");
+	let wanted_sudt_type_script_hash: [u8; 32] = wanted_sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Token Swap Lock Script
+	let script_args: Bytes = wanted_sudt_type_script_hash.to_vec().into();
+	let token_swap_lock_script = context.build_script(&out_point_token_swap_lock, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let mut data = vec!();
+	data.extend(100u128.to_le_bytes().to_vec());
+	data.extend(seller_lock_script_hash.to_vec());
+	data.extend(50u128.to_le_bytes().to_vec());
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_swap_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let data = 500u128.to_le_bytes().to_vec();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(wanted_sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(wanted_sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(wanted_sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	let data = 100u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 50u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 450u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.cell_dep(token_swap_lock_dep)
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Run
+	let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+	assert_error_eq!(err, ScriptError::ValidationFailure(ERROR_TYPE_HASH).input_lock_script(0));
+}
+
+#[test]
+fn test_swap_overflow()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_swap_lock = context.deploy_cell(Loader::default().load_binary("token-swap-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_swap_lock_dep = CellDep::new_builder().out_point(out_point_token_swap_lock.clone()).build();
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	// let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+	let wanted_sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![4u8; 1])).expect("script");
+	let wanted_sudt_owner_lock_script_hash: [u8; 32] = wanted_sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare Offered SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	// let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Wanted SUDT Type Script
+	let script_args: Bytes = wanted_sudt_owner_lock_script_hash.to_vec().into();
+	let wanted_sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let wanted_sudt_type_script_hash: [u8; 32] = wanted_sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Token Swap Lock Script
+	let script_args: Bytes = wanted_sudt_type_script_hash.to_vec().into();
+	let token_swap_lock_script = context.build_script(&out_point_token_swap_lock, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let mut data = vec!();
+	data.extend(100u128.to_le_bytes().to_vec());
+	data.extend(seller_lock_script_hash.to_vec());
+	data.extend(50u128.to_le_bytes().to_vec());
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_swap_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let data = 500u128.to_le_bytes().to_vec();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(wanted_sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(wanted_sudt_owner_lock_script.clone()).build(), Bytes::new());
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(wanted_sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(wanted_sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	// The two payments to the seller sum to more than a u128 can hold.
+	let mut outputs_data: Vec<Bytes> = vec![];
+	let data = 100u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = (u128::MAX / 2 + 1).to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = (u128::MAX / 2 + 1).to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.cell_dep(token_swap_lock_dep)
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Run
+	let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+	assert_error_eq!(err, ScriptError::ValidationFailure(ERROR_OVERFLOW).input_lock_script(0));
+}
+
+#[test]
+fn test_swap_multiple_swap_cells_different_owners()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_swap_lock = context.deploy_cell(Loader::default().load_binary("token-swap-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_swap_lock_dep = CellDep::new_builder().out_point(out_point_token_swap_lock.clone()).build();
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	// let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
+	let seller_lock_script2 = context.build_script(&out_point_always_success, Bytes::from(vec![2u8; 1])).expect("script");
+	let seller_lock_script2_hash: [u8; 32] = seller_lock_script2.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+	let wanted_sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![4u8; 1])).expect("script");
+	let wanted_sudt_owner_lock_script_hash: [u8; 32] = wanted_sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare Offered SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	// let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Wanted SUDT Type Script
+	let script_args: Bytes = wanted_sudt_owner_lock_script_hash.to_vec().into();
+	let wanted_sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let wanted_sudt_type_script_hash: [u8; 32] = wanted_sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Token Swap Lock Script
+	let script_args: Bytes = wanted_sudt_type_script_hash.to_vec().into();
+	let token_swap_lock_script = context.build_script(&out_point_token_swap_lock, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let mut data = vec!();
+	data.extend(100u128.to_le_bytes().to_vec());
+	data.extend(seller_lock_script_hash.to_vec());
+	data.extend(50u128.to_le_bytes().to_vec());
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_swap_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let mut data = vec!();
+	data.extend(100u128.to_le_bytes().to_vec());
+	data.extend(seller_lock_script2_hash.to_vec());
+	data.extend(50u128.to_le_bytes().to_vec());
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_swap_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let data = 500u128.to_le_bytes().to_vec();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(wanted_sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(wanted_sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script2.clone()).type_(Some(wanted_sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(wanted_sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	let data = 200u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 50u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 50u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 400u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.cell_dep(token_swap_lock_dep)
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Run
+	let _cycles = context.verify_tx(&tx, MAX_CYCLES).expect("pass verification");
+	// println!("consume cycles: {}", cycles);
+}
+
+#[test]
+fn test_swap_multiple_swap_cells_different_owners_short()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_swap_lock = context.deploy_cell(Loader::default().load_binary("token-swap-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_swap_lock_dep = CellDep::new_builder().out_point(out_point_token_swap_lock.clone()).build();
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	// let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
+	let seller_lock_script2 = context.build_script(&out_point_always_success, Bytes::from(vec![2u8; 1])).expect("script");
+	let seller_lock_script2_hash: [u8; 32] = seller_lock_script2.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+	let wanted_sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![4u8; 1])).expect("script");
+	let wanted_sudt_owner_lock_script_hash: [u8; 32] = wanted_sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare Offered SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	// let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Wanted SUDT Type Script
+	let script_args: Bytes = wanted_sudt_owner_lock_script_hash.to_vec().into();
+	let wanted_sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let wanted_sudt_type_script_hash: [u8; 32] = wanted_sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Token Swap Lock Script
+	let script_args: Bytes = wanted_sudt_type_script_hash.to_vec().into();
+	let token_swap_lock_script = context.build_script(&out_point_token_swap_lock, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let mut data = vec!();
+	data.extend(100u128.to_le_bytes().to_vec());
+	data.extend(seller_lock_script_hash.to_vec());
+	data.extend(50u128.to_le_bytes().to_vec());
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_swap_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let mut data = vec!();
+	data.extend(100u128.to_le_bytes().to_vec());
+	data.extend(seller_lock_script2_hash.to_vec());
+	data.extend(50u128.to_le_bytes().to_vec());
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_swap_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let data = 500u128.to_le_bytes().to_vec();
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(wanted_sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(wanted_sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script2.clone()).type_(Some(wanted_sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(wanted_sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	let data = 200u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 50u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 49u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	let data = 401u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.cell_dep(token_swap_lock_dep)
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Run
+	let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+	assert_error_eq!(err, ScriptError::ValidationFailure(ERROR_AMOUNT).input_lock_script(0));
+}
+
+#[test]
+fn test_swap_withdrawal_by_owner()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_swap_lock = context.deploy_cell(Loader::default().load_binary("token-swap-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_swap_lock_dep = CellDep::new_builder().out_point(out_point_token_swap_lock.clone()).build();
+
+	// Prepare Identities
+	// let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	// let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+	let wanted_sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![4u8; 1])).expect("script");
+	let wanted_sudt_owner_lock_script_hash: [u8; 32] = wanted_sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare Offered SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	// let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Wanted SUDT Type Script
+	let script_args: Bytes = wanted_sudt_owner_lock_script_hash.to_vec().into();
+	let wanted_sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let wanted_sudt_type_script_hash: [u8; 32] = wanted_sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Token Swap Lock Script
+	let script_args: Bytes = wanted_sudt_type_script_hash.to_vec().into();
+	let token_swap_lock_script = context.build_script(&out_point_token_swap_lock, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let mut data = vec!();
+	data.extend(100u128.to_le_bytes().to_vec());
+	data.extend(seller_lock_script_hash.to_vec());
+	data.extend(50u128.to_le_bytes().to_vec());
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_swap_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).build(), Bytes::new());
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(seller_lock_script.clone()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	let data = 100u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	outputs_data.push(Bytes::new());
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.cell_dep(token_swap_lock_dep)
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Run
+	let _cycles = context.verify_tx(&tx, MAX_CYCLES).expect("pass verification");
+	// println!("consume cycles: {}", cycles);
+}
+
+#[test]
+fn test_swap_withdrawal_by_not_owner()
+{
+	// Create Context
+	let mut context = Context::default();
+
+	// Deploy Contracts
+	let out_point_always_success = context.deploy_cell(ALWAYS_SUCCESS.clone());
+	let out_point_sudt = context.deploy_cell(Loader::default().load_binary("sudt"));
+	let out_point_token_swap_lock = context.deploy_cell(Loader::default().load_binary("token-swap-lock"));
+
+	// Prepare Cell Deps
+	let always_success_dep = CellDep::new_builder().out_point(out_point_always_success.clone()).build();
+	let sudt_dep = CellDep::new_builder().out_point(out_point_sudt.clone()).build();
+	let token_swap_lock_dep = CellDep::new_builder().out_point(out_point_token_swap_lock.clone()).build();
+
+	// Prepare Identities
+	let buyer_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![0u8; 1])).expect("script");
+	// let buyer_lock_script_hash: [u8; 32] = buyer_lock_script.calc_script_hash().unpack();
+	let seller_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![1u8; 1])).expect("script");
+	let seller_lock_script_hash: [u8; 32] = seller_lock_script.calc_script_hash().unpack();
+	let sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![3u8; 1])).expect("script");
+	let sudt_owner_lock_script_hash: [u8; 32] = sudt_owner_lock_script.calc_script_hash().unpack();
+	let wanted_sudt_owner_lock_script = context.build_script(&out_point_always_success, Bytes::from(vec![4u8; 1])).expect("script");
+	let wanted_sudt_owner_lock_script_hash: [u8; 32] = wanted_sudt_owner_lock_script.calc_script_hash().unpack();
+
+	// Prepare Offered SUDT Type Script
+	let script_args: Bytes = sudt_owner_lock_script_hash.to_vec().into();
+	let sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	// let sudt_type_script_hash: [u8; 32] = sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Wanted SUDT Type Script
+	let script_args: Bytes = wanted_sudt_owner_lock_script_hash.to_vec().into();
+	let wanted_sudt_type_script = context.build_script(&out_point_sudt, script_args).expect("script");
+	let wanted_sudt_type_script_hash: [u8; 32] = wanted_sudt_type_script.calc_script_hash().unpack();
+
+	// Prepare Token Swap Lock Script
+	let script_args: Bytes = wanted_sudt_type_script_hash.to_vec().into();
+	let token_swap_lock_script = context.build_script(&out_point_token_swap_lock, script_args).expect("script");
+
+	// Prepare Input Cells
+	let mut inputs = vec![];
+	let mut data = vec!();
+	data.extend(100u128.to_le_bytes().to_vec());
+	data.extend(seller_lock_script_hash.to_vec());
+	data.extend(50u128.to_le_bytes().to_vec());
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(token_swap_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build(), Bytes::from(data));
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	let input_out_point = context.create_cell(CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).build(), Bytes::new());
+	let input = CellInput::new_builder().previous_output(input_out_point).build();
+	inputs.push(input);
+	
+	// Prepare Output Cells
+	let mut outputs = vec![];
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).type_(Some(sudt_type_script.clone()).pack()).build();
+	outputs.push(output);
+	let output = CellOutput::new_builder().capacity(100_000_000_000_u64.pack()).lock(buyer_lock_script.clone()).build();
+	outputs.push(output);
+
+	// Prepare Output Data
+	let mut outputs_data: Vec<Bytes> = vec![];
+	let data = 100u128.to_le_bytes().to_vec();
+	outputs_data.push(Bytes::from(data));
+	outputs_data.push(Bytes::new());
+
+	// Build Transaction
+	let tx = TransactionBuilder::default()
+		.inputs(inputs)
+		.outputs(outputs)
+		.outputs_data(outputs_data.pack())
+		.cell_dep(always_success_dep)
+		.cell_dep(sudt_dep)
+		.cell_dep(token_swap_lock_dep)
+		.build();
+	let tx = context.complete_tx(tx);
+
+	// Run
+	let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+	assert_error_eq!(err, ScriptError::ValidationFailure(ERROR_AMOUNT).input_lock_script(0));
+}
@@ -0,0 +1,19 @@
+use std::env;
+use std::path::PathBuf;
+
+use molecule_codegen::{Compiler, Language};
+
+// Generate the Rust types for the Molecule schema at build time.
+fn main()
+{
+	let schema_path = "schemas/token_buy_lock.mol";
+	println!("cargo:rerun-if-changed={}", schema_path);
+
+	let out_dir = PathBuf::from(env::var("OUT_DIR").expect("OUT_DIR"));
+	Compiler::new()
+		.input_schema_file(schema_path)
+		.generate_code(Language::Rust)
+		.output_dir(out_dir)
+		.run()
+		.expect("generate molecule types");
+}
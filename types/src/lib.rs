@@ -0,0 +1,95 @@
+//! Molecule types for the Token Buy Lock order args and cell data.
+//!
+//! The types are generated from `schemas/token_buy_lock.mol` at build time.
+//! The crate is `no_std` by default for use in contracts. Enable the `std` feature to use it on the host.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+use molecule::bytes::Bytes;
+use molecule::prelude::*;
+
+pub use molecule;
+
+#[allow(clippy::all)]
+pub mod token_buy_lock
+{
+	include!(concat!(env!("OUT_DIR"), "/token_buy_lock.rs"));
+}
+
+use token_buy_lock::{Byte20, Byte32, Uint16, Uint32, Uint64, Uint128};
+
+/// The version byte of args and cell data which use `OrderDataV1`.
+pub const ORDER_VERSION_V1: u8 = 1;
+
+/// The version byte of args and cell data which use `OrderDataV2`.
+pub const ORDER_VERSION_V2: u8 = 2;
+
+/// The version byte which prefixes Molecule encoded args and cell data of new orders, which use `OrderData`.
+pub const ORDER_VERSION: u8 = 3;
+
+impl From<[u8; 20]> for Byte20
+{
+	fn from(value: [u8; 20]) -> Self
+	{
+		Byte20::new_unchecked(Bytes::from(value.to_vec()))
+	}
+}
+
+impl From<[u8; 32]> for Byte32
+{
+	fn from(value: [u8; 32]) -> Self
+	{
+		Byte32::new_unchecked(Bytes::from(value.to_vec()))
+	}
+}
+
+impl From<u16> for Uint16
+{
+	fn from(value: u16) -> Self
+	{
+		Uint16::new_unchecked(Bytes::from(value.to_le_bytes().to_vec()))
+	}
+}
+
+impl From<u32> for Uint32
+{
+	fn from(value: u32) -> Self
+	{
+		Uint32::new_unchecked(Bytes::from(value.to_le_bytes().to_vec()))
+	}
+}
+
+impl From<u64> for Uint64
+{
+	fn from(value: u64) -> Self
+	{
+		Uint64::new_unchecked(Bytes::from(value.to_le_bytes().to_vec()))
+	}
+}
+
+impl From<u128> for Uint128
+{
+	fn from(value: u128) -> Self
+	{
+		Uint128::new_unchecked(Bytes::from(value.to_le_bytes().to_vec()))
+	}
+}
+
+/// Prefix a Molecule encoded entity with the version byte.
+pub fn encode_versioned<T: Entity>(entity: &T) -> Vec<u8>
+{
+	encode_with_version(ORDER_VERSION, entity)
+}
+
+/// Prefix a Molecule encoded entity with an earlier version byte, such as for the remainder of an order created with that version.
+pub fn encode_with_version<T: Entity>(version: u8, entity: &T) -> Vec<u8>
+{
+	let mut data = Vec::with_capacity(1 + entity.as_slice().len());
+	data.push(version);
+	data.extend_from_slice(entity.as_slice());
+
+	data
+}